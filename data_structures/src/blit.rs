@@ -0,0 +1,259 @@
+use memory_math::memory_index2d::MemIndex2D;
+use memory_math::memory_offset2d::MemOffset2D;
+use memory_math::memory_span2d::MemSpan2D;
+use memory_math::size_2d::{HasSize2D, Size2D};
+use crate::pitched_vec2d::PitchedVec2D;
+use crate::vec2d::{Vec2D, Vec2DMutSlice, Vec2DSlice};
+use crate::vec2d_view::{Vec2DView, Vec2DViewMut};
+
+/// A 2d container that can hand out each of its rows as one contiguous slice
+pub trait Rows2D<T>: HasSize2D {
+    fn row_slice(&self, row: usize) -> Option<&[T]>;
+}
+
+/// A 2d container whose rows can be written as contiguous slices, the target of a blit
+pub trait Rows2DMut<T>: Rows2D<T> {
+    fn row_slice_mut(&mut self, row: usize) -> Option<&mut [T]>;
+
+    /// Copy src onto self with its top left cell at at, one clone_from_slice per row.
+    /// Cells falling outside of self are clipped, returns the span of self that was written, None if nothing was.
+    fn blit<S: Rows2D<T> + ?Sized>(&mut self, src: &S, at: MemOffset2D) -> Option<MemSpan2D>
+    where
+        T: Clone,
+    {
+        blit_rows(self, src, at, |dst_row, src_row, _| dst_row.clone_from_slice(src_row))
+    }
+
+    /// Like blit, but every written cell is combined with its source cell by blend instead of overwritten
+    fn blit_with<U, S, F>(&mut self, src: &S, at: MemOffset2D, mut blend: F) -> Option<MemSpan2D>
+    where
+        S: Rows2D<U> + ?Sized,
+        F: FnMut(&mut T, &U),
+    {
+        blit_rows(self, src, at, |dst_row, src_row, _| {
+            dst_row.iter_mut().zip(src_row).for_each(|(dst, src)| blend(dst, src));
+        })
+    }
+
+    /// Like blit, but only the cells whose mask cell is true are copied.
+    /// The returned span covers the clipped rectangle, masked out cells in it are left untouched.
+    /// None if mask is not the size of src.
+    fn blit_masked<S, M>(&mut self, src: &S, mask: &M, at: MemOffset2D) -> Option<MemSpan2D>
+    where
+        T: Clone,
+        S: Rows2D<T> + ?Sized,
+        M: Rows2D<bool> + ?Sized,
+    {
+        if mask.size() != src.size() {
+            return None;
+        }
+
+        blit_rows(self, src, at, |dst_row, src_row, src_span| {
+            let mask_row: &[bool] = &mask.row_slice(src_span.0).unwrap()[src_span.1..src_span.1 + src_row.len()];
+            for ((dst, src), keep) in dst_row.iter_mut().zip(src_row).zip(mask_row) {
+                if *keep {
+                    dst.clone_from(src);
+                }
+            }
+        })
+    }
+}
+
+/// The part of a src of size placed at at that lands inside dst, as the span of dst and the top left cell of src
+pub(crate) fn clip(src: Size2D, dst: Size2D, at: MemOffset2D) -> Option<(MemSpan2D, MemIndex2D)> {
+    let clip_axis = |offset: isize, src_len: usize, dst_len: usize| -> Option<(usize, usize)> {
+        let min: isize = offset.max(0);
+        let max: isize = offset.checked_add(isize::try_from(src_len).ok()?)?.min(isize::try_from(dst_len).ok()?);
+        (min < max).then_some((min as usize, max as usize))
+    };
+
+    let (row_min, row_max) = clip_axis(at.row, src.row_count, dst.row_count)?;
+    let (col_min, col_max) = clip_axis(at.col, src.column_count, dst.column_count)?;
+
+    let src_min = MemIndex2D::new(row_min.checked_add_signed(-at.row)?, col_min.checked_add_signed(-at.col)?);
+    Some((MemSpan2D::new_from_usize(row_min, col_min, row_max, col_max), src_min))
+}
+
+/// Hand every clipped pair of rows to copy_row, along with the source row and column the pair starts at
+fn blit_rows<T, U, D, S>(
+    dst: &mut D,
+    src: &S,
+    at: MemOffset2D,
+    mut copy_row: impl FnMut(&mut [T], &[U], (usize, usize)),
+) -> Option<MemSpan2D>
+where
+    D: Rows2DMut<T> + ?Sized,
+    S: Rows2D<U> + ?Sized,
+{
+    let (written, src_min) = clip(src.size(), dst.size(), at)?;
+    let (col_min, width) = (written.col_span.min, written.col_span.len());
+
+    for (i, dst_row) in written.row_span.into_iter().enumerate() {
+        let src_row: usize = src_min.row + i;
+        let src_cells: &[U] = &src.row_slice(src_row).unwrap()[src_min.col..src_min.col + width];
+        let dst_cells: &mut [T] = &mut dst.row_slice_mut(dst_row).unwrap()[col_min..col_min + width];
+        copy_row(dst_cells, src_cells, (src_row, src_min.col));
+    }
+
+    Some(written)
+}
+
+impl<T> Rows2D<T> for Vec2D<T> {
+    fn row_slice(&self, row: usize) -> Option<&[T]> {
+        self.get_row(row)
+    }
+}
+
+impl<T> Rows2DMut<T> for Vec2D<T> {
+    fn row_slice_mut(&mut self, row: usize) -> Option<&mut [T]> {
+        self.get_row_mut(row)
+    }
+}
+
+impl<'a, T> Rows2D<T> for Vec2DSlice<'a, T> {
+    fn row_slice(&self, row: usize) -> Option<&[T]> {
+        self.get_span_row(row)
+    }
+}
+
+impl<'a, T> Rows2D<T> for Vec2DMutSlice<'a, T> {
+    fn row_slice(&self, row: usize) -> Option<&[T]> {
+        self.get_span_row(row)
+    }
+}
+
+impl<'a, T> Rows2DMut<T> for Vec2DMutSlice<'a, T> {
+    fn row_slice_mut(&mut self, row: usize) -> Option<&mut [T]> {
+        self.get_span_row_mut(row)
+    }
+}
+
+impl<'a, T> Rows2D<T> for Vec2DView<'a, T> {
+    fn row_slice(&self, row: usize) -> Option<&[T]> {
+        self.get_span_row(row)
+    }
+}
+
+impl<'a, T> Rows2D<T> for Vec2DViewMut<'a, T> {
+    fn row_slice(&self, row: usize) -> Option<&[T]> {
+        self.get_span_row(row)
+    }
+}
+
+impl<'a, T> Rows2DMut<T> for Vec2DViewMut<'a, T> {
+    fn row_slice_mut(&mut self, row: usize) -> Option<&mut [T]> {
+        self.get_span_row_mut(row)
+    }
+}
+
+impl<T> Rows2D<T> for PitchedVec2D<T> {
+    fn row_slice(&self, row: usize) -> Option<&[T]> {
+        self.get_row(row)
+    }
+}
+
+impl<T> Rows2DMut<T> for PitchedVec2D<T> {
+    fn row_slice_mut(&mut self, row: usize) -> Option<&mut [T]> {
+        self.get_row_mut(row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: usize, columns: usize, value: i32) -> Vec2D<i32> {
+        Vec2D::new_items_size(vec![value; rows * columns], Size2D::new(rows, columns)).unwrap()
+    }
+
+    fn counting(rows: usize, columns: usize) -> Vec2D<i32> {
+        Vec2D::new_items_size((1..=(rows * columns) as i32).collect(), Size2D::new(rows, columns)).unwrap()
+    }
+
+    #[test]
+    fn test_blit_clips() {
+        let mut dst = grid(4, 5, 0);
+        let src = counting(3, 3);
+
+        assert_eq!(Some(MemSpan2D::new_from_usize(1, 1, 4, 4)), dst.blit(&src, MemOffset2D::new(1, 1)));
+        assert_eq!(&[0, 4, 5, 6, 0], dst.row_slice(2).unwrap());
+
+        //hanging off the top left corner only writes the bottom right of src
+        let written = dst.blit(&src, MemOffset2D::new(-2, -1));
+        assert_eq!(Some(MemSpan2D::new_from_usize(0, 0, 1, 2)), written);
+        assert_eq!(&[8, 9, 0, 0, 0], dst.row_slice(0).unwrap());
+
+        //hanging off the bottom right corner only writes the top left of src
+        assert_eq!(Some(MemSpan2D::new_from_usize(3, 4, 4, 5)), dst.blit(&src, MemOffset2D::new(3, 4)));
+        assert_eq!(1, dst[MemIndex2D::new(3, 4)]);
+
+        assert!(dst.blit(&src, MemOffset2D::new(4, 0)).is_none());
+        assert!(dst.blit(&src, MemOffset2D::new(0, -3)).is_none());
+        assert!(dst.blit(&src, MemOffset2D::new(isize::MAX, isize::MIN)).is_none());
+        assert!(dst.blit(&grid(0, 0, 1), MemOffset2D::new(0, 0)).is_none());
+    }
+
+    #[test]
+    fn test_push_range_clips_like_blit() {
+        let mut dst = grid(3, 4, 0);
+        dst.push_range(MemIndex2D::new(1, 2), counting(3, 3));
+        assert_eq!(&[0, 0, 0, 0], dst.row_slice(0).unwrap());
+        assert_eq!(&[0, 0, 1, 2], dst.row_slice(1).unwrap());
+        assert_eq!(&[0, 0, 4, 5], dst.row_slice(2).unwrap());
+
+        //cells that do not need to be cloned can be pushed too, a range starting past self writes nothing
+        let mut names = Vec2D::new_items_size(vec![String::new(); 4], Size2D::new(2, 2)).unwrap();
+        names.push_range(MemIndex2D::new(0, 1), Vec2D::new_items_size(vec![String::from("a"), String::from("b")], Size2D::new(2, 1)).unwrap());
+        names.push_range(MemIndex2D::new(2, 0), Vec2D::new_items_size(vec![String::from("c")], Size2D::new(1, 1)).unwrap());
+        assert_eq!(&[String::new(), String::from("b")], names.row_slice(1).unwrap());
+    }
+
+    #[test]
+    fn test_blit_between_containers() {
+        let src = counting(4, 4);
+        let slice = src.get_slice(MemSpan2D::new_from_usize(1, 1, 3, 3)).unwrap();
+
+        let mut pitched = PitchedVec2D::new_size_reference(Size2D::new(3, 3), 32, &0).unwrap();
+        assert!(pitched.blit(&slice, MemOffset2D::new(0, 1)).is_some());
+        assert_eq!(&[0, 6, 7], pitched.row_slice(0).unwrap());
+        assert_eq!(&[0, 10, 11], pitched.row_slice(1).unwrap());
+
+        let mut dst = grid(4, 4, 0);
+        {
+            let mut corner = dst.get_slice_mut(MemSpan2D::new_from_usize(2, 2, 4, 4)).unwrap();
+            let written = corner.blit(&pitched.as_view(), MemOffset2D::new(0, -1));
+            assert_eq!(Some(MemSpan2D::new_from_usize(0, 0, 2, 2)), written);
+        }
+        assert_eq!(&[0, 0, 6, 7], dst.row_slice(2).unwrap());
+        assert_eq!(&[0, 0, 10, 11], dst.row_slice(3).unwrap());
+    }
+
+    #[test]
+    fn test_blend_and_mask() {
+        let mut dst = grid(3, 3, 100);
+        let src = counting(2, 2);
+
+        let written = dst.blit_with(&src, MemOffset2D::new(1, 1), |dst, src| *dst += *src);
+        assert_eq!(Some(MemSpan2D::new_from_usize(1, 1, 3, 3)), written);
+        assert_eq!(&[100, 103, 104], dst.row_slice(2).unwrap());
+
+        //source cells of another type can be blended in
+        let alpha = Vec2D::new_items_size(vec![0.5f32; 9], Size2D::new(3, 3)).unwrap();
+        dst.blit_with(&alpha, MemOffset2D::new(0, 0), |dst, a| *dst = (*dst as f32 * a) as i32);
+        assert_eq!(&[50, 51, 52], dst.row_slice(2).unwrap());
+
+        let mask = Vec2D::new_items_size(vec![true, false, false, true], Size2D::new(2, 2)).unwrap();
+        assert!(dst.blit_masked(&src, &mask, MemOffset2D::new(0, 0)).is_some());
+        assert_eq!(&[1, 50, 50], dst.row_slice(0).unwrap());
+        assert_eq!(&[50, 4, 51], dst.row_slice(1).unwrap());
+
+        //the mask follows the source when it is clipped
+        assert!(dst.blit_masked(&src, &mask, MemOffset2D::new(-1, 2)).is_some());
+        assert_eq!(&[1, 50, 50], dst.row_slice(0).unwrap());
+        assert!(dst.blit_masked(&src, &mask, MemOffset2D::new(-1, 1)).is_some());
+        assert_eq!(&[1, 50, 4], dst.row_slice(0).unwrap());
+
+        let wide_mask = Vec2D::new_items_size(vec![true; 6], Size2D::new(2, 3)).unwrap();
+        assert!(dst.blit_masked(&src, &wide_mask, MemOffset2D::new(0, 0)).is_none());
+    }
+}
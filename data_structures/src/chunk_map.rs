@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use memory_math::memory_index2d::MemIndex2D;
+use memory_math::memory_span2d::MemSpan2D;
+use memory_math::size_2d::Size2D;
+use memory_math::world_coord::{WorldCoord2D, WorldSpan2D};
+use crate::vec2d::{Vec2D, Vec2DSlice};
+
+///Unbounded 2D map made of equally sized Vec2D chunks, created on first write.
+/// Cells of chunks that were never written read as the fill value.
+pub struct ChunkMap<T> {
+    chunk_size: Size2D,
+    fill: T,
+    chunks: HashMap<WorldCoord2D, Vec2D<T>>,
+}
+
+///The part of a world region that falls inside one chunk
+pub struct ChunkRegion<'a, T> {
+    pub chunk: WorldCoord2D,
+    pub world_span: WorldSpan2D,
+    pub local_span: MemSpan2D,
+    ///None when the chunk has not been created yet
+    pub data: Option<Vec2DSlice<'a, T>>,
+}
+
+impl<T> ChunkMap<T> {
+
+    ///None if the chunk size is empty or too large to address with signed coordinates
+    pub fn new(chunk_size: Size2D, fill: T) -> Option<Self> {
+        if chunk_size.area().is_none_or(|area| area == 0)
+            || isize::try_from(chunk_size.row_count).is_err()
+            || isize::try_from(chunk_size.column_count).is_err()
+        {
+            return None;
+        }
+
+        Some(ChunkMap {
+            chunk_size,
+            fill,
+            chunks: HashMap::new(),
+        })
+    }
+
+    pub fn chunk_size(&self) -> Size2D {
+        self.chunk_size
+    }
+
+    pub fn fill_value(&self) -> &T {
+        &self.fill
+    }
+
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    pub fn chunk(&self, chunk: WorldCoord2D) -> Option<&Vec2D<T>> {
+        self.chunks.get(&chunk)
+    }
+
+    pub fn chunk_mut(&mut self, chunk: WorldCoord2D) -> Option<&mut Vec2D<T>> {
+        self.chunks.get_mut(&chunk)
+    }
+
+    pub fn remove_chunk(&mut self, chunk: WorldCoord2D) -> Option<Vec2D<T>> {
+        self.chunks.remove(&chunk)
+    }
+
+    pub fn chunks(&self) -> impl Iterator<Item = (&WorldCoord2D, &Vec2D<T>)> {
+        self.chunks.iter()
+    }
+
+    ///World cells covered by a chunk
+    pub fn chunk_world_span(&self, chunk: WorldCoord2D) -> Option<WorldSpan2D> {
+        let min = WorldCoord2D::from_chunk(chunk, MemIndex2D::origin(), &self.chunk_size)?;
+        let span = WorldSpan2D::new(min, self.chunk_size);
+        span.upper_bound()?;
+        Some(span)
+    }
+
+    ///Chunk holding a world cell and the cell's index inside that chunk
+    pub fn locate(&self, coord: WorldCoord2D) -> Option<(WorldCoord2D, MemIndex2D)> {
+        coord.to_chunk(&self.chunk_size)
+    }
+
+    ///None if the cell lies in a chunk that has not been created
+    pub fn get(&self, coord: WorldCoord2D) -> Option<&T> {
+        let (chunk, local) = self.locate(coord)?;
+        self.chunks.get(&chunk)?.get_index2d(local)
+    }
+
+    ///Cell value, falling back to the fill value for missing chunks
+    pub fn get_or_fill(&self, coord: WorldCoord2D) -> &T {
+        self.get(coord).unwrap_or(&self.fill)
+    }
+
+    pub fn get_mut(&mut self, coord: WorldCoord2D) -> Option<&mut T> {
+        let (chunk, local) = self.locate(coord)?;
+        Some(&mut self.chunks.get_mut(&chunk)?[local])
+    }
+
+    ///Split a world region into its per chunk parts, in row major chunk order
+    pub fn region_chunks(&self, span: &WorldSpan2D) -> impl Iterator<Item = ChunkRegion<'_, T>> + '_ {
+        let chunk_span: Option<WorldSpan2D> = span.chunk_span(&self.chunk_size);
+        let span: WorldSpan2D = *span;
+
+        chunk_span.into_iter()
+            .flat_map(|chunk_span| chunk_span.coords().collect::<Vec<_>>())
+            .filter_map(move |chunk| {
+                let chunk_world_span: WorldSpan2D = self.chunk_world_span(chunk)?;
+                let world_span: WorldSpan2D = chunk_world_span.intersect(&span)?;
+                let local_span: MemSpan2D = world_span.to_mem_span2d(chunk_world_span.min)?;
+                let data = self.chunks.get(&chunk).and_then(|c| c.get_slice(local_span.clone()));
+
+                Some(ChunkRegion { chunk, world_span, local_span, data })
+            })
+    }
+
+    ///Every cell of a world region with its value, chunk by chunk and row major inside each chunk.
+    /// Cells of missing chunks yield the fill value.
+    pub fn region(&self, span: &WorldSpan2D) -> impl Iterator<Item = (WorldCoord2D, &T)> + '_ {
+        self.region_chunks(span).flat_map(move |part| {
+            //existing chunks walk the row runs of their slice, missing ones repeat the fill value.
+            //a part never outgrows its chunk, whose area new checked
+            let fill_len: usize = if part.data.is_some() { 0 } else { part.world_span.area().unwrap_or(0) };
+            let values = part.data.into_iter().flatten().chain(std::iter::repeat_n(&self.fill, fill_len));
+            part.world_span.coords().zip(values)
+        })
+    }
+}
+
+impl<T: Clone> ChunkMap<T> {
+
+    ///Mutable access to a cell, creating its chunk filled with the fill value if needed
+    pub fn get_mut_or_insert(&mut self, coord: WorldCoord2D) -> Option<&mut T> {
+        let (chunk, local) = self.locate(coord)?;
+        let chunk_size: Size2D = self.chunk_size;
+        let fill: &T = &self.fill;
+
+        let data: &mut Vec2D<T> = self.chunks.entry(chunk)
+            .or_insert_with(|| Vec2D::new_size_reference(chunk_size, fill));
+        Some(&mut data[local])
+    }
+
+    ///Write a cell, returning the previous value
+    pub fn set(&mut self, coord: WorldCoord2D, value: T) -> Option<T> {
+        Some(std::mem::replace(self.get_mut_or_insert(coord)?, value))
+    }
+
+    ///Write every cell of a world region, creating chunks as needed
+    pub fn fill_region(&mut self, span: &WorldSpan2D, value: &T) {
+        for coord in span.coords() {
+            if let Some(cell) = self.get_mut_or_insert(coord) {
+                *cell = value.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negative_cells_round_trip() {
+        let mut map: ChunkMap<i32> = ChunkMap::new(Size2D::new(4, 4), 0).unwrap();
+        assert_eq!(&0, map.get_or_fill(WorldCoord2D::new(-100, 7)));
+        assert!(map.get(WorldCoord2D::new(-1, -1)).is_none());
+
+        assert_eq!(Some(0), map.set(WorldCoord2D::new(-1, -1), 5));
+        assert_eq!(Some(0), map.set(WorldCoord2D::new(0, 0), 6));
+        assert_eq!(Some(&5), map.get(WorldCoord2D::new(-1, -1)));
+        assert_eq!(Some(&6), map.get(WorldCoord2D::origin()));
+        assert_eq!(2, map.chunk_count());
+
+        let chunk = map.chunk(WorldCoord2D::new(-1, -1)).unwrap();
+        assert_eq!(5, chunk[MemIndex2D::new(3, 3)]);
+        assert!(ChunkMap::new(Size2D::new(0, 4), 0).is_none());
+    }
+
+    #[test]
+    fn test_region_across_chunks() {
+        let mut map: ChunkMap<i32> = ChunkMap::new(Size2D::new(4, 4), 0).unwrap();
+        let span = WorldSpan2D::from_bounds(WorldCoord2D::new(-2, -2), WorldCoord2D::new(2, 3)).unwrap();
+        map.fill_region(&WorldSpan2D::new(WorldCoord2D::new(-2, -2), Size2D::new(2, 2)), &1);
+
+        let parts: Vec<ChunkRegion<'_, i32>> = map.region_chunks(&span).collect();
+        assert_eq!(4, parts.len());
+        assert_eq!(WorldCoord2D::new(-1, -1), parts[0].chunk);
+        assert_eq!(MemSpan2D::new_from_usize(2, 2, 4, 4), parts[0].local_span);
+        assert!(parts[0].data.is_some());
+        assert!(parts[3].data.is_none());
+        assert_eq!(20, parts.iter().map(|p| p.world_span.area().unwrap()).sum::<usize>());
+
+        let cells: Vec<(WorldCoord2D, &i32)> = map.region(&span).collect();
+        assert_eq!(20, cells.len());
+        assert_eq!(4, cells.iter().filter(|(_, v)| **v == 1).count());
+        assert_eq!((WorldCoord2D::new(-2, -2), &1), cells[0]);
+
+        map.set(WorldCoord2D::new(1, 2), 7);
+        let cells: Vec<(WorldCoord2D, &i32)> = map.region(&span).collect();
+        assert!(cells.iter().all(|(coord, v)| map.get_or_fill(*coord) == *v));
+        assert_eq!(5, cells.iter().filter(|(_, v)| **v != 0).count());
+    }
+}
@@ -1,3 +1,15 @@
 pub mod tile_map;
 pub mod vec2d;
 pub mod vec2d_iter;
+pub mod blit;
+pub mod vec2d_transform;
+pub mod vec2d_view;
+pub mod pitched_vec2d;
+pub mod wrapped_vec2d;
+pub mod neighborhood_iter;
+pub mod quad_tree;
+pub mod vec3d;
+pub mod chunk_map;
+pub mod morton_vec2d;
+#[cfg(feature = "rayon")]
+pub mod par_iter;
@@ -0,0 +1,298 @@
+use std::ops::{Index, IndexMut};
+
+use memory_math::memory_index2d::MemIndex2D;
+use memory_math::memory_iterators::LinearMemoryIterator;
+use memory_math::memory_span::MemSpan;
+use memory_math::memory_span2d::MemSpan2D;
+use memory_math::morton;
+use memory_math::size_2d::{HasSize2D, Size2D};
+use crate::vec2d::Vec2D;
+
+///Square power of two tiles laid out row major, each stored along the Morton curve.
+/// The side is the shorter axis rounded up, so long thin grids do not pad out to a square.
+#[derive(Clone, Copy)]
+struct MortonTiles {
+    shift: u32,
+    tile_columns: usize,
+}
+
+impl MortonTiles {
+
+    ///Tiling for size and the number of cells it stores, None if that does not fit in usize
+    fn for_size(size: &Size2D) -> Option<(MortonTiles, usize)> {
+        let side: usize = size.row_count.min(size.column_count).checked_next_power_of_two()?;
+        let tile_len: usize = side.checked_mul(side)?;
+        morton::encode(MemIndex2D::new(side - 1, side - 1))?;
+
+        let tiles = MortonTiles { shift: side.trailing_zeros(), tile_columns: size.column_count.div_ceil(side) };
+        let storage_len: usize = size.row_count.div_ceil(side).checked_mul(tiles.tile_columns)?.checked_mul(tile_len)?;
+        Some((tiles, storage_len))
+    }
+
+    fn side(&self) -> usize {
+        1 << self.shift
+    }
+
+    fn tile_start(&self, tile_row: usize, tile_col: usize) -> usize {
+        (tile_row * self.tile_columns + tile_col) << (2 * self.shift)
+    }
+
+    fn storage_index(&self, index2d: MemIndex2D) -> usize {
+        let mask: usize = self.side() - 1;
+        let local: u64 = morton::encode(MemIndex2D::new(index2d.row & mask, index2d.col & mask)).unwrap();
+        self.tile_start(index2d.row >> self.shift, index2d.col >> self.shift) + local as usize
+    }
+
+    fn index2d(&self, storage_index: usize) -> MemIndex2D {
+        let tile: usize = storage_index >> (2 * self.shift);
+        let local: MemIndex2D = morton::decode((storage_index & ((1 << (2 * self.shift)) - 1)) as u64);
+        MemIndex2D::new(((tile / self.tile_columns) << self.shift) + local.row, ((tile % self.tile_columns) << self.shift) + local.col)
+    }
+}
+
+///Grid stored along the Morton (Z-order) curve so that nearby cells share cache lines.
+/// Cells are kept in square Morton tiles as wide as the shorter axis rounded up to a power of two,
+/// so padding stays below four times the cell count whatever the shape. Padding cells are never handed out.
+#[derive(Clone)]
+pub struct MortonVec2D<T> {
+    pub size: Size2D,
+    tiles: MortonTiles,
+    items: Vec<T>,
+}
+
+impl<T> HasSize2D for MortonVec2D<T> {
+    fn row_count(&self) -> usize {
+        self.size.row_count
+    }
+
+    fn column_count(&self) -> usize {
+        self.size.column_count
+    }
+}
+
+impl<T> Index<MemIndex2D> for MortonVec2D<T> {
+    type Output = T;
+
+    fn index(&self, index: MemIndex2D) -> &Self::Output {
+        match self.get(index) {
+            Some(val) => val,
+            None => panic!(
+                "Index2d out of bounds. Index was {} but the size is {}",
+                index,
+                self.size
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<MemIndex2D> for MortonVec2D<T> {
+    fn index_mut(&mut self, index: MemIndex2D) -> &mut Self::Output {
+        let extents = self.size;
+        match self.get_mut(index) {
+            Some(v) => v,
+            None => panic!(
+                "Index2d out of bounds. Index was {} but the size is {}",
+                index, extents
+            ),
+        }
+    }
+}
+
+impl<T> MortonVec2D<T> {
+
+    ///None if the shorter axis needs more than 32 bits or the padded storage does not fit in usize
+    pub fn new_size_reference(size: Size2D, ref_item: &T) -> Option<Self>
+    where
+        T: Clone,
+    {
+        let (tiles, storage_len) = MortonTiles::for_size(&size)?;
+        Some(MortonVec2D {
+            items: vec![ref_item.clone(); storage_len],
+            tiles,
+            size,
+        })
+    }
+
+    ///Copy a row major grid into Morton order, padding cells are filled with clones of the first cell
+    pub fn from_vec2d(vec2d: &Vec2D<T>) -> Option<Self>
+    where
+        T: Clone,
+    {
+        let first: &T = vec2d.get_index2d(MemIndex2D::origin())?;
+        let mut morton_vec: MortonVec2D<T> = MortonVec2D::new_size_reference(vec2d.size, first)?;
+
+        for row in 0..vec2d.row_count() {
+            for col in 0..vec2d.column_count() {
+                let index2d = MemIndex2D::new(row, col);
+                morton_vec[index2d] = vec2d[index2d].clone();
+            }
+        }
+
+        Some(morton_vec)
+    }
+
+    pub fn to_vec2d(&self) -> Vec2D<T>
+    where
+        T: Clone,
+    {
+        let items: Vec<T> = LinearMemoryIterator::new(self.size.into())
+            .map(|index2d| self[index2d].clone())
+            .collect();
+        Vec2D::new_items_size(items, self.size).unwrap()
+    }
+
+    ///Number of stored cells, padding included
+    pub fn storage_len(&self) -> usize {
+        self.items.len()
+    }
+
+    ///Position of a cell in storage
+    #[inline]
+    pub fn storage_index(&self, index2d: MemIndex2D) -> Option<usize> {
+        if !self.size.index2d_in_bounds(&index2d) {
+            return None;
+        }
+
+        Some(self.tiles.storage_index(index2d))
+    }
+
+    pub fn get(&self, index2d: MemIndex2D) -> Option<&T> {
+        self.items.get(self.storage_index(index2d)?)
+    }
+
+    pub fn get_mut(&mut self, index2d: MemIndex2D) -> Option<&mut T> {
+        let storage_index: usize = self.storage_index(index2d)?;
+        self.items.get_mut(storage_index)
+    }
+
+    ///Cells in storage order with their index, padding cells are skipped
+    pub fn iter(&self) -> impl Iterator<Item = (MemIndex2D, &T)> {
+        self.items.iter().enumerate().filter_map(move |(storage_index, item)| {
+            let index2d: MemIndex2D = self.tiles.index2d(storage_index);
+            self.size.index2d_in_bounds(&index2d).then_some((index2d, item))
+        })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (MemIndex2D, &mut T)> {
+        let (size, tiles) = (self.size, self.tiles);
+        self.items.iter_mut().enumerate().filter_map(move |(storage_index, item)| {
+            let index2d: MemIndex2D = tiles.index2d(storage_index);
+            size.index2d_in_bounds(&index2d).then_some((index2d, item))
+        })
+    }
+
+    ///Contiguous storage runs covering exactly the cells of span, in storage order.
+    /// Runs that carry on from one tile into the next are joined.
+    pub fn span_runs(&self, span2d: &MemSpan2D) -> Option<Vec<MemSpan>> {
+        if !span2d.valid() || span2d.max_row()? >= self.row_count() || span2d.max_column()? >= self.column_count() {
+            return None;
+        }
+
+        let (shift, side) = (self.tiles.shift, self.tiles.side());
+        let mut runs: Vec<MemSpan> = Vec::new();
+        for tile_row in span2d.min_row() >> shift..=span2d.max_row()? >> shift {
+            for tile_col in span2d.min_column() >> shift..=span2d.max_column()? >> shift {
+                let origin = MemIndex2D::new(tile_row << shift, tile_col << shift);
+                let tile: MemSpan2D = MemSpan2D::new_from_index2d(origin, MemIndex2D::new(origin.row + side, origin.col + side));
+                let covered: MemSpan2D = span2d.intersect(&tile)?;
+                let local = MemSpan2D::new_from_usize(
+                    covered.min_row() - origin.row,
+                    covered.min_column() - origin.col,
+                    covered.row_span.upper_bound() - origin.row,
+                    covered.col_span.upper_bound() - origin.col,
+                );
+
+                let tile_start: usize = self.tiles.tile_start(tile_row, tile_col);
+                for run in morton::span_runs(&local)? {
+                    let run = MemSpan::new_range(tile_start + usize::try_from(run.min).ok()?..tile_start + usize::try_from(run.upper_bound()).ok()?);
+                    match runs.last_mut() {
+                        Some(last) if last.upper_bound() == run.min => *last = MemSpan::new_range(last.min..run.upper_bound()),
+                        _ => runs.push(run),
+                    }
+                }
+            }
+        }
+
+        Some(runs)
+    }
+
+    ///Storage slices of a span, one per contiguous Morton run
+    pub fn span_slices(&self, span2d: &MemSpan2D) -> Option<Vec<&[T]>> {
+        let runs: Vec<MemSpan> = self.span_runs(span2d)?;
+        Some(runs.into_iter().map(|run| &self.items[run.min..run.upper_bound()]).collect())
+    }
+
+    ///Cells of a span with their index, in storage order
+    pub fn span_iter(&self, span2d: &MemSpan2D) -> Option<impl Iterator<Item = (MemIndex2D, &T)>> {
+        let runs: Vec<MemSpan> = self.span_runs(span2d)?;
+        Some(runs.into_iter().flat_map(move |run| {
+            run.into_iter().map(move |storage_index| (self.tiles.index2d(storage_index), &self.items[storage_index]))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_order() {
+        let vec2d = Vec2D::new_items_size((0..15).collect::<Vec<i32>>(), Size2D::new(3, 5)).unwrap();
+        let morton_vec = MortonVec2D::from_vec2d(&vec2d).unwrap();
+
+        assert_eq!(7, morton_vec[MemIndex2D::new(1, 2)]);
+        assert!(morton_vec.get(MemIndex2D::new(3, 0)).is_none());
+        assert!(morton_vec.storage_len() >= 15);
+
+        let order: Vec<i32> = morton_vec.iter().map(|(_, v)| *v).take(6).collect();
+        assert_eq!(vec![0, 1, 5, 6, 2, 3], order);
+        assert_eq!(15, morton_vec.iter().count());
+        assert!(morton_vec.iter().all(|(index2d, v)| vec2d[index2d] == *v));
+
+        let back = morton_vec.to_vec2d();
+        assert!((0..15).all(|i| back[i] == vec2d[i]));
+    }
+
+    #[test]
+    fn test_span_queries() {
+        let size = Size2D::new(16, 16);
+        let items: Vec<usize> = (0..size.area().unwrap()).collect();
+        let morton_vec = MortonVec2D::from_vec2d(&Vec2D::new_items_size(items, size).unwrap()).unwrap();
+        assert_eq!(256, morton_vec.storage_len());
+
+        let span = MemSpan2D::new_from_usize(4, 4, 8, 8);
+        assert_eq!(1, morton_vec.span_slices(&span).unwrap().len());
+
+        let span = MemSpan2D::new_from_usize(3, 2, 11, 13);
+        let cells: Vec<(MemIndex2D, &usize)> = morton_vec.span_iter(&span).unwrap().collect();
+        assert_eq!(span.area(), Some(cells.len()));
+        assert!(cells.iter().all(|(index2d, v)| span.contains_index2d(index2d) && **v == index2d.row * 16 + index2d.col));
+
+        let slices = morton_vec.span_slices(&span).unwrap();
+        assert!(slices.len() < span.area().unwrap() / 2);
+        assert!(morton_vec.span_runs(&MemSpan2D::new_from_usize(10, 10, 17, 12)).is_none());
+    }
+
+    #[test]
+    fn test_non_square_storage() {
+        let line: MortonVec2D<u8> = MortonVec2D::new_size_reference(Size2D::new(1, 65536), &0).unwrap();
+        assert_eq!(65536, line.storage_len());
+
+        let size = Size2D::new(5, 37);
+        let items: Vec<usize> = (0..size.area().unwrap()).collect();
+        let morton_vec = MortonVec2D::from_vec2d(&Vec2D::new_items_size(items, size).unwrap()).unwrap();
+        assert_eq!(8 * 40, morton_vec.storage_len());
+        assert_eq!(size.area(), Some(morton_vec.iter().count()));
+        assert!(morton_vec.iter().all(|(index2d, v)| *v == index2d.row * 37 + index2d.col));
+
+        //the span crosses several tiles, its runs still cover each cell exactly once
+        let span = MemSpan2D::new_from_usize(1, 6, 5, 30);
+        let cells: Vec<(MemIndex2D, &usize)> = morton_vec.span_iter(&span).unwrap().collect();
+        assert_eq!(span.area(), Some(cells.len()));
+        assert!(cells.iter().all(|(index2d, v)| span.contains_index2d(index2d) && **v == index2d.row * 37 + index2d.col));
+
+        //whole tiles in a row join into one run
+        let tall = MortonVec2D::new_size_reference(Size2D::new(12, 4), &0).unwrap();
+        assert_eq!(vec![MemSpan::new_range(0..48)], tall.span_runs(&MemSpan2D::new_row_columns(12, 4)).unwrap());
+    }
+}
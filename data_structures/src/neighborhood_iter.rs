@@ -0,0 +1,186 @@
+use std::slice;
+
+use memory_math::memory_index2d::MemIndex2D;
+use memory_math::memory_offset2d::MemOffset2D;
+use memory_math::neighborhood::Neighborhood;
+use memory_math::size_2d::{HasSize2D, Size2D};
+use crate::vec2d::Vec2D;
+
+/// What a neighbor lookup does when the offset leaves the grid
+pub enum BoundaryPolicy<'a, T> {
+    /// Leave the neighbor out
+    Skip,
+    /// Use the nearest cell on the edge
+    Clamp,
+    /// Continue from the opposite edge, as on a torus
+    Wrap,
+    /// Yield this value, paired with the nearest index on the edge
+    Constant(&'a T),
+}
+
+impl<'a, T> Clone for BoundaryPolicy<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for BoundaryPolicy<'a, T> {}
+
+fn clamp_index2d(center: MemIndex2D, offset: MemOffset2D, size: Size2D) -> MemIndex2D {
+    let clamp = |at: usize, by: isize, len: usize| at.saturating_add_signed(by).min(len - 1);
+    MemIndex2D::new(clamp(center.row, offset.row, size.row_count), clamp(center.col, offset.col, size.column_count))
+}
+
+/// The neighbors of one cell of a Vec2D, in the order of the neighborhood's offsets.
+/// Centers far enough from every edge take a fast path without bounds checks or boundary handling.
+pub struct NeighborhoodIter<'a, 'n, T> {
+    grid: &'a Vec2D<T>,
+    center: MemIndex2D,
+    offsets: slice::Iter<'n, MemOffset2D>,
+    policy: BoundaryPolicy<'a, T>,
+    interior: bool,
+}
+
+impl<'a, 'n, T> NeighborhoodIter<'a, 'n, T> {
+
+    /// None if center lies outside of grid
+    pub fn new(grid: &'a Vec2D<T>, center: MemIndex2D, neighborhood: &'n Neighborhood, policy: BoundaryPolicy<'a, T>) -> Option<Self> {
+        if !grid.size.index2d_in_bounds(&center) {
+            return None;
+        }
+
+        let interior: bool = neighborhood.is_interior(center, grid.size);
+        Some(NeighborhoodIter { grid, center, offsets: neighborhood.offsets().iter(), policy, interior })
+    }
+
+    pub fn center(&self) -> MemIndex2D {
+        self.center
+    }
+
+    fn interior_neighbor(&self, offset: MemOffset2D) -> (MemIndex2D, &'a T) {
+        let index2d = MemIndex2D::new(self.center.row.wrapping_add_signed(offset.row), self.center.col.wrapping_add_signed(offset.col));
+        let grid: &'a Vec2D<T> = self.grid;
+        (index2d, &grid.items()[Size2D::index2d_to_index_unchecked(grid.column_count(), index2d)])
+    }
+
+    fn edge_neighbor(&self, offset: MemOffset2D) -> Option<(MemIndex2D, &'a T)> {
+        let grid: &'a Vec2D<T> = self.grid;
+        let inside: Option<MemIndex2D> = (self.center + offset).filter(|index2d| grid.size.index2d_in_bounds(index2d));
+        if let Some(index2d) = inside {
+            return Some((index2d, &grid[index2d]));
+        }
+
+        match self.policy {
+            BoundaryPolicy::Skip => None,
+            BoundaryPolicy::Clamp => {
+                let index2d: MemIndex2D = clamp_index2d(self.center, offset, grid.size);
+                Some((index2d, &grid[index2d]))
+            },
+            BoundaryPolicy::Wrap => {
+                let index2d: MemIndex2D = grid.size.wrapping_add(self.center, offset)?;
+                Some((index2d, &grid[index2d]))
+            },
+            BoundaryPolicy::Constant(value) => Some((clamp_index2d(self.center, offset, grid.size), value)),
+        }
+    }
+}
+
+impl<'a, 'n, T> Iterator for NeighborhoodIter<'a, 'n, T> {
+    type Item = (MemIndex2D, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.interior {
+            let offset: MemOffset2D = *self.offsets.next()?;
+            return Some(self.interior_neighbor(offset));
+        }
+
+        loop {
+            let offset: MemOffset2D = *self.offsets.next()?;
+            if let Some(neighbor) = self.edge_neighbor(offset) {
+                return Some(neighbor);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining: usize = self.offsets.len();
+        match (self.interior, self.policy) {
+            (false, BoundaryPolicy::Skip) => (0, Some(remaining)),
+            _ => (remaining, Some(remaining)),
+        }
+    }
+}
+
+impl<T> Vec2D<T> {
+
+    /// Neighbors of center under policy, None if center lies outside of the grid
+    pub fn neighbors<'a, 'n>(&'a self, center: MemIndex2D, neighborhood: &'n Neighborhood, policy: BoundaryPolicy<'a, T>) -> Option<NeighborhoodIter<'a, 'n, T>> {
+        NeighborhoodIter::new(self, center, neighborhood, policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counting(rows: usize, columns: usize) -> Vec2D<i32> {
+        Vec2D::new_items_size((0..(rows * columns) as i32).collect(), Size2D::new(rows, columns)).unwrap()
+    }
+
+    fn values<'a>(iter: impl Iterator<Item = (MemIndex2D, &'a i32)>) -> Vec<i32> {
+        iter.map(|(_, value)| *value).collect()
+    }
+
+    #[test]
+    fn test_boundary_policies() {
+        let grid = counting(3, 4);
+        let cross = Neighborhood::von_neumann(1);
+        let corner = MemIndex2D::origin();
+
+        assert_eq!(vec![1, 4], values(grid.neighbors(corner, &cross, BoundaryPolicy::Skip).unwrap()));
+        assert_eq!(vec![0, 0, 1, 4], values(grid.neighbors(corner, &cross, BoundaryPolicy::Clamp).unwrap()));
+        assert_eq!(vec![8, 3, 1, 4], values(grid.neighbors(corner, &cross, BoundaryPolicy::Wrap).unwrap()));
+        assert_eq!(vec![-1, -1, 1, 4], values(grid.neighbors(corner, &cross, BoundaryPolicy::Constant(&-1)).unwrap()));
+
+        let wrapped: Vec<MemIndex2D> = grid.neighbors(corner, &cross, BoundaryPolicy::Wrap).unwrap().map(|(index2d, _)| index2d).collect();
+        assert_eq!(MemIndex2D::new(2, 0), wrapped[0]);
+        assert_eq!(MemIndex2D::new(0, 3), wrapped[1]);
+
+        assert!(grid.neighbors(MemIndex2D::new(3, 0), &cross, BoundaryPolicy::Skip).is_none());
+    }
+
+    #[test]
+    fn test_interior_matches_checked_path() {
+        let grid = counting(7, 8);
+        let ring = Neighborhood::moore(2);
+
+        let centers: Vec<MemIndex2D> = grid.indexed_iter().map(|(index2d, _)| index2d).collect();
+        for index2d in centers {
+            let fast: Vec<(MemIndex2D, &i32)> = grid.neighbors(index2d, &ring, BoundaryPolicy::Wrap).unwrap().collect();
+            let checked: Vec<(MemIndex2D, &i32)> = ring.offsets().iter().map(|offset| {
+                let neighbor = grid.size.wrapping_add(index2d, *offset).unwrap();
+                (neighbor, &grid[neighbor])
+            }).collect();
+            assert_eq!(checked, fast);
+            assert_eq!(24, fast.len());
+        }
+
+        let cross = Neighborhood::von_neumann(1);
+        assert_eq!(vec![1, 8, 10, 17], values(grid.neighbors(MemIndex2D::new(1, 1), &cross, BoundaryPolicy::Skip).unwrap()));
+        assert_eq!(3, grid.neighbors(MemIndex2D::new(6, 7), &Neighborhood::moore(1), BoundaryPolicy::Skip).unwrap().count());
+    }
+
+    #[test]
+    fn test_custom_mask() {
+        let grid = counting(5, 5);
+        let knight = Neighborhood::from_offsets(vec![MemOffset2D::new(-2, -1), MemOffset2D::new(-1, 2), MemOffset2D::new(2, 1)]);
+
+        let iter = grid.neighbors(MemIndex2D::new(2, 2), &knight, BoundaryPolicy::Skip).unwrap();
+        assert_eq!((3, Some(3)), iter.size_hint());
+        assert_eq!(vec![1, 9, 23], values(iter));
+
+        let iter = grid.neighbors(MemIndex2D::new(0, 4), &knight, BoundaryPolicy::Skip).unwrap();
+        assert_eq!((0, Some(3)), iter.size_hint());
+        assert!(values(iter).is_empty());
+    }
+}
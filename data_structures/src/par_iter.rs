@@ -0,0 +1,119 @@
+use rayon::prelude::*;
+
+use memory_math::memory_index2d::MemIndex2D;
+use memory_math::memory_span2d::MemSpan2D;
+use memory_math::size_2d::{HasSize2D, Size2D};
+use crate::vec2d::{Vec2D, Vec2DMutSlice, Vec2DSlice};
+
+impl<T: Sync> Vec2D<T> {
+    /// Rows in parallel
+    pub fn par_rows(&self) -> impl IndexedParallelIterator<Item = &[T]> {
+        self.items().par_chunks(self.column_count().max(1))
+    }
+
+    /// Every cell with its index in parallel
+    pub fn par_iter_indexed(&self) -> impl IndexedParallelIterator<Item = (MemIndex2D, &T)> {
+        let size: Size2D = self.size;
+        self.items()
+            .par_iter()
+            .enumerate()
+            .map(move |(i, item)| (size.index_to_index2d(i).unwrap(), item))
+    }
+}
+
+impl<T: Send> Vec2D<T> {
+    /// Mutable rows in parallel
+    pub fn par_rows_mut(&mut self) -> impl IndexedParallelIterator<Item = &mut [T]> {
+        let column_count: usize = self.column_count().max(1);
+        self.items_mut().par_chunks_mut(column_count)
+    }
+
+    /// Hand each span to f on the thread pool as its own mutable slice.
+    /// Returns the number of processed spans, None if the spans overlap or do not fit.
+    pub fn par_for_each_chunk_mut<F>(&mut self, spans: Vec<MemSpan2D>, f: F) -> Option<usize>
+    where
+        F: Fn(Vec2DMutSlice<'_, T>) + Sync + Send,
+    {
+        let chunks: Vec<Vec2DMutSlice<'_, T>> = self.get_non_overlapping_chunks_mut(spans)?;
+        let chunk_count: usize = chunks.len();
+        chunks.into_par_iter().for_each(f);
+        Some(chunk_count)
+    }
+}
+
+impl<'a, T: Sync> Vec2DSlice<'a, T> {
+    /// Rows of the 2D slice in parallel
+    pub fn par_rows(&self) -> impl IndexedParallelIterator<Item = &[T]> {
+        (0..self.span2d.row_count())
+            .into_par_iter()
+            .map(move |row| self.get_span_row(row).unwrap())
+    }
+
+    /// Every cell with its index relative to the slice in parallel
+    pub fn par_iter_indexed(&self) -> impl IndexedParallelIterator<Item = (MemIndex2D, &T)> {
+        let size: Size2D = self.span2d.size();
+        //the slice lies inside an allocated grid, so its area always fits
+        (0..size.area().unwrap_or(0))
+            .into_par_iter()
+            .map(move |i| {
+                let index2d: MemIndex2D = size.index_to_index2d(i).unwrap();
+                (index2d, self.get(index2d).unwrap())
+            })
+    }
+}
+
+impl<'a, T: Send> Vec2DMutSlice<'a, T> {
+    /// Mutable rows of the 2D slice in parallel
+    pub fn par_rows_mut(&mut self) -> impl IndexedParallelIterator<Item = &mut [T]> {
+        self.rows_mut().collect::<Vec<&mut [T]>>().into_par_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_par_rows_and_indexed() {
+        let mut vec2d = Vec2D::from_vec((0..20).collect::<Vec<i32>>(), 5).unwrap();
+
+        let row_sums: Vec<i32> = vec2d.par_rows().map(|row| row.iter().sum()).collect();
+        assert_eq!(vec![10, 35, 60, 85], row_sums);
+        assert!(vec2d.par_iter_indexed().all(|(index2d, v)| *v as usize == index2d.row * 5 + index2d.col));
+
+        vec2d.par_rows_mut().enumerate().for_each(|(row, items)| items.iter_mut().for_each(|v| *v = row as i32));
+        assert_eq!(3, vec2d[MemIndex2D::new(3, 4)]);
+
+        let slice = vec2d.get_slice(MemSpan2D::new_from_usize(1, 1, 3, 4)).unwrap();
+        let rows: Vec<&[i32]> = slice.par_rows().collect();
+        assert_eq!(vec![[1, 1, 1].as_slice(), [2, 2, 2].as_slice()], rows);
+
+        let cells: Vec<(MemIndex2D, &i32)> = slice.par_iter_indexed().collect();
+        assert_eq!(6, cells.len());
+        assert_eq!((MemIndex2D::new(1, 2), &2), cells[5]);
+    }
+
+    #[test]
+    fn test_par_for_each_chunk_mut() {
+        let mut vec2d = Vec2D::new_size_reference(Size2D::new(4, 6), &0usize);
+        let spans = vec![
+            MemSpan2D::new_from_usize(0, 0, 2, 3),
+            MemSpan2D::new_from_usize(0, 3, 4, 6),
+            MemSpan2D::new_from_usize(2, 0, 4, 3),
+        ];
+
+        let processed = vec2d.par_for_each_chunk_mut(spans, |mut chunk| {
+            let value: usize = chunk.span2d.min_row() * 10 + chunk.span2d.min_column();
+            chunk.par_rows_mut().for_each(|row| row.iter_mut().for_each(|v| *v = value));
+        });
+
+        assert_eq!(Some(3), processed);
+        assert_eq!(0, vec2d[MemIndex2D::new(1, 2)]);
+        assert_eq!(3, vec2d[MemIndex2D::new(3, 5)]);
+        assert_eq!(20, vec2d[MemIndex2D::new(3, 0)]);
+
+        let overlapping = vec![MemSpan2D::new_from_usize(0, 0, 2, 2), MemSpan2D::new_from_usize(1, 1, 3, 3)];
+        assert!(vec2d.par_for_each_chunk_mut(overlapping, |_| {}).is_none());
+        assert!(vec2d.par_for_each_chunk_mut(vec![MemSpan2D::new_from_usize(3, 0, 5, 2)], |_| {}).is_none());
+    }
+}
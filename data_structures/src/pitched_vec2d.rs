@@ -0,0 +1,281 @@
+use std::mem::{align_of, size_of};
+use std::ops::{Index, IndexMut};
+
+use memory_math::memory_index2d::MemIndex2D;
+use memory_math::size_2d::{HasSize2D, Size2D};
+use crate::vec2d::Vec2D;
+use crate::vec2d_view::{Vec2DView, Vec2DViewMut};
+
+/// Owning 2d grid whose rows each start on an alignment byte boundary, for vectorized row loops.
+/// Rows are padded up to row_pitch items, the padding is never visible through the 2d api.
+pub struct PitchedVec2D<T> {
+    size: Size2D,
+    alignment: usize,
+    row_pitch: usize,
+    start: usize,
+    items: Vec<T>,
+}
+
+impl<T> HasSize2D for PitchedVec2D<T> {
+    fn row_count(&self) -> usize {
+        self.size.row_count
+    }
+
+    fn column_count(&self) -> usize {
+        self.size.column_count
+    }
+
+    fn size(&self) -> Size2D {
+        self.size
+    }
+}
+
+impl<T> Index<MemIndex2D> for PitchedVec2D<T> {
+    type Output = T;
+
+    fn index(&self, index: MemIndex2D) -> &Self::Output {
+        match self.get_index2d(index) {
+            Some(val) => val,
+            None => panic!(
+                "Index2d out of bounds. Index was {} but the size is {}",
+                index,
+                self.size
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<MemIndex2D> for PitchedVec2D<T> {
+    fn index_mut(&mut self, index: MemIndex2D) -> &mut Self::Output {
+        let extents = self.size;
+        match self.get_mut_index2d(index) {
+            Some(v) => v,
+            None => panic!(
+                "Vev2d Coordinates out of bounds. Coordinate was {} but the size is {}",
+                index, extents
+            ),
+        }
+    }
+}
+
+impl<T: Clone> Clone for PitchedVec2D<T> {
+    /// The copy gets its own allocation, so its rows are aligned again rather than offset like the original
+    fn clone(&self) -> Self {
+        let mut padded = self.items[self.start..].iter();
+        PitchedVec2D::build(self.size, self.alignment, |col| match col {
+            Some(_) => padded.next().unwrap().clone(),
+            None => self.items[0].clone(),
+        }).unwrap()
+    }
+}
+
+impl<T> PitchedVec2D<T> {
+
+    /// Items per row once padded so every row starts on an alignment byte boundary.
+    /// None unless alignment is a power of two at least as strict as T's own alignment
+    pub fn row_pitch_for(column_count: usize, alignment: usize) -> Option<usize> {
+        let item_size: usize = size_of::<T>();
+        if item_size == 0 || !alignment.is_power_of_two() || alignment < align_of::<T>() {
+            return None;
+        }
+
+        //rows advance by whole items, so only multiples of this many items keep the boundary
+        let step: usize = alignment / (1 << item_size.trailing_zeros()).min(alignment);
+        column_count.div_ceil(step).checked_mul(step)
+    }
+
+    /// Allocate and fill the buffer in order, cell(Some(col)) is asked for every item of every padded row
+    /// and cell(None) for the lead-in that brings the first row onto the boundary
+    fn build(size: Size2D, alignment: usize, mut cell: impl FnMut(Option<usize>) -> T) -> Option<Self> {
+        let row_pitch: usize = PitchedVec2D::<T>::row_pitch_for(size.column_count, alignment)?;
+        let padded_len: usize = row_pitch.checked_mul(size.row_count)?;
+        if padded_len == 0 {
+            return Some(PitchedVec2D { size, alignment, row_pitch, start: 0, items: Vec::new() });
+        }
+
+        //the first aligned item lies within one step of the allocation start, if the allocation allows one at all
+        let step: usize = alignment / (1 << size_of::<T>().trailing_zeros()).min(alignment);
+        let mut items: Vec<T> = Vec::with_capacity(padded_len + step - 1);
+        let base: usize = items.as_ptr() as usize;
+        let start: usize = (0..step).find(|k| (base + k * size_of::<T>()).is_multiple_of(alignment))?;
+
+        items.extend((0..start + padded_len).map(|i| cell(i.checked_sub(start).map(|i| i % row_pitch))));
+        Some(PitchedVec2D { size, alignment, row_pitch, start, items })
+    }
+
+    /// None if alignment is not usable for T, see row_pitch_for
+    pub fn new_size_reference(size: Size2D, alignment: usize, ref_item: &T) -> Option<Self>
+    where
+        T: Clone,
+    {
+        PitchedVec2D::build(size, alignment, |_| ref_item.clone())
+    }
+
+    /// Pad a tightly packed grid, padding items are T::default()
+    pub fn from_vec2d(vec2d: Vec2D<T>, alignment: usize) -> Option<Self>
+    where
+        T: Default,
+    {
+        let size: Size2D = vec2d.size;
+        let mut packed = vec2d.into_items().into_iter();
+        PitchedVec2D::build(size, alignment, |col| match col {
+            Some(col) if col < size.column_count => packed.next().unwrap(),
+            _ => T::default(),
+        })
+    }
+
+    /// Drop the padding and hand the cells over to a tightly packed grid
+    pub fn into_vec2d(self) -> Vec2D<T> {
+        let (size, row_pitch) = (self.size, self.row_pitch);
+        let items: Vec<T> = self.items
+            .into_iter()
+            .skip(self.start)
+            .enumerate()
+            .filter_map(|(i, item)| (i % row_pitch < size.column_count).then_some(item))
+            .collect();
+
+        Vec2D::new_items_size(items, size).unwrap()
+    }
+
+    pub fn to_vec2d(&self) -> Vec2D<T>
+    where
+        T: Clone,
+    {
+        Vec2D::new_items_size(self.rows().flatten().cloned().collect(), self.size).unwrap()
+    }
+
+    /// Items between the starts of two neighbouring rows
+    pub fn row_pitch(&self) -> usize {
+        self.row_pitch
+    }
+
+    /// Byte boundary every row starts on
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+
+    fn index2d_to_offset(&self, index2d: MemIndex2D) -> Option<usize> {
+        if !self.size.index2d_in_bounds(&index2d) {
+            return None;
+        }
+
+        Some(self.start + Size2D::index2d_to_index_unchecked(self.row_pitch, index2d))
+    }
+
+    fn row_start(&self, row: usize) -> Option<usize> {
+        (row < self.size.row_count).then(|| self.start + row * self.row_pitch)
+    }
+
+    pub fn get_index2d(&self, coordinates: MemIndex2D) -> Option<&T> {
+        self.items.get(self.index2d_to_offset(coordinates)?)
+    }
+
+    pub fn get_mut_index2d(&mut self, coordinates: MemIndex2D) -> Option<&mut T> {
+        let offset: usize = self.index2d_to_offset(coordinates)?;
+        self.items.get_mut(offset)
+    }
+
+    /// Aligned slice of a complete row, without its padding
+    pub fn get_row(&self, row: usize) -> Option<&[T]> {
+        let start: usize = self.row_start(row)?;
+        Some(&self.items[start..start + self.size.column_count])
+    }
+
+    pub fn get_row_mut(&mut self, row: usize) -> Option<&mut [T]> {
+        let start: usize = self.row_start(row)?;
+        let end: usize = start + self.size.column_count;
+        Some(&mut self.items[start..end])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.size.row_count).map(move |row| self.get_row(row).unwrap())
+    }
+
+    /// All rows at once, each aligned and without its padding
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        let (column_count, row_pitch) = (self.size.column_count, self.row_pitch);
+        let mut rest: &mut [T] = &mut self.items[self.start..];
+        //split per row rather than chunking the buffer, rows without columns still have to be handed out
+        (0..self.size.row_count).map(move |_| {
+            let (row, tail) = std::mem::take(&mut rest).split_at_mut(row_pitch);
+            rest = tail;
+            &mut row[..column_count]
+        })
+    }
+
+    /// Strided view of the cells, compatible with every Vec2DView consumer
+    pub fn as_view(&self) -> Vec2DView<'_, T> {
+        Vec2DView::new(&self.items[self.start..], self.size, self.row_pitch).unwrap()
+    }
+
+    pub fn as_view_mut(&mut self) -> Vec2DViewMut<'_, T> {
+        Vec2DViewMut::new(&mut self.items[self.start..], self.size, self.row_pitch).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counting_grid(rows: usize, columns: usize) -> Vec2D<u32> {
+        Vec2D::new_items_size((0..(rows * columns) as u32).collect(), Size2D::new(rows, columns)).unwrap()
+    }
+
+    #[test]
+    fn test_rows_are_aligned() {
+        for (columns, alignment) in [(1, 32), (7, 32), (16, 64), (17, 64), (33, 64)] {
+            let pitched = PitchedVec2D::from_vec2d(counting_grid(5, columns), alignment).unwrap();
+            assert!((pitched.row_pitch() * size_of::<u32>()).is_multiple_of(alignment));
+            assert!(pitched.row_pitch() >= columns && pitched.row_pitch() < columns + alignment / size_of::<u32>());
+            assert!(pitched.rows().all(|row| row.len() == columns && (row.as_ptr() as usize).is_multiple_of(alignment)));
+
+            let copy = pitched.clone();
+            assert!(copy.rows().all(|row| (row.as_ptr() as usize).is_multiple_of(alignment)));
+            assert_eq!(pitched.to_vec2d().items(), copy.into_vec2d().items());
+        }
+
+        //items of size 12 only line up every 8 items on a 32 byte boundary
+        assert_eq!(Some(8), PitchedVec2D::<[u32; 3]>::row_pitch_for(5, 32));
+        assert!(PitchedVec2D::<u64>::row_pitch_for(5, 4).is_none());
+        assert!(PitchedVec2D::<u32>::row_pitch_for(5, 48).is_none());
+    }
+
+    #[test]
+    fn test_indexing_matches_vec2d() {
+        let packed = counting_grid(4, 5);
+        let mut pitched = PitchedVec2D::from_vec2d(packed.clone(), 32).unwrap();
+        assert_eq!(8, pitched.row_pitch());
+
+        for row in 0..4 {
+            for col in 0..5 {
+                assert_eq!(packed[MemIndex2D::new(row, col)], pitched[MemIndex2D::new(row, col)]);
+            }
+        }
+        assert!(pitched.get_index2d(MemIndex2D::new(0, 5)).is_none());
+        assert!(pitched.get_index2d(MemIndex2D::new(4, 0)).is_none());
+
+        pitched[MemIndex2D::new(2, 4)] = 100;
+        pitched.rows_mut().for_each(|row| row[0] += 1000);
+        assert_eq!(Some(&1005), pitched.as_view().get(MemIndex2D::new(1, 0)));
+
+        let unpacked = pitched.into_vec2d();
+        assert_eq!(100, unpacked[MemIndex2D::new(2, 4)]);
+        assert_eq!(1015, unpacked[MemIndex2D::new(3, 0)]);
+        assert_eq!(Size2D::new(4, 5), unpacked.size);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_past_row_end_panics() {
+        let pitched = PitchedVec2D::new_size_reference(Size2D::new(2, 3), 64, &0u8).unwrap();
+        let _ = pitched[MemIndex2D::new(0, 3)];
+    }
+
+    #[test]
+    fn test_empty_rows() {
+        let mut pitched = PitchedVec2D::new_size_reference(Size2D::new(3, 0), 32, &0u16).unwrap();
+        assert_eq!(3, pitched.rows().filter(|row| row.is_empty()).count());
+        assert_eq!(3, pitched.rows_mut().filter(|row| row.is_empty()).count());
+        assert_eq!(Size2D::new(3, 0), pitched.into_vec2d().size);
+    }
+}
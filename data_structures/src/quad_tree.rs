@@ -0,0 +1,369 @@
+use std::ops::Index;
+
+use memory_math::memory_index2d::MemIndex2D;
+use memory_math::memory_span2d::MemSpan2D;
+use memory_math::size_2d::{HasSize2D, Size2D};
+use crate::vec2d::Vec2D;
+
+enum QuadNode<T> {
+    Leaf(T),
+    Branch(Box<[QuadNode<T>; 4]>),
+}
+
+impl<T: Clone + PartialEq> QuadNode<T> {
+
+    fn split(&mut self) {
+        if let QuadNode::Leaf(value) = self {
+            let children = [
+                QuadNode::Leaf(value.clone()),
+                QuadNode::Leaf(value.clone()),
+                QuadNode::Leaf(value.clone()),
+                QuadNode::Leaf(value.clone()),
+            ];
+            *self = QuadNode::Branch(Box::new(children));
+        }
+    }
+
+    ///Replace a branch by a single leaf when every non-empty quadrant holds the same value
+    fn try_collapse(&mut self, span: &MemSpan2D) {
+        let QuadNode::Branch(children) = self else {
+            return;
+        };
+
+        let spans: [MemSpan2D; 4] = quadrant_spans(span);
+        let mut uniform: Option<&T> = None;
+
+        for (child, child_span) in children.iter().zip(spans.iter()) {
+            if !child_span.valid() {
+                continue;
+            }
+
+            match (child, uniform) {
+                (QuadNode::Leaf(value), None) => uniform = Some(value),
+                (QuadNode::Leaf(value), Some(current)) if value == current => {}
+                _ => return,
+            }
+        }
+
+        if let Some(value) = uniform.cloned() {
+            *self = QuadNode::Leaf(value);
+        }
+    }
+
+    fn fill(&mut self, span: &MemSpan2D, region: &MemSpan2D, value: &T) {
+        let overlap: MemSpan2D = match span.intersect(region) {
+            Some(overlap) if overlap.valid() => overlap,
+            _ => return,
+        };
+
+        if overlap == *span {
+            *self = QuadNode::Leaf(value.clone());
+            return;
+        }
+
+        if let QuadNode::Leaf(current) = self {
+            if current == value {
+                return;
+            }
+        }
+
+        self.split();
+
+        if let QuadNode::Branch(children) = self {
+            let spans: [MemSpan2D; 4] = quadrant_spans(span);
+            for (child, child_span) in children.iter_mut().zip(spans.iter()) {
+                child.fill(child_span, region, value);
+            }
+        }
+
+        self.try_collapse(span);
+    }
+
+    fn build(vec2d: &Vec2D<T>, span: &MemSpan2D) -> Self {
+        if span.area().is_some_and(|area| area <= 1) {
+            return QuadNode::Leaf(vec2d[span.min_absolute_index2d()].clone());
+        }
+
+        let spans: [MemSpan2D; 4] = quadrant_spans(span);
+        let children = [
+            Self::build(vec2d, &spans[0]),
+            Self::build(vec2d, &spans[1]),
+            Self::build(vec2d, &spans[2]),
+            Self::build(vec2d, &spans[3]),
+        ];
+
+        let mut node = QuadNode::Branch(Box::new(children));
+        node.try_collapse(span);
+        node
+    }
+}
+
+///Split a span into its four quadrants (top left, top right, bottom left, bottom right).
+/// A span with a single row or column produces empty quadrants, these are never visited.
+fn quadrant_spans(span: &MemSpan2D) -> [MemSpan2D; 4] {
+    let min_row: usize = span.min_row();
+    let min_col: usize = span.min_column();
+    let mid_row: usize = min_row + span.row_count() / 2;
+    let mid_col: usize = min_col + span.column_count() / 2;
+    let row_upper_bound: usize = span.row_span.upper_bound();
+    let col_upper_bound: usize = span.col_span.upper_bound();
+
+    [
+        MemSpan2D::new_from_usize(min_row, min_col, mid_row, mid_col),
+        MemSpan2D::new_from_usize(min_row, mid_col, mid_row, col_upper_bound),
+        MemSpan2D::new_from_usize(mid_row, min_col, row_upper_bound, mid_col),
+        MemSpan2D::new_from_usize(mid_row, mid_col, row_upper_bound, col_upper_bound),
+    ]
+}
+
+fn quadrant_of(span: &MemSpan2D, index2d: MemIndex2D) -> usize {
+    let mid_row: usize = span.min_row() + span.row_count() / 2;
+    let mid_col: usize = span.min_column() + span.column_count() / 2;
+
+    (index2d.row >= mid_row) as usize * 2 + (index2d.col >= mid_col) as usize
+}
+
+///Region quadtree over a Size2D, uniform quadrants are collapsed into a single leaf
+/// so large areas holding the same value cost a single node.
+pub struct QuadTree<T> {
+    size: Size2D,
+    root: QuadNode<T>,
+}
+
+impl<T> HasSize2D for QuadTree<T> {
+    fn row_count(&self) -> usize {
+        self.size.row_count()
+    }
+
+    fn column_count(&self) -> usize {
+        self.size.column_count()
+    }
+
+    fn size(&self) -> Size2D {
+        self.size
+    }
+}
+
+impl<T> Index<MemIndex2D> for QuadTree<T> {
+    type Output = T;
+
+    fn index(&self, index: MemIndex2D) -> &Self::Output {
+        match self.get(index) {
+            Some(val) => val,
+            None => panic!(
+                "Index2d out of bounds. Index was {} but the size is {}",
+                index,
+                self.size
+            ),
+        }
+    }
+}
+
+impl<T> QuadTree<T> {
+
+    pub fn new(size: Size2D, value: T) -> Self {
+        QuadTree {
+            size,
+            root: QuadNode::Leaf(value),
+        }
+    }
+
+    pub fn get(&self, index2d: MemIndex2D) -> Option<&T> {
+        if !self.size.index2d_in_bounds(&index2d) {
+            return None;
+        }
+
+        let mut span: MemSpan2D = self.size.into();
+        let mut node: &QuadNode<T> = &self.root;
+
+        loop {
+            match node {
+                QuadNode::Leaf(value) => return Some(value),
+                QuadNode::Branch(children) => {
+                    let quadrant: usize = quadrant_of(&span, index2d);
+                    span = quadrant_spans(&span)[quadrant].clone();
+                    node = &children[quadrant];
+                }
+            }
+        }
+    }
+
+    /// Iterate over the uniform blocks of the tree as (span, value) pairs.
+    /// Each block is a maximal quadtree aligned region holding a single value.
+    pub fn blocks(&self) -> QuadTreeBlocks<'_, T> {
+        QuadTreeBlocks {
+            stack: vec![(self.size.into(), &self.root)],
+        }
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.blocks().count()
+    }
+
+    //the bottom right quadrant is never empty, so following it always ends on a live leaf
+    fn reference_value(&self) -> &T {
+        let mut node: &QuadNode<T> = &self.root;
+        loop {
+            match node {
+                QuadNode::Leaf(value) => return value,
+                QuadNode::Branch(children) => node = &children[3],
+            }
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> QuadTree<T> {
+
+    /// Set a single cell, returns false if the index is out of bounds
+    pub fn set(&mut self, index2d: MemIndex2D, value: T) -> bool {
+        let (Some(row_upper_bound), Some(col_upper_bound)) = (index2d.row.checked_add(1), index2d.col.checked_add(1)) else {
+            return false;
+        };
+
+        let upper_bound = MemIndex2D::new(row_upper_bound, col_upper_bound);
+        self.fill(MemSpan2D::new_from_index2d(index2d, upper_bound), value)
+    }
+
+    /// Set every cell inside span2d to value, returns false if the span does not fit in the tree
+    pub fn fill(&mut self, span2d: MemSpan2D, value: T) -> bool {
+        if span2d.row_span.upper_bound() > self.row_count() || span2d.col_span.upper_bound() > self.column_count() {
+            return false;
+        }
+
+        let extents: MemSpan2D = self.size.into();
+        self.root.fill(&extents, &span2d, &value);
+        true
+    }
+
+    pub fn from_vec2d(vec2d: &Vec2D<T>) -> Option<Self> {
+        let extents: MemSpan2D = vec2d.size().into();
+        if !extents.valid() {
+            return None;
+        }
+
+        Some(QuadTree {
+            size: vec2d.size(),
+            root: QuadNode::build(vec2d, &extents),
+        })
+    }
+
+    pub fn to_vec2d(&self) -> Vec2D<T> {
+        let mut vec2d: Vec2D<T> = Vec2D::new_size_reference(self.size, self.reference_value());
+
+        for (span, value) in self.blocks() {
+            for row in span.row_span {
+                if let Some(row_slice) = vec2d.get_row_slice_mut(row, span.col_span) {
+                    row_slice.fill(value.clone());
+                }
+            }
+        }
+
+        vec2d
+    }
+}
+
+impl<T: Clone + PartialEq> From<&QuadTree<T>> for Vec2D<T> {
+    fn from(tree: &QuadTree<T>) -> Self {
+        tree.to_vec2d()
+    }
+}
+
+pub struct QuadTreeBlocks<'a, T> {
+    stack: Vec<(MemSpan2D, &'a QuadNode<T>)>,
+}
+
+impl<'a, T> Iterator for QuadTreeBlocks<'a, T> {
+    type Item = (MemSpan2D, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((span, node)) = self.stack.pop() {
+            if !span.valid() {
+                continue;
+            }
+
+            match node {
+                QuadNode::Leaf(value) => return Some((span, value)),
+                QuadNode::Branch(children) => {
+                    //pushed in reverse so the top left quadrant is visited first
+                    let spans: [MemSpan2D; 4] = quadrant_spans(&span);
+                    for (child_span, child) in spans.into_iter().zip(children.iter()).rev() {
+                        self.stack.push((child_span, child));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uniform_tree_is_single_block() {
+        let tree = QuadTree::new(Size2D::new(5, 7), 0u8);
+
+        assert_eq!(1, tree.leaf_count());
+        assert_eq!(Some(&0), tree.get(MemIndex2D::new(4, 6)));
+        assert!(tree.get(MemIndex2D::new(5, 0)).is_none());
+    }
+
+    #[test]
+    fn test_set_and_collapse() {
+        let mut tree = QuadTree::new(Size2D::new(4, 4), 0);
+
+        assert!(tree.set(MemIndex2D::new(1, 2), 7));
+        assert_eq!(7, tree[MemIndex2D::new(1, 2)]);
+        assert_eq!(0, tree[MemIndex2D::new(1, 1)]);
+        assert!(tree.leaf_count() > 1);
+
+        assert!(tree.set(MemIndex2D::new(1, 2), 0));
+        assert_eq!(1, tree.leaf_count());
+
+        assert!(!tree.set(MemIndex2D::new(4, 0), 1));
+        assert!(!tree.set(MemIndex2D::new(usize::MAX, 0), 1));
+        assert!(!tree.set(MemIndex2D::new(0, usize::MAX), 1));
+    }
+
+    #[test]
+    fn test_fill_odd_size() {
+        let mut tree = QuadTree::new(Size2D::new(5, 3), 'a');
+        assert!(tree.fill(MemSpan2D::new_from_usize(1, 1, 4, 3), 'b'));
+
+        for row in 0..5 {
+            for col in 0..3 {
+                let expected = if (1..4).contains(&row) && col >= 1 { 'b' } else { 'a' };
+                assert_eq!(expected, tree[MemIndex2D::new(row, col)], "at ({}, {})", row, col);
+            }
+        }
+
+        let covered: usize = tree.blocks().map(|(span, _)| span.area().unwrap()).sum();
+        assert_eq!(15, covered);
+
+        assert!(!tree.fill(MemSpan2D::new_from_usize(0, 0, 6, 3), 'c'));
+    }
+
+    #[test]
+    fn test_vec2d_round_trip() {
+        let items: Vec<i32> = vec![
+            1, 1, 2, 2, 2,
+            1, 1, 2, 2, 2,
+            3, 3, 3, 3, 3,
+        ];
+        let vec2d = Vec2D::new_items_rows_columns(items.clone(), 3, 5).unwrap();
+        let tree = QuadTree::from_vec2d(&vec2d).unwrap();
+
+        assert!(tree.leaf_count() < items.len());
+        assert_eq!(vec2d.size(), tree.size());
+
+        let round_trip: Vec2D<i32> = Vec2D::from(&tree);
+        for row in 0..3 {
+            for col in 0..5 {
+                let index2d = MemIndex2D::new(row, col);
+                assert_eq!(vec2d[index2d], round_trip[index2d]);
+            }
+        }
+    }
+}
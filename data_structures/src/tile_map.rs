@@ -1,20 +1,15 @@
 use std::{
-    clone, fmt,
-    fmt::Pointer,
+    fmt,
     ops::{Index, IndexMut},
 };
 
 use memory_math::{
-    memory_span2d::{HasMemSpan2D, MemSpan2D},
+    memory_span2d::MemSpan2D,
     memory_index2d::MemIndex2D,
-    memory_offset2d::MemOffset2D,
 };
-use memory_math::memory_iterators::LinearMemoryIterator;
-use memory_math::memory_span::MemSpan;
-use crate::vec2d::{MutVec2DMethods, Vec2DMethods, Vec2DSlice};
-use super::{vec2d::Vec2D, vec2d_iter::Vec2DIntoIter};
+use crate::vec2d::Vec2DSlice;
+use super::vec2d::Vec2D;
 use memory_math::mem_grid::{GridIndex, MemGrid2D, MemoryGrid, NonUniformMemGrid2D};
-use memory_math::memory_span2d::MemSpanIndex2D;
 use memory_math::size_2d::{HasSize2D, Size2D};
 
 pub struct TileMap<T>
@@ -37,7 +32,6 @@ impl<T> HasSize2D for TileMap<T>
 }
 
 ///Range of tiles in a tilemap
-
 pub struct TileRange2D(pub MemSpan2D);
 
 impl HasSize2D for TileRange2D {
@@ -154,7 +148,7 @@ impl<T: Clone> TileMap<T> {
         let tile_list: Vec<Vec2D<T>> = vec![tile; capacity_width * capacity_height];
         let tiles: Vec2D<Vec2D<T>> = Vec2D::new_items_rows_columns(tile_list, capacity_height, capacity_width)?;
 
-        let grid: MemGrid2D = MemGrid2D::new(tiles.size, grid_row_count, grid_column_count);
+        let grid: MemGrid2D = MemGrid2D::new(TileMap::<T>::cell_size(tiles.size, grid_row_count, grid_column_count), grid_row_count, grid_column_count);
 
         Some(TileMap {
             tiles,
@@ -166,7 +160,7 @@ impl<T: Clone> TileMap<T> {
 
 impl<T> TileMap<T> {
     pub fn new(tiles: Vec2D<Vec2D<T>>, tile_rows: usize, tile_columns: usize) -> Self {
-        let size: Size2D = tiles.size();
+        let size: Size2D = TileMap::<T>::cell_size(tiles.size(), tile_rows, tile_columns);
 
         TileMap {
             tiles,
@@ -174,6 +168,11 @@ impl<T> TileMap<T> {
         }
     }
 
+    /// Size in cells of a grid of tile_count tiles that are each tile_rows x tile_columns
+    fn cell_size(tile_count: Size2D, tile_rows: usize, tile_columns: usize) -> Size2D {
+        Size2D::new(tile_count.row_count * tile_rows, tile_count.column_count * tile_columns)
+    }
+
     pub fn get_slice(&self, extents: MemSpan2D) -> Option<TileMapSlice<'_, T>> {
         let (range, intersections) = self.grid.grid_intersections(&extents)?;
         let mut tile_slices: Vec<Vec2DSlice<'_, T>> = Vec::with_capacity(intersections.len());
@@ -184,15 +183,26 @@ impl<T> TileMap<T> {
             tile_slices.push(cur_slice);
         }
 
-        let tile_slices = Vec2D::new_items_size( tile_slices, range.0.size())?;
-        // Some(TileMapSlice
-        // {
-        //     tile_slices,
-        //     row_offsets: Vec::new(),
-        //     column_offsets: Vec::new(),
-        //     cell_extents: range.0
-        // })
-        None
+        let tile_slices: Vec2D<Vec2DSlice<'_, T>> = Vec2D::new_items_size(tile_slices, range.0.size())?;
+
+        //the first and last tiles may be cut, so the offsets are summed from the actual slice sizes
+        let row_offsets: Vec<usize> = (0..tile_slices.row_count() - 1)
+            .scan(0, |offset, row| {
+                *offset += tile_slices[MemIndex2D::new(row, 0)].span2d.row_count();
+                Some(*offset)
+            })
+            .collect();
+        let column_offsets: Vec<usize> = (0..tile_slices.column_count() - 1)
+            .scan(0, |offset, col| {
+                *offset += tile_slices[MemIndex2D::new(0, col)].span2d.column_count();
+                Some(*offset)
+            })
+            .collect();
+
+        Some(TileMapSlice {
+            tile_slices,
+            grid: NonUniformMemGrid2D::new(extents.size(), row_offsets, column_offsets),
+        })
     }
 
 }
@@ -219,25 +229,76 @@ impl<'a, T> TileMapSlice<'a, T>
 
     #[inline]
     pub fn end_grid_row_count(&self) -> usize {
-        self.tile_slices[self.tile_slices.row_count() - 1].span2d.row_count()
+        self.tile_slices[MemIndex2D::new(self.tile_slices.row_count() - 1, 0)].span2d.row_count()
     }
 
     #[inline]
     pub fn end_grid_column_count(&self) -> usize {
-        self.tile_slices[self.tile_slices.column_count() - 1].span2d.column_count()
+        self.tile_slices[MemIndex2D::new(0, self.tile_slices.column_count() - 1)].span2d.column_count()
     }
 
     pub fn get(&self, cell_index2d: MemIndex2D) -> Option<&'a T> {
         let grid_index: GridIndex = self.grid.index2d_to_grid_index(&cell_index2d)?;
-        let index_in_grid: MemSpanIndex2D = MemSpanIndex2D(self.grid.index2d_relative_to_grid(&cell_index2d, &grid_index)?);
-        self.tile_slices[grid_index.0].get_span_index2d(&index_in_grid)
+        let index_in_grid: MemIndex2D = self.grid.index2d_relative_to_grid(&cell_index2d, &grid_index)?;
+        self.tile_slices.get_index2d(grid_index.0)?.get(index_in_grid)
     }
 }
 
+impl<'a, T> HasSize2D for TileMapSlice<'a, T> {
+    fn row_count(&self) -> usize {
+        self.grid.row_count()
+    }
 
+    fn column_count(&self) -> usize {
+        self.grid.column_count()
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// 2x3 tiles of 2x2 cells, every cell holds its own row * 10 + col
+    fn numbered_map() -> TileMap<usize> {
+        let tiles: Vec<Vec2D<usize>> = (0..6)
+            .map(|tile| {
+                let (tile_row, tile_col) = (tile / 3, tile % 3);
+                let cells: Vec<usize> = (0..4).map(|cell| (tile_row * 2 + cell / 2) * 10 + tile_col * 2 + cell % 2).collect();
+                Vec2D::new_items_size(cells, Size2D::new(2, 2)).unwrap()
+            })
+            .collect();
+
+        TileMap::new(Vec2D::new_items_size(tiles, Size2D::new(2, 3)).unwrap(), 2, 2)
+    }
+
+    #[test]
+    fn test_index_across_tiles() {
+        let mut map = numbered_map();
+        assert_eq!(Size2D::new(4, 6), map.size());
+        assert_eq!(0, map[MemIndex2D::new(0, 0)]);
+        assert_eq!(25, map[MemIndex2D::new(2, 5)]);
+        assert_eq!(34, map[MemIndex2D::new(3, 4)]);
+
+        map[MemIndex2D::new(3, 3)] = 100;
+        assert_eq!(Some(&100), map[GridIndex::new(1, 1)].get_index2d(MemIndex2D::new(1, 1)));
+    }
+
+    #[test]
+    fn test_slice_across_tiles() {
+        let map = numbered_map();
+        let slice = map.get_slice(MemSpan2D::new_from_usize(1, 1, 4, 6)).unwrap();
+
+        assert_eq!(Size2D::new(3, 5), slice.size());
+        assert_eq!(1, slice.start_grid_row_count());
+        assert_eq!(1, slice.start_grid_column_count());
+        assert_eq!(2, slice.end_grid_row_count());
+        assert_eq!(2, slice.end_grid_column_count());
+
+        for row in 0..3 {
+            for col in 0..5 {
+                assert_eq!(Some(&((row + 1) * 10 + col + 1)), slice.get(MemIndex2D::new(row, col)));
+            }
+        }
+        assert!(slice.get(MemIndex2D::new(3, 0)).is_none());
+    }
 }
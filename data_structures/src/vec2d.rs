@@ -1,19 +1,23 @@
-use std::cmp::max;
+use std::iter::StepBy;
 use std::{ops::{Index, IndexMut}, slice};
-use std::marker::PhantomData;
 
 extern crate proc_macro;
 
 use memory_math::{
-    memory_span2d::{MemSpan2D, HasMemSpan2D},
+    memory_span2d::MemSpan2D,
     memory_index2d::MemIndex2D,
-    memory_range_iter::IterateWithMemIndex,
-    memory_iterators::LinearMemoryIterator,
+    memory_range_iter::{IndexEnumerate, IterateWithMemIndex},
+    memory_iterators::{LinearMemoryIterator, MemoryIterator},
     memory_offset2d::MemOffset2D,
 };
 use memory_math::memory_span::MemSpan;
+use memory_math::copy_plan::CopyPlan;
 use memory_math::size_2d::{HasSize2D, Size2D};
-use super::{vec2d_iter::Vec2DIter};
+use super::blit::clip;
+use super::vec2d_iter::{
+    RowMajorIter, StridedRows, Vec2DIntoIter, Vec2DIter, Vec2DIterMut, Vec2DLinearIntoIter, Vec2DLinearIter, Vec2DLinearIterMut,
+    Vec2DMutSliceIntoIter, Vec2DMutSliceIter, Vec2DMutSliceIterMut, Vec2DSliceIter,
+};
 
 #[derive(Clone)]
 pub struct Vec2D<T> {
@@ -21,6 +25,37 @@ pub struct Vec2D<T> {
     items: Vec<T>,
 }
 
+/// The part of a grid that stays in place when resize grows or shrinks it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResizeAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl ResizeAnchor {
+    /// Halves of the size change taken from the top and from the left
+    fn halves(&self) -> (isize, isize) {
+        match self {
+            ResizeAnchor::TopLeft => (0, 0),
+            ResizeAnchor::Top => (0, 1),
+            ResizeAnchor::TopRight => (0, 2),
+            ResizeAnchor::Left => (1, 0),
+            ResizeAnchor::Center => (1, 1),
+            ResizeAnchor::Right => (1, 2),
+            ResizeAnchor::BottomLeft => (2, 0),
+            ResizeAnchor::Bottom => (2, 1),
+            ResizeAnchor::BottomRight => (2, 2),
+        }
+    }
+}
+
 impl<T> HasSize2D for Vec2D<T>
 {
     fn row_count(&self) -> usize {
@@ -42,7 +77,7 @@ impl<T> Vec2D<T> {
     }
 
     /// Get a slice of a complete row
-    fn get_row(&self, row: usize) -> Option<&[T]> {
+    pub fn get_row(&self, row: usize) -> Option<&[T]> {
         if row >= self.row_count() {
             return None;
         }
@@ -52,7 +87,7 @@ impl<T> Vec2D<T> {
         Some(&self.items[start..end])
     }
 
-    fn get_mut_index2d(&mut self, coordinates: MemIndex2D) -> Option<&mut T> {
+    pub fn get_mut_index2d(&mut self, coordinates: MemIndex2D) -> Option<&mut T> {
         self.size.index2d_to_index(coordinates).and_then(|i| self.items.get_mut(i))
     }
 }
@@ -103,6 +138,34 @@ impl<T> IndexMut<usize> for Vec2D<T> {
     }
 }
 
+impl<T> IntoIterator for Vec2D<T> {
+    type Item = T;
+    type IntoIter = Vec2DLinearIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let order = LinearMemoryIterator::new(self.size.into());
+        RowMajorIter::new(Some(self.items.into_iter()).into_iter(), order)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Vec2D<T> {
+    type Item = &'a T;
+    type IntoIter = Vec2DLinearIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Vec2D<T> {
+    type Item = &'a mut T;
+    type IntoIter = Vec2DLinearIterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 impl<T> Vec2D<T> {
 
     pub fn new_size_reference(size: Size2D, ref_item: &T) -> Self
@@ -115,7 +178,7 @@ impl<T> Vec2D<T> {
     }
 
     pub fn from_vec(items: Vec<T>, width: usize) -> Option<Self> {
-        if items.len() % width != 0 {
+        if !items.len().is_multiple_of(width) {
             return None;
         }
 
@@ -144,21 +207,192 @@ impl<T> Vec2D<T> {
     }
 
 
-    pub fn push_range(&mut self, start_index: MemIndex2D, range: Vec2D<T>) {
-        if start_index.col > self.column_count() || start_index.row > self.row_count() {
-            return;
+    /// Move the cells of range onto self with its top left cell at start_index, clipped the same way as Rows2DMut::blit.
+    /// Unlike blit the cells are moved rather than cloned, the cells they replace are dropped along with range.
+    pub fn push_range(&mut self, start_index: MemIndex2D, mut range: Vec2D<T>) {
+        let Some((written, src_min)) = clip(range.size, self.size, MemOffset2D::from(start_index)) else { return };
+        let (col_min, width) = (written.col_span.min, written.col_span.len());
+
+        for (i, row) in written.row_span.into_iter().enumerate() {
+            let src_cells: &mut [T] = &mut range.get_row_mut(src_min.row + i).unwrap()[src_min.col..src_min.col + width];
+            self.get_row_mut(row).unwrap()[col_min..col_min + width].swap_with_slice(src_cells);
         }
+    }
 
-        let shift: MemOffset2D = MemOffset2D::from(start_index);
+    ///Copy a span of src onto an equally sized span of self, one copy_from_slice per contiguous run.
+    /// Returns the number of copied cells, None if the spans differ in size or do not fit.
+    pub fn copy_region_from(&mut self, dst_span: &MemSpan2D, src: &Vec2D<T>, src_span: &MemSpan2D) -> Option<usize>
+    where
+        T: Copy,
+    {
+        let plan: CopyPlan = CopyPlan::new(src_span, &src.size, dst_span, &self.size)?;
 
-        for (index, item) in range.linear_iter().iterate_with_mem_index() {
-            if let Some(self_index2d) = index + shift{
-                self[self_index2d] = item;
-            }
+        for op in plan.ops() {
+            self.items[op.dst_offset..op.dst_offset + op.len]
+                .copy_from_slice(&src.items[op.src_offset..op.src_offset + op.len]);
+        }
+
+        Some(plan.cell_count())
+    }
+
+    /// Insert a row before row at, the rows below move down in a single memmove.
+    /// The row needs exactly column_count items, a grid without rows takes its width from the row.
+    pub fn insert_row(&mut self, at: usize, row: impl IntoIterator<Item = T>) -> bool {
+        if at > self.row_count() {
+            return false;
+        }
+
+        let row: Vec<T> = row.into_iter().collect();
+        if self.row_count() == 0 {
+            self.size.column_count = row.len();
+        } else if row.len() != self.column_count() {
+            return false;
+        }
+
+        let start: usize = at * self.column_count();
+        self.items.splice(start..start, row);
+        self.size.row_count += 1;
+        true
+    }
+
+    /// Take out a row, the rows below move up in a single memmove
+    pub fn remove_row(&mut self, at: usize) -> Option<Vec<T>> {
+        if at >= self.row_count() {
+            return None;
+        }
+
+        let start: usize = at * self.column_count();
+        let row: Vec<T> = self.items.drain(start..start + self.column_count()).collect();
+        self.size.row_count -= 1;
+        Some(row)
+    }
+
+    /// Insert a column before column at in a single pass over the grid.
+    /// The column needs exactly row_count items, a grid without rows or columns takes its height from the column.
+    pub fn insert_column(&mut self, at: usize, column: impl IntoIterator<Item = T>) -> bool {
+        let column_count: usize = self.column_count();
+        if at > column_count {
+            return false;
+        }
+
+        let column: Vec<T> = column.into_iter().collect();
+        let shapeless: bool = self.row_count() == 0 && column_count == 0;
+        if column.len() != self.row_count() && !shapeless {
+            return false;
+        }
+
+        let row_count: usize = column.len();
+        let mut items: Vec<T> = Vec::with_capacity(row_count * (column_count + 1));
+        let mut old = std::mem::take(&mut self.items).into_iter();
+        for cell in column {
+            items.extend(old.by_ref().take(at));
+            items.push(cell);
+            items.extend(old.by_ref().take(column_count - at));
+        }
+
+        self.items = items;
+        self.size = Size2D::new(row_count, column_count + 1);
+        true
+    }
+
+    /// Take out a column in a single pass over the grid
+    pub fn remove_column(&mut self, at: usize) -> Option<Vec<T>> {
+        let column_count: usize = self.column_count();
+        if at >= column_count {
+            return None;
+        }
+
+        let mut column: Vec<T> = Vec::with_capacity(self.row_count());
+        self.items = std::mem::take(&mut self.items)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, item)| {
+                if i % column_count != at {
+                    return Some(item);
+                }
+                column.push(item);
+                None
+            })
+            .collect();
+
+        self.size.column_count -= 1;
+        Some(column)
+    }
+
+    /// Rebuild the grid at size, where the new cell (r, c) is the old cell at origin + (r, c) or a fill value outside of the old grid.
+    /// Surviving cells keep their row major order, so they are moved over in a single pass.
+    /// False, leaving the grid untouched, if the area of size does not fit in usize.
+    fn reframe(&mut self, size: Size2D, origin: MemOffset2D, mut fill: impl FnMut() -> T) -> bool {
+        let Some(area) = size.area() else {
+            return false;
+        };
+
+        let old_size: Size2D = self.size;
+        let in_old = |row: isize, col: isize| {
+            (0..old_size.row_count as isize).contains(&row) && (0..old_size.column_count as isize).contains(&col)
+        };
+        let in_new = |row: isize, col: isize| {
+            (0..size.row_count as isize).contains(&row) && (0..size.column_count as isize).contains(&col)
+        };
+
+        let mut survivors = std::mem::take(&mut self.items)
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| in_new((i / old_size.column_count) as isize - origin.row, (i % old_size.column_count) as isize - origin.col))
+            .map(|(_, item)| item);
+
+        self.items = (0..area)
+            .map(|i| {
+                let (row, col) = ((i / size.column_count) as isize, (i % size.column_count) as isize);
+                if in_old(row + origin.row, col + origin.col) { survivors.next().unwrap() } else { fill() }
+            })
+            .collect();
+        self.size = size;
+        true
+    }
+
+    /// Grow or shrink to size, anchor decides which cells stay in place and new cells get fill.
+    /// False if the area of size does not fit in usize
+    pub fn resize(&mut self, size: Size2D, fill: &T, anchor: ResizeAnchor) -> bool
+    where
+        T: Clone,
+    {
+        let (top, left) = anchor.halves();
+        let origin = MemOffset2D {
+            row: top * (self.row_count() as isize - size.row_count as isize) / 2,
+            col: left * (self.column_count() as isize - size.column_count as isize) / 2,
+        };
+
+        self.reframe(size, origin, || fill.clone())
+    }
+
+    /// Keep only the cells of span2d, which becomes the whole grid. False if span2d is empty or does not fit
+    pub fn crop(&mut self, span2d: &MemSpan2D) -> bool {
+        if !span2d.valid() || !self.size.contains_span2d(span2d) {
+            return false;
         }
+
+        let origin: MemOffset2D = MemOffset2D::from(span2d.min_absolute_index2d());
+        self.reframe(span2d.size(), origin, || unreachable!("a crop never reaches outside of the grid"))
     }
 
-    fn get_row_slice(&self, row: usize, span: MemSpan) -> Option<&[T]> {
+    /// Surround the grid with borders of fill, given in rows for top and bottom and columns for left and right.
+    /// False if the padded grid would be too large to address
+    pub fn pad(&mut self, top: usize, bottom: usize, left: usize, right: usize, fill: &T) -> bool
+    where
+        T: Clone,
+    {
+        let row_count: Option<usize> = self.row_count().checked_add(top).and_then(|rows| rows.checked_add(bottom));
+        let column_count: Option<usize> = self.column_count().checked_add(left).and_then(|cols| cols.checked_add(right));
+        let (Some(row_count), Some(column_count)) = (row_count, column_count) else {
+            return false;
+        };
+
+        let origin = MemOffset2D { row: -(top as isize), col: -(left as isize) };
+        self.reframe(Size2D::new(row_count, column_count), origin, || fill.clone())
+    }
+
+    pub fn get_row_slice(&self, row: usize, span: MemSpan) -> Option<&[T]> {
         let min_col: usize = span.min;
         let max_col: usize = MemSpan::max(&span)?;
 
@@ -185,7 +419,7 @@ impl<T> Vec2D<T> {
 
     pub fn get_row_slice_mut(&mut self, row: usize, span: MemSpan) -> Option<&mut [T]> {
 
-        if span.len() == 0 {
+        if span.is_empty() {
             return None;
         }
 
@@ -212,37 +446,92 @@ impl<T> Vec2D<T> {
             Vec2DSlice::new(
                 self.size.column_count,
                 span2d,
-                &self.items.as_slice()
+                self.items.as_slice()
             )
         )
     }
 
 
     pub fn get_slice_mut(&'_ mut self, span2d: MemSpan2D) -> Option<Vec2DMutSlice<'_, T>> {
-        if span2d.valid() || !self.size.contains_span2d(&span2d) {
+        if !span2d.valid() || !self.size.contains_span2d(&span2d) {
             return None;
         }
 
-        Some(
-            Vec2DMutSlice::new(
-                self.size.column_count,
-                span2d,
-                 self.items.as_mut_ptr()
-            )
-        )
+        Vec2DMutSlice::from_buffer(&mut self.items, self.size.column_count, span2d)
     }
 
-    unsafe fn get_slice_mut_unchecked(&'_ mut self, span2d: MemSpan2D) -> Vec2DMutSlice<'_, T> {
-            Vec2DMutSlice::new(
-                self.size.column_count,
-                span2d,
-                self.items.as_mut_ptr()
-            )
+    pub(crate) fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    pub(crate) fn items_mut(&mut self) -> &mut [T] {
+        &mut self.items
+    }
+
+    pub(crate) fn into_items(self) -> Vec<T> {
+        self.items
     }
 
-    pub fn linear_iter(&self) -> Vec2DIter<LinearMemoryIterator, T>
+    pub fn linear_iter(&self) -> Vec2DLinearIter<'_, T>
     {
-        Vec2DIter::new(LinearMemoryIterator::new(self.size.into()), &self)
+        self.iter()
+    }
+
+    /// Rows of the grid, top to bottom
+    pub fn rows(&self) -> slice::Chunks<'_, T> {
+        self.items.chunks(self.column_count().max(1))
+    }
+
+    pub fn rows_mut(&mut self) -> slice::ChunksMut<'_, T> {
+        let column_count: usize = self.column_count().max(1);
+        self.items.chunks_mut(column_count)
+    }
+
+    /// Columns of the grid left to right, each walked top to bottom by striding over the rows
+    pub fn columns(&self) -> impl Iterator<Item = StepBy<slice::Iter<'_, T>>> {
+        let column_count: usize = self.column_count();
+        (0..column_count).map(move |col| self.items[col..].iter().step_by(column_count))
+    }
+
+    /// Cells in row major order, straight from the buffer
+    pub fn iter(&self) -> Vec2DLinearIter<'_, T> {
+        RowMajorIter::new(Some(self.items.iter()).into_iter(), LinearMemoryIterator::new(self.size.into()))
+    }
+
+    /// Cells in the order of any MemoryIterator over grid indexes, None if it reaches outside of the grid
+    pub fn iter_with<I: MemoryIterator>(&self, order: I) -> Option<Vec2DIter<'_, I, T>> {
+        Vec2DIter::new(order, self.rows().collect())
+    }
+
+    pub fn iter_mut(&mut self) -> Vec2DLinearIterMut<'_, T> {
+        let order = LinearMemoryIterator::new(self.size.into());
+        RowMajorIter::new(Some(self.items.iter_mut()).into_iter(), order)
+    }
+
+    pub fn iter_mut_with<I: MemoryIterator>(&mut self, order: I) -> Option<Vec2DIterMut<'_, I, T>> {
+        Vec2DIterMut::new(order, self.rows_mut().collect())
+    }
+
+    /// Move the cells out in the order of any MemoryIterator over grid indexes
+    pub fn into_iter_with<I: MemoryIterator>(self, order: I) -> Option<Vec2DIntoIter<I, T>> {
+        Vec2DIntoIter::new(order, self.items, self.size)
+    }
+
+    /// Cells in row major order together with their index
+    pub fn indexed_iter(&self) -> IndexEnumerate<Vec2DLinearIter<'_, T>> {
+        self.iter().iterate_with_mem_index()
+    }
+
+    pub fn indexed_iter_with<I: MemoryIterator>(&self, order: I) -> Option<IndexEnumerate<Vec2DIter<'_, I, T>>> {
+        Some(self.iter_with(order)?.iterate_with_mem_index())
+    }
+
+    pub fn indexed_iter_mut(&mut self) -> IndexEnumerate<Vec2DLinearIterMut<'_, T>> {
+        self.iter_mut().iterate_with_mem_index()
+    }
+
+    pub fn indexed_iter_mut_with<I: MemoryIterator>(&mut self, order: I) -> Option<IndexEnumerate<Vec2DIterMut<'_, I, T>>> {
+        Some(self.iter_mut_with(order)?.iterate_with_mem_index())
     }
 
     pub fn get_non_overlapping_chunks(&'_ self, spans: Vec<MemSpan2D>) -> Option<Vec<Vec2DSlice<'_, T>>> {
@@ -265,25 +554,38 @@ impl<T> Vec2D<T> {
             }
         }
 
-        return Some(chunks);
+        Some(chunks)
     }
 
-    pub fn get_non_overlapping_chunks_mut(&'_ mut self, mut spans: Vec<MemSpan2D>) -> Option<Vec<Vec2DMutSlice<'_, T>>> {
-        if MemSpan2D::spans_overlap_or_invalid(&spans)
+    pub fn get_non_overlapping_chunks_mut(&'_ mut self, spans: Vec<MemSpan2D>) -> Option<Vec<Vec2DMutSlice<'_, T>>> {
+        if MemSpan2D::spans_overlap_or_invalid(&spans) || !spans.iter().all(|s| self.size.contains_span2d(s))
         {
             return None;
         }
 
-        let slice = self.items.as_mut_ptr();
-        let len = self.items.len();
+        // Deal out each buffer row left to right, so every chunk only holds its own segments
+        let mut chunk_rows: Vec<Vec<&mut [T]>> = spans.iter().map(|s| Vec::with_capacity(s.row_count())).collect();
+        let column_count: usize = self.size.column_count.max(1);
+
+        for (row, mut rest) in self.items.chunks_mut(column_count).enumerate() {
+            let mut crossing: Vec<usize> = (0..spans.len()).filter(|i| spans[*i].row_span.contains(row)).collect();
+            crossing.sort_by_key(|i| spans[*i].min_column());
+
+            let mut consumed: usize = 0;
+            for chunk in crossing {
+                let col_span: MemSpan = spans[chunk].col_span;
+                let (_, tail) = std::mem::take(&mut rest).split_at_mut(col_span.min - consumed);
+                let (segment, tail) = tail.split_at_mut(col_span.len());
+                chunk_rows[chunk].push(segment);
+                rest = tail;
+                consumed = col_span.upper_bound();
+            }
+        }
 
-        Some(spans.into_iter().map(|s|
-        Vec2DMutSlice{
-            vec_column_count: self.size.column_count,
-            span2d: s,
-            data: unsafe{std::slice::from_raw_parts_mut(slice, len).as_mut_ptr()},
-            bound: PhantomData
-        }).collect())
+        spans.into_iter()
+            .zip(chunk_rows)
+            .map(|(span2d, rows)| Vec2DMutSlice::new(span2d, rows))
+            .collect()
     }
 
 }
@@ -311,11 +613,19 @@ trait SliceMethods
     }
 }
 
+fn row_cells<'r, T>(row: &'r &mut [T]) -> slice::Iter<'r, T> {
+    row.iter()
+}
+
+fn row_cells_mut<'r, T>(row: &'r mut &mut [T]) -> slice::IterMut<'r, T> {
+    row.iter_mut()
+}
+
+/// A mutable 2D view made of the row segments of its span.
+/// Each view only borrows its own cells, so views over disjoint spans can live side by side.
 pub struct Vec2DMutSlice<'a, T> {
-    pub vec_column_count: usize,
     pub span2d: MemSpan2D,
-    data: *mut T,
-    bound: PhantomData<&'a T>
+    rows: Vec<&'a mut [T]>,
 }
 
 impl<'a, T> HasSize2D for Vec2DMutSlice<'a, T> {
@@ -328,19 +638,6 @@ impl<'a, T> HasSize2D for Vec2DMutSlice<'a, T> {
     }
 }
 
-impl<'a, T> SliceMethods for Vec2DMutSlice<'a, T>
-{
-    fn vec_column_count(&self) -> usize
-    {
-        self.vec_column_count
-    }
-
-    fn span2d(&self) -> &MemSpan2D
-    {
-        &self.span2d
-    }
-}
-
 impl<'a, T> Index<MemIndex2D> for Vec2DMutSlice<'a, T>
 {
     type Output = T;
@@ -359,30 +656,201 @@ impl<'a, T> IndexMut<MemIndex2D> for Vec2DMutSlice<'a, T>
 
 impl<'a, T> Vec2DMutSlice<'a, T> {
 
-    pub fn new(vec_column_count: usize, span2d: MemSpan2D, data: *mut T) -> Vec2DMutSlice<'a, T>
-    {
-        Vec2DMutSlice
+    /// View over row segments, one per row of span2d and each as wide as span2d
+    pub fn new(span2d: MemSpan2D, rows: Vec<&'a mut [T]>) -> Option<Self> {
+        if !span2d.valid()
+            || rows.len() != span2d.row_count()
+            || rows.iter().any(|row| row.len() != span2d.column_count())
         {
-            vec_column_count,
-            span2d,
-            data,
-            bound: PhantomData
+            return None;
+        }
+
+        Some(Vec2DMutSlice { span2d, rows })
+    }
+
+    /// View of span2d inside a row major buffer with buffer_column_count columns
+    pub fn from_buffer(buffer: &'a mut [T], buffer_column_count: usize, span2d: MemSpan2D) -> Option<Self> {
+        if !span2d.valid()
+            || span2d.max_column()? >= buffer_column_count
+            || buffer.len() < span2d.row_span.upper_bound() * buffer_column_count
+        {
+            return None;
+        }
+
+        let min_col: usize = span2d.min_column();
+        let width: usize = span2d.column_count();
+        let rows: Vec<&'a mut [T]> = buffer
+            .chunks_mut(buffer_column_count)
+            .skip(span2d.min_row())
+            .take(span2d.row_count())
+            .map(|row| &mut row[min_col..min_col + width])
+            .collect();
+
+        Vec2DMutSlice::new(span2d, rows)
+    }
+
+    /// Shorter lived view of the same cells
+    pub fn reborrow(&mut self) -> Vec2DMutSlice<'_, T> {
+        Vec2DMutSlice {
+            span2d: self.span2d.clone(),
+            rows: self.rows.iter_mut().map(|row| &mut **row).collect(),
         }
     }
 
     pub fn get(&self, index2d: MemIndex2D) -> Option<&T> {
-        let offset: usize = self.index2d_to_offset(index2d)?;
-        Some(unsafe {&*self.data.add(offset)})
+        self.rows.get(index2d.row)?.get(index2d.col)
     }
 
     pub fn get_mut(&mut self, index2d: MemIndex2D) -> Option<&mut T> {
-        let offset: usize = self.index2d_to_offset(index2d)?;
-        Some(unsafe {&mut *self.data.add(offset)})
+        self.rows.get_mut(index2d.row)?.get_mut(index2d.col)
     }
-}
 
+    pub fn get_span_row(&self, row: usize) -> Option<&[T]> {
+        self.rows.get(row).map(|row| &**row)
+    }
 
+    pub fn get_span_row_mut(&mut self, row: usize) -> Option<&mut [T]> {
+        self.rows.get_mut(row).map(|row| &mut **row)
+    }
 
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.rows.iter().map(|row| &**row)
+    }
+
+    /// Copy the cells out into a grid of their own
+    pub fn to_vec2d(&self) -> Vec2D<T>
+    where
+        T: Clone,
+    {
+        Vec2D::new_items_size(self.iter().cloned().collect(), self.span2d.size()).unwrap()
+    }
+
+    /// All rows of the 2D slice at once, rows never overlap
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> + use<'_, 'a, T> {
+        self.rows.iter_mut().map(|row| &mut **row)
+    }
+
+    /// Columns left to right, each walked top to bottom
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.column_count()).map(move |col| self.rows().map(move |row| &row[col]))
+    }
+
+    /// Cells in row major order, walking the rows the slice already holds
+    pub fn iter(&self) -> Vec2DMutSliceIter<'_, 'a, T> {
+        let rows = self.rows.iter().map(row_cells as for<'r> fn(&'r &'a mut [T]) -> slice::Iter<'r, T>);
+        RowMajorIter::new(rows, LinearMemoryIterator::new(self.size().into()))
+    }
+
+    /// Cells in the order of any MemoryIterator over slice relative indexes, None if it reaches outside of the slice
+    pub fn iter_with<I: MemoryIterator>(&self, order: I) -> Option<Vec2DIter<'_, I, T>> {
+        Vec2DIter::new(order, self.rows().collect())
+    }
+
+    pub fn iter_mut(&mut self) -> Vec2DMutSliceIterMut<'_, 'a, T> {
+        let order = LinearMemoryIterator::new(self.size().into());
+        let rows = self.rows.iter_mut().map(row_cells_mut as for<'r> fn(&'r mut &'a mut [T]) -> slice::IterMut<'r, T>);
+        RowMajorIter::new(rows, order)
+    }
+
+    pub fn iter_mut_with<I: MemoryIterator>(&mut self, order: I) -> Option<Vec2DIterMut<'_, I, T>> {
+        Vec2DIterMut::new(order, self.rows_mut().collect())
+    }
+
+    /// Cells in row major order together with their slice relative index
+    pub fn indexed_iter(&self) -> IndexEnumerate<Vec2DMutSliceIter<'_, 'a, T>> {
+        self.iter().iterate_with_mem_index()
+    }
+
+    pub fn indexed_iter_with<I: MemoryIterator>(&self, order: I) -> Option<IndexEnumerate<Vec2DIter<'_, I, T>>> {
+        Some(self.iter_with(order)?.iterate_with_mem_index())
+    }
+
+    pub fn indexed_iter_mut(&mut self) -> IndexEnumerate<Vec2DMutSliceIterMut<'_, 'a, T>> {
+        self.iter_mut().iterate_with_mem_index()
+    }
+
+    pub fn indexed_iter_mut_with<I: MemoryIterator>(&mut self, order: I) -> Option<IndexEnumerate<Vec2DIterMut<'_, I, T>>> {
+        Some(self.iter_mut_with(order)?.iterate_with_mem_index())
+    }
+
+    /// Hand out the cells for the whole lifetime of the slice, in the order of any MemoryIterator over slice relative indexes
+    pub fn into_iter_with<I: MemoryIterator>(self, order: I) -> Option<Vec2DIterMut<'a, I, T>> {
+        Vec2DIterMut::new(order, self.rows)
+    }
+
+    /// Top and bottom views, the top keeps `row` rows. None unless both are non empty
+    pub fn split_at_row(mut self, row: usize) -> Option<(Self, Self)> {
+        if row == 0 || row >= self.row_count() {
+            return None;
+        }
+
+        let (top_rows, bottom_rows) = self.span2d.row_span.split_at(row)?;
+        let bottom = self.rows.split_off(row);
+
+        Some((
+            Vec2DMutSlice { span2d: MemSpan2D { row_span: top_rows, col_span: self.span2d.col_span }, rows: self.rows },
+            Vec2DMutSlice { span2d: MemSpan2D { row_span: bottom_rows, col_span: self.span2d.col_span }, rows: bottom },
+        ))
+    }
+
+    /// Left and right views, the left keeps `col` columns. None unless both are non empty
+    pub fn split_at_col(self, col: usize) -> Option<(Self, Self)> {
+        if col == 0 || col >= self.column_count() {
+            return None;
+        }
+
+        let (left_cols, right_cols) = self.span2d.col_span.split_at(col)?;
+        let (left, right): (Vec<&'a mut [T]>, Vec<&'a mut [T]>) = self.rows
+            .into_iter()
+            .map(|row| row.split_at_mut(col))
+            .unzip();
+
+        Some((
+            Vec2DMutSlice { span2d: MemSpan2D { row_span: self.span2d.row_span, col_span: left_cols }, rows: left },
+            Vec2DMutSlice { span2d: MemSpan2D { row_span: self.span2d.row_span, col_span: right_cols }, rows: right },
+        ))
+    }
+
+    /// Top left, top right, bottom left and bottom right views around a slice relative index.
+    /// None unless all four are non empty
+    pub fn split_quadrants(self, at: MemIndex2D) -> Option<[Self; 4]> {
+        let (top, bottom) = self.split_at_row(at.row)?;
+        let (top_left, top_right) = top.split_at_col(at.col)?;
+        let (bottom_left, bottom_right) = bottom.split_at_col(at.col)?;
+        Some([top_left, top_right, bottom_left, bottom_right])
+    }
+
+    /// Quadrants around the middle, top and left halves take the extra row or column
+    pub fn quadrants(self) -> Option<[Self; 4]> {
+        let middle: MemIndex2D = MemIndex2D::new(self.row_count().div_ceil(2), self.column_count().div_ceil(2));
+        self.split_quadrants(middle)
+    }
+
+    /// View of a span given relative to this view, None if it reaches outside of it
+    pub fn sub_slice(self, span2d: MemSpan2D) -> Option<Self> {
+        let absolute: MemSpan2D = self.span2d.relative_span2d_to_absolute_span2d(&span2d)?;
+        let col_span: MemSpan = span2d.col_span;
+        let rows: Vec<&'a mut [T]> = self.rows
+            .into_iter()
+            .skip(span2d.min_row())
+            .take(span2d.row_count())
+            .map(|row| &mut row[col_span.min..col_span.upper_bound()])
+            .collect();
+
+        Vec2DMutSlice::new(absolute, rows)
+    }
+}
+
+impl<'a, T> IntoIterator for Vec2DMutSlice<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = Vec2DMutSliceIntoIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let order = LinearMemoryIterator::new(self.size().into());
+        let rows = self.rows.into_iter().map(<&'a mut [T]>::into_iter as fn(&'a mut [T]) -> slice::IterMut<'a, T>);
+        RowMajorIter::new(rows, order)
+    }
+}
 
 /// A 2D slice view that contains a vector of row slices
 pub struct Vec2DSlice<'a, T> {
@@ -391,6 +859,22 @@ pub struct Vec2DSlice<'a, T> {
     data: &'a [T]
 }
 
+impl<'a, T> Clone for Vec2DSlice<'a, T> {
+    fn clone(&self) -> Self {
+        Vec2DSlice::new(self.vec_column_count, self.span2d.clone(), self.data)
+    }
+}
+
+impl<'a, T> HasSize2D for Vec2DSlice<'a, T> {
+    fn row_count(&self) -> usize {
+        self.span2d.row_count()
+    }
+
+    fn column_count(&self) -> usize {
+        self.span2d.column_count()
+    }
+}
+
 impl<'a, T> SliceMethods for Vec2DSlice<'a, T>
 {
     fn vec_column_count(&self) -> usize {
@@ -413,26 +897,97 @@ impl<'a, T> Vec2DSlice<'a, T> {
         }
     }
 
-    pub fn get(&self, index2d: MemIndex2D) -> Option<&T> {
+    pub fn get(&self, index2d: MemIndex2D) -> Option<&'a T> {
         let offset: usize = self.index2d_to_offset(index2d)?;
         self.data.get(offset)
     }
 
     /// Get a complete row slice within the 2D slice bounds
-    pub fn get_span_row(&self, row: usize) -> Option<&[T]> {
+    pub fn get_span_row(&self, row: usize) -> Option<&'a [T]> {
         let min_offset: usize = self.min_offset_for_row(row)?;
-        let max_offset: usize = min_offset + self.vec_column_count;
+        let max_offset: usize = min_offset + self.span2d.column_count();
 
         Some(&self.data[min_offset..max_offset])
     }
 
+    /// View of a span given relative to this slice, None if it reaches outside of it
+    pub fn sub_slice(&self, span2d: MemSpan2D) -> Option<Vec2DSlice<'a, T>> {
+        let absolute: MemSpan2D = self.span2d.relative_span2d_to_absolute_span2d(&span2d)?;
+        Some(Vec2DSlice::new(self.vec_column_count, absolute, self.data))
+    }
+
+    /// Top and bottom slices, the top keeps `row` rows. None unless both are non empty
+    pub fn split_at_row(&self, row: usize) -> Option<(Vec2DSlice<'a, T>, Vec2DSlice<'a, T>)> {
+        let columns: usize = self.column_count();
+        Some((
+            self.sub_slice(MemSpan2D::new_from_usize(0, 0, row, columns))?,
+            self.sub_slice(MemSpan2D::new_from_usize(row, 0, self.row_count(), columns))?,
+        ))
+    }
+
+    /// Left and right slices, the left keeps `col` columns. None unless both are non empty
+    pub fn split_at_col(&self, col: usize) -> Option<(Vec2DSlice<'a, T>, Vec2DSlice<'a, T>)> {
+        let rows: usize = self.row_count();
+        Some((
+            self.sub_slice(MemSpan2D::new_from_usize(0, 0, rows, col))?,
+            self.sub_slice(MemSpan2D::new_from_usize(0, col, rows, self.column_count()))?,
+        ))
+    }
+
+    /// Top left, top right, bottom left and bottom right slices around a slice relative index.
+    /// None unless all four are non empty
+    pub fn split_quadrants(&self, at: MemIndex2D) -> Option<[Vec2DSlice<'a, T>; 4]> {
+        let (top, bottom) = self.split_at_row(at.row)?;
+        let (top_left, top_right) = top.split_at_col(at.col)?;
+        let (bottom_left, bottom_right) = bottom.split_at_col(at.col)?;
+        Some([top_left, top_right, bottom_left, bottom_right])
+    }
+
+    /// Quadrants around the middle, top and left halves take the extra row or column
+    pub fn quadrants(&self) -> Option<[Vec2DSlice<'a, T>; 4]> {
+        self.split_quadrants(MemIndex2D::new(self.row_count().div_ceil(2), self.column_count().div_ceil(2)))
+    }
+
     /// Iterator over all rows in the 2D slice
-    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
-        (0..self.span2d.row_count()).map(move |row| self.get_span_row(row).unwrap())
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + Clone + use<'a, T> {
+        let col_span: MemSpan = self.span2d.col_span;
+        self.data
+            .chunks(self.vec_column_count.max(1))
+            .skip(self.span2d.min_row())
+            .take(self.span2d.row_count())
+            .map(move |row| &row[col_span.min..col_span.upper_bound()])
     }
 
-    pub fn iter(&self) -> core::slice::Iter<'_, T> {
-        self.data.iter()
+    /// Columns left to right, each walked top to bottom
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &'a T>> + use<'a, T> {
+        let rows = self.rows();
+        (0..self.span2d.column_count()).map(move |col| rows.clone().map(move |row| &row[col]))
+    }
+
+    /// Cells of the slice in row major order, one run of the parent buffer per row
+    pub fn iter(&self) -> Vec2DSliceIter<'a, T> {
+        let data: &'a [T] = self.data;
+        let cells: &'a [T] = match self.span2d.max_absolute_index2d() {
+            Some(last) => &data[self.span2d.min_row() * self.vec_column_count + self.span2d.min_column()..=last.row * self.vec_column_count + last.col],
+            None => &[],
+        };
+
+        let rows: StridedRows<'a, T> = StridedRows::new(cells, self.vec_column_count, self.span2d.column_count());
+        RowMajorIter::new(rows, LinearMemoryIterator::new(self.span2d.size().into()))
+    }
+
+    /// Cells in the order of any MemoryIterator over slice relative indexes, None if it reaches outside of the slice
+    pub fn iter_with<I: MemoryIterator>(&self, order: I) -> Option<Vec2DIter<'a, I, T>> {
+        Vec2DIter::new(order, self.rows().collect())
+    }
+
+    /// Cells in row major order together with their slice relative index
+    pub fn indexed_iter(&self) -> IndexEnumerate<Vec2DSliceIter<'a, T>> {
+        self.iter().iterate_with_mem_index()
+    }
+
+    pub fn indexed_iter_with<I: MemoryIterator>(&self, order: I) -> Option<IndexEnumerate<Vec2DIter<'a, I, T>>> {
+        Some(self.iter_with(order)?.iterate_with_mem_index())
     }
 
     /// Check if the slice contains a value
@@ -440,11 +995,26 @@ impl<'a, T> Vec2DSlice<'a, T> {
     where
         T: PartialEq,
     {
-        self.data.contains(x)
+        self.rows().any(|row| row.contains(x))
+    }
+
+    /// Copy the cells out into a grid of their own
+    pub fn to_vec2d(&self) -> Vec2D<T>
+    where
+        T: Clone,
+    {
+        Vec2D::new_items_size(self.iter().cloned().collect(), self.span2d.size()).unwrap()
     }
 }
 
-/// Iterator over elements in a Vec2DSlice
+impl<'a, T> IntoIterator for Vec2DSlice<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Vec2DSliceIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
 
 // Example usage and tests
 #[cfg(test)]
@@ -481,7 +1051,7 @@ mod tests {
 
         let row = vec2d.get_row_mut(1).unwrap();
 
-        let mut expected_row = [4, 5, 6, 7];
+        let expected_row = [4, 5, 6, 7];
         assert_eq!(row, expected_row.as_slice());
     }
 
@@ -536,10 +1106,10 @@ mod tests {
         assert_eq!(slice.span2d.row_count(), 2);
 
         // Test element access
-        assert_eq!(slice.get_span_index(0, 0), Some(&5));
-        assert_eq!(slice.get_span_index(0, 1), Some(&6));
-        assert_eq!(slice.get_span_index(1, 0), Some(&9));
-        assert_eq!(slice.get_span_index(1, 1), Some(&10));
+        assert_eq!(slice.get(MemIndex2D::new(0, 0)), Some(&5));
+        assert_eq!(slice.get(MemIndex2D::new(0, 1)), Some(&6));
+        assert_eq!(slice.get(MemIndex2D::new(1, 0)), Some(&9));
+        assert_eq!(slice.get(MemIndex2D::new(1, 1)), Some(&10));
 
         // Test row access
         assert_eq!(slice.get_span_row(0), Some([5, 6].as_slice()));
@@ -551,8 +1121,8 @@ mod tests {
 
         // Test conversion to Vec2D
         let new_vec2d = slice.to_vec2d();
-        assert_eq!(new_vec2d.get(0, 0), Some(&5));
-        assert_eq!(new_vec2d.get(1, 1), Some(&10));
+        assert_eq!(new_vec2d.get_index2d(MemIndex2D::new(0, 0)), Some(&5));
+        assert_eq!(new_vec2d.get_index2d(MemIndex2D::new(1, 1)), Some(&10));
     }
 
     #[test]
@@ -574,14 +1144,14 @@ mod tests {
         assert_eq!(slice.row_count(), 2);
 
         // Test element access
-        assert_eq!(slice.get(0, 0), Some(&5));
-        assert_eq!(slice.get(0, 1), Some(&6));
-        assert_eq!(slice.get(1, 0), Some(&9));
-        assert_eq!(slice.get(1, 1), Some(&10));
+        assert_eq!(slice.get(MemIndex2D::new(0, 0)), Some(&5));
+        assert_eq!(slice.get(MemIndex2D::new(0, 1)), Some(&6));
+        assert_eq!(slice.get(MemIndex2D::new(1, 0)), Some(&9));
+        assert_eq!(slice.get(MemIndex2D::new(1, 1)), Some(&10));
 
         // Test row access
-        assert_eq!(slice.get_row(0), Some([5, 6].as_slice()));
-        assert_eq!(slice.get_row(1), Some([9, 10].as_slice()));
+        assert_eq!(slice.get_span_row(0), Some([5, 6].as_slice()));
+        assert_eq!(slice.get_span_row(1), Some([9, 10].as_slice()));
 
         // Test iterator
         let values: Vec<i32> = slice.iter().cloned().collect();
@@ -589,8 +1159,8 @@ mod tests {
 
         // Test conversion to Vec2D
         let new_vec2d = slice.to_vec2d();
-        assert_eq!(new_vec2d.get(0, 0), Some(&5));
-        assert_eq!(new_vec2d.get(1, 1), Some(&10));
+        assert_eq!(new_vec2d.get_index2d(MemIndex2D::new(0, 0)), Some(&5));
+        assert_eq!(new_vec2d.get_index2d(MemIndex2D::new(1, 1)), Some(&10));
     }
 
     #[test]
@@ -608,7 +1178,7 @@ mod tests {
         let mut slice = vec2d.get_slice(MemSpan2D::new_from_usize(1, 1, 2, 2)).unwrap();
         assert_eq!(slice.span2d.column_count(), 1);
         assert_eq!(slice.span2d.row_count(), 1);
-        assert_eq!(slice.get_span_index(0, 0), Some(&4));
+        assert_eq!(slice.get(MemIndex2D::new(0, 0)), Some(&4));
 
         assert_eq!([6,7,8].as_slice(), vec2d.get_row(2).unwrap());
 
@@ -638,7 +1208,7 @@ mod tests {
         let mut slice = vec2d.get_slice_mut(MemSpan2D::new_from_usize(1, 1, 2, 2)).unwrap();
         assert_eq!(slice.column_count(), 1);
         assert_eq!(slice.row_count(), 1);
-        assert_eq!(slice.get(0, 0), Some(&4));
+        assert_eq!(slice.get(MemIndex2D::new(0, 0)), Some(&4));
 
         assert_eq!([6, 7, 8].as_slice(), vec2d.get_row(2).unwrap());
 
@@ -646,7 +1216,7 @@ mod tests {
         slice = vec2d.get_slice_mut(MemSpan2D::new_from_usize(2, 0, 3, 3)).unwrap();
         assert_eq!(slice.column_count(), 3);
         assert_eq!(slice.row_count(), 1);
-        assert_eq!(slice.get_row(0), Some([6, 7, 8].as_slice()));
+        assert_eq!(slice.get_span_row(0), Some([6, 7, 8].as_slice()));
 
         // Invalid bounds should return None
         assert!(vec2d.get_slice_mut(MemSpan2D::new_from_usize(0, 0, 5, 5)).is_none());
@@ -669,7 +1239,7 @@ mod tests {
         assert!(!slice.contains(&9));
 
         // Test map
-        let doubled: Vec<i32> = slice.map(|x| x * 2);
+        let doubled: Vec<i32> = slice.iter().map(|x| x * 2).collect();
         assert_eq!(doubled, vec![2, 4, 8, 10]);
 
         // Test rows iterator
@@ -707,8 +1277,8 @@ mod tests {
         assert!(chunks.is_some(), "Expected some, provided spans do not overlap");
         let mut chunks_unwrap = chunks.unwrap();
 
-        let mut chunk_0 = chunks_unwrap[0].iter().map(|i| i.clone()).collect::<Vec<i32>>();
-        let mut chunk_1 = chunks_unwrap[1].iter().map(|i| i.clone()).collect::<Vec<i32>>();
+        let mut chunk_0 = chunks_unwrap[0].iter().copied().collect::<Vec<i32>>();
+        let mut chunk_1 = chunks_unwrap[1].iter().copied().collect::<Vec<i32>>();
 
         assert_eq!(chunk_0, vec![1, 2]);
         assert_eq!(chunk_1, vec![7, 8]);
@@ -721,8 +1291,8 @@ mod tests {
         assert!(chunks.is_some(), "Expected some, provided spans do not overlap");
 
         chunks_unwrap = chunks.unwrap();
-        chunk_0 = chunks_unwrap[0].iter().map(|i| i.clone()).collect::<Vec<i32>>();
-        chunk_1 = chunks_unwrap[1].iter().map(|i| i.clone()).collect::<Vec<i32>>();
+        chunk_0 = chunks_unwrap[0].iter().copied().collect::<Vec<i32>>();
+        chunk_1 = chunks_unwrap[1].iter().copied().collect::<Vec<i32>>();
 
         assert_eq!(chunk_0, vec![1, 2, 3]);
         assert_eq!(chunk_1, vec![4, 5, 6]);
@@ -756,8 +1326,8 @@ mod tests {
         assert!(chunks.is_some(), "Expected some, provided spans do not overlap");
         let mut chunks_unwrap = chunks.unwrap();
 
-        let mut chunk_0 = chunks_unwrap[0].iter().map(|i| i.clone()).collect::<Vec<i32>>();
-        let mut chunk_1 = chunks_unwrap[1].iter().map(|i| i.clone()).collect::<Vec<i32>>();
+        let mut chunk_0 = chunks_unwrap[0].iter().copied().collect::<Vec<i32>>();
+        let mut chunk_1 = chunks_unwrap[1].iter().copied().collect::<Vec<i32>>();
 
         assert_eq!(chunk_0, vec![1, 2]);
         assert_eq!(chunk_1, vec![7, 8]);
@@ -770,11 +1340,292 @@ mod tests {
         assert!(chunks.is_some(), "Expected some, provided spans do not overlap");
 
         chunks_unwrap = chunks.unwrap();
-        chunk_0 = chunks_unwrap[0].iter().map(|i| i.clone()).collect::<Vec<i32>>();
-        chunk_1 = chunks_unwrap[1].iter().map(|i| i.clone()).collect::<Vec<i32>>();
+        chunk_0 = chunks_unwrap[0].iter().copied().collect::<Vec<i32>>();
+        chunk_1 = chunks_unwrap[1].iter().copied().collect::<Vec<i32>>();
 
         assert_eq!(chunk_0, vec![1, 2, 3]);
         assert_eq!(chunk_1, vec![4, 5, 6]);
     }
+
+    #[test]
+    fn test_copy_region_from() {
+        let src = Vec2D::from_vec((0..16).collect::<Vec<i32>>(), 4).unwrap();
+        let mut dst = Vec2D::new_size_reference(Size2D::new(3, 5), &-1);
+
+        // Copy the center 2x2 of src into the top right corner of dst
+        let copied = dst.copy_region_from(&MemSpan2D::new_from_usize(0, 3, 2, 5), &src, &MemSpan2D::new_from_usize(1, 1, 3, 3));
+        assert_eq!(Some(4), copied);
+        assert_eq!(5, dst[MemIndex2D::new(0, 3)]);
+        assert_eq!(10, dst[MemIndex2D::new(1, 4)]);
+        assert_eq!(-1, dst[MemIndex2D::new(0, 2)]);
+        assert_eq!(-1, dst[MemIndex2D::new(2, 3)]);
+
+        // Mismatched sizes and out of bounds spans copy nothing
+        assert!(dst.copy_region_from(&MemSpan2D::new_from_usize(0, 0, 2, 2), &src, &MemSpan2D::new_from_usize(0, 0, 2, 3)).is_none());
+        assert!(dst.copy_region_from(&MemSpan2D::new_from_usize(2, 0, 4, 2), &src, &MemSpan2D::new_from_usize(0, 0, 2, 2)).is_none());
+    }
+
+    #[test]
+    fn test_mut_slice_splits() {
+        let mut vec2d = Vec2D::from_vec((0..30).collect::<Vec<i32>>(), 6).unwrap();
+        let slice = vec2d.get_slice_mut(MemSpan2D::new_from_usize(1, 1, 5, 5)).unwrap();
+        assert_eq!(Some(&7), slice.get(MemIndex2D::origin()));
+        assert_eq!(Some([13, 14, 15, 16].as_slice()), slice.get_span_row(1));
+
+        let [mut top_left, top_right, bottom_left, mut bottom_right] = slice.split_quadrants(MemIndex2D::new(1, 3)).unwrap();
+        assert_eq!(MemSpan2D::new_from_usize(1, 1, 2, 4), top_left.span2d);
+        assert_eq!(MemSpan2D::new_from_usize(1, 4, 2, 5), top_right.span2d);
+        assert_eq!(MemSpan2D::new_from_usize(2, 1, 5, 4), bottom_left.span2d);
+        assert_eq!(vec![16, 22, 28], bottom_right.iter().copied().collect::<Vec<i32>>());
+
+        top_left.iter_mut().for_each(|v| *v = -1);
+        bottom_right[MemIndex2D::new(2, 0)] = -2;
+        assert!(top_left.reborrow().split_at_row(1).is_none());
+        assert!(top_right.split_at_col(0).is_none());
+        drop(bottom_left);
+
+        assert_eq!(-1, vec2d[MemIndex2D::new(1, 3)]);
+        assert_eq!(10, vec2d[MemIndex2D::new(1, 4)]);
+        assert_eq!(-2, vec2d[MemIndex2D::new(4, 4)]);
+        assert!(vec2d.get_slice_mut(MemSpan2D::new_from_usize(3, 3, 6, 5)).is_none());
+    }
+
+    #[test]
+    fn test_nested_slices() {
+        let vec2d = Vec2D::from_vec((0..48).collect::<Vec<i32>>(), 8).unwrap();
+        let slice = vec2d.get_slice(MemSpan2D::new_from_usize(1, 2, 6, 7)).unwrap();
+
+        let inner = slice.sub_slice(MemSpan2D::new_from_usize(1, 1, 4, 4)).unwrap();
+        assert_eq!(MemSpan2D::new_from_usize(2, 3, 5, 6), inner.span2d);
+        assert_eq!(Some(&19), inner.get(MemIndex2D::origin()));
+        let innermost = inner.sub_slice(MemSpan2D::new_from_usize(2, 1, 3, 3)).unwrap();
+        assert_eq!(Some([36, 37].as_slice()), innermost.get_span_row(0));
+        assert!(inner.sub_slice(MemSpan2D::new_from_usize(2, 1, 4, 3)).is_none());
+
+        let (top, bottom) = slice.split_at_row(2).unwrap();
+        assert_eq!((2, 3), (top.row_count(), bottom.row_count()));
+        assert_eq!(Some(&26), bottom.get(MemIndex2D::origin()));
+        let (left, right) = bottom.split_at_col(4).unwrap();
+        assert_eq!(Some([26, 27, 28, 29].as_slice()), left.get_span_row(0));
+        assert_eq!(Some([38].as_slice()), right.get_span_row(1));
+        assert!(slice.split_at_row(5).is_none());
+        assert!(slice.split_at_col(0).is_none());
+
+        let [top_left, top_right, bottom_left, bottom_right] = slice.quadrants().unwrap();
+        assert_eq!(MemSpan2D::new_from_usize(1, 2, 4, 5), top_left.span2d);
+        assert_eq!(MemSpan2D::new_from_usize(1, 5, 4, 7), top_right.span2d);
+        assert_eq!(MemSpan2D::new_from_usize(4, 2, 6, 5), bottom_left.span2d);
+        assert_eq!(Some(&45), bottom_right.get(MemIndex2D::new(1, 0)));
+        assert!(innermost.quadrants().is_none());
+    }
+
+    #[test]
+    fn test_nested_mut_slices() {
+        let mut vec2d = Vec2D::new_size_reference(Size2D::new(6, 6), &0);
+        let slice = vec2d.get_slice_mut(MemSpan2D::new_from_usize(1, 1, 6, 6)).unwrap();
+
+        let mut inner = slice.sub_slice(MemSpan2D::new_from_usize(1, 0, 5, 4)).unwrap();
+        assert_eq!(MemSpan2D::new_from_usize(2, 1, 6, 5), inner.span2d);
+        assert!(inner.reborrow().sub_slice(MemSpan2D::new_from_usize(0, 0, 5, 1)).is_none());
+
+        let quadrants = inner.quadrants().unwrap();
+        for (i, mut quadrant) in quadrants.into_iter().enumerate() {
+            quadrant.iter_mut().for_each(|v| *v = i + 1);
+        }
+
+        assert_eq!(vec![0, 0, 0, 0, 0, 0], vec2d.get_row(1).unwrap().to_vec());
+        assert_eq!(vec![0, 1, 1, 2, 2, 0], vec2d.get_row(3).unwrap().to_vec());
+        assert_eq!(vec![0, 3, 3, 4, 4, 0], vec2d.get_row(5).unwrap().to_vec());
+    }
+
+    #[test]
+    fn test_non_overlapping_chunks_mut_side_by_side() {
+        let mut vec2d = Vec2D::new_size_reference(Size2D::new(3, 5), &0);
+        let spans = vec![
+            MemSpan2D::new_from_usize(0, 3, 3, 5),
+            MemSpan2D::new_from_usize(1, 0, 3, 3),
+            MemSpan2D::new_from_usize(0, 1, 1, 2),
+        ];
+
+        let mut chunks = vec2d.get_non_overlapping_chunks_mut(spans).unwrap();
+        for (i, chunk) in chunks.iter_mut().enumerate() {
+            chunk.iter_mut().for_each(|v| *v = i + 1);
+        }
+        drop(chunks);
+
+        assert_eq!(vec![0, 3, 0, 1, 1], vec2d.get_row(0).unwrap().to_vec());
+        assert_eq!(vec![2, 2, 2, 1, 1], vec2d.get_row(2).unwrap().to_vec());
+        assert!(vec2d.get_non_overlapping_chunks_mut(vec![MemSpan2D::new_from_usize(2, 4, 4, 5)]).is_none());
+    }
+
+    #[test]
+    fn test_iterator_family() {
+        use memory_math::memory_iterators::BoustrophedonIterator;
+        use memory_math::traversal_iterators::ColumnMajorIterator;
+
+        let mut vec2d = Vec2D::from_vec((0..12).collect::<Vec<i32>>(), 4).unwrap();
+        assert_eq!((0..12).collect::<Vec<i32>>(), vec2d.iter().copied().collect::<Vec<i32>>());
+        assert_eq!(vec![11, 10], vec2d.iter().rev().take(2).copied().collect::<Vec<i32>>());
+        assert_eq!(12, vec2d.iter().len());
+
+        let snake = vec2d.iter_with(BoustrophedonIterator::new(vec2d.size.into())).unwrap();
+        assert_eq!(vec![0, 1, 2, 3, 7, 6, 5, 4, 8], snake.take(9).copied().collect::<Vec<i32>>());
+        assert!(vec2d.iter_with(LinearMemoryIterator::new(MemSpan2D::new_from_usize(0, 0, 4, 4))).is_none());
+
+        let column_major = ColumnMajorIterator::new(MemSpan2D::new_from_usize(1, 1, 3, 3));
+        let (index, value) = vec2d.indexed_iter_with(column_major).unwrap().nth(1).unwrap();
+        assert_eq!((MemIndex2D::new(2, 1), &9), (index, value));
+
+        for (index, value) in vec2d.indexed_iter_mut() {
+            *value += (index.row * 100) as i32;
+        }
+        assert_eq!(vec![1, 105, 209], vec2d.columns().nth(1).unwrap().copied().collect::<Vec<i32>>());
+        assert_eq!(vec![[208, 209, 210, 211].as_slice()], vec2d.rows().skip(2).collect::<Vec<&[i32]>>());
+        (&mut vec2d).into_iter().for_each(|value| *value %= 100);
+
+        let owned: Vec<i32> = vec2d.clone().into_iter_with(ColumnMajorIterator::new(vec2d.size.into())).unwrap().take(4).collect();
+        assert_eq!(vec![0, 4, 8, 1], owned);
+        let pairs: Vec<(MemIndex2D, i32)> = vec2d.into_iter().iterate_with_mem_index().skip(10).collect();
+        assert_eq!(vec![(MemIndex2D::new(2, 2), 10), (MemIndex2D::new(2, 3), 11)], pairs);
+    }
+
+    #[test]
+    fn test_ordered_iterators_hand_out_each_cell_once() {
+        use std::rc::Rc;
+        use memory_math::traversal_iterators::ColumnMajorIterator;
+
+        let mut vec2d = Vec2D::from_vec((0..12).collect::<Vec<i32>>(), 4).unwrap();
+        let mut cells = vec2d.iter_mut_with(ColumnMajorIterator::new(MemSpan2D::new_from_usize(0, 1, 3, 3))).unwrap();
+        assert_eq!(6, cells.len());
+        *cells.next().unwrap() = -1;
+        *cells.next_back().unwrap() = -2;
+        assert_eq!(4, cells.len());
+        assert_eq!(4, cells.count());
+        assert_eq!(vec![-1, 2, 9, -2], [1, 2, 9, 10].iter().map(|&i| vec2d.items[i]).collect::<Vec<i32>>());
+
+        //every cell is dropped once, whether moved out, skipped, never reached or outside of the order
+        let shared: Rc<()> = Rc::new(());
+        let grid = Vec2D::from_vec(vec![shared.clone(); 12], 4).unwrap();
+        let mut owned = grid.into_iter_with(ColumnMajorIterator::new(MemSpan2D::new_from_usize(1, 1, 3, 3))).unwrap();
+        assert_eq!(5, Rc::strong_count(&shared));
+        assert_eq!(4, owned.len());
+        let kept = owned.nth(1).unwrap();
+        assert_eq!(2, owned.len());
+        assert_eq!(4, Rc::strong_count(&shared));
+        drop(owned);
+        assert_eq!(2, Rc::strong_count(&shared));
+        drop(kept);
+        assert_eq!(1, Rc::strong_count(&shared));
+    }
+
+    #[test]
+    fn test_slice_iterators() {
+        use memory_math::traversal_iterators::ColumnMajorIterator;
+
+        let mut vec2d = Vec2D::from_vec((0..20).collect::<Vec<i32>>(), 5).unwrap();
+        {
+            let slice = vec2d.get_slice(MemSpan2D::new_from_usize(1, 1, 3, 4)).unwrap();
+            assert_eq!(vec![6, 7, 8, 11, 12, 13], slice.iter().copied().collect::<Vec<i32>>());
+            assert_eq!(vec![8, 13], slice.columns().last().unwrap().copied().collect::<Vec<i32>>());
+            assert!(!slice.contains(&0) && slice.contains(&13));
+
+            let last = slice.indexed_iter().last().unwrap();
+            assert_eq!((MemIndex2D::new(1, 2), &13), last);
+            let column_major = ColumnMajorIterator::new(slice.span2d.size().into());
+            assert_eq!(vec![6, 11, 7], slice.iter_with(column_major).unwrap().take(3).copied().collect::<Vec<i32>>());
+        }
+
+        let slice = vec2d.get_slice_mut(MemSpan2D::new_from_usize(2, 3, 4, 5)).unwrap();
+        let (top, bottom) = slice.split_at_row(1).unwrap();
+        for (index, value) in bottom.into_iter().iterate_with_mem_index() {
+            *value = -(index.col as i32) - 1;
+        }
+        top.into_iter().for_each(|value| *value = 0);
+        assert_eq!(vec![15, 16, 17, -1, -2], vec2d.rows().nth(3).unwrap().to_vec());
+        assert_eq!(vec![10, 11, 12, 0, 0], vec2d.rows().nth(2).unwrap().to_vec());
+    }
+
+    #[test]
+    fn test_linear_iterators_match_order_driven() {
+        let mut vec2d = Vec2D::from_vec((0..20).collect::<Vec<i32>>(), 5).unwrap();
+        let linear = |size: Size2D| LinearMemoryIterator::new(size.into());
+
+        let slice = vec2d.get_slice(MemSpan2D::new_from_usize(1, 1, 4, 4)).unwrap();
+        assert_eq!(slice.iter_with(linear(slice.size())).unwrap().rev().collect::<Vec<&i32>>(), slice.iter().rev().collect::<Vec<&i32>>());
+
+        //meeting in the middle from both ends hands out every cell once
+        let mut both_ends = slice.iter();
+        assert_eq!((Some(&6), Some(&18)), (both_ends.next(), both_ends.next_back()));
+        assert_eq!(7, both_ends.len());
+        assert_eq!(vec![7, 8, 11, 12, 13, 16, 17], both_ends.copied().collect::<Vec<i32>>());
+
+        let no_columns: Vec2D<i32> = Vec2D::new_items_size(Vec::new(), Size2D::new(3, 0)).unwrap();
+        assert_eq!(0, no_columns.iter().count());
+
+        let mut slice = vec2d.get_slice_mut(MemSpan2D::new_from_usize(0, 3, 4, 5)).unwrap();
+        assert_eq!(vec![19, 18, 14], slice.iter().rev().take(3).copied().collect::<Vec<i32>>());
+        let mut cells = slice.iter_mut();
+        *cells.next_back().unwrap() = -1;
+        *cells.next().unwrap() = -2;
+        assert_eq!(vec![-2, 4, 8, 9, 13, 14, 18, -1], slice.into_iter().map(|value| *value).collect::<Vec<i32>>());
+
+        let owned: Vec<i32> = vec2d.into_iter().rev().take(2).collect();
+        assert_eq!(vec![-1, 18], owned);
+    }
+
+    #[test]
+    fn test_row_and_column_edits() {
+        let mut vec2d = Vec2D::from_vec((0..6).collect::<Vec<i32>>(), 3).unwrap();
+
+        assert!(vec2d.insert_row(1, [10, 11, 12]));
+        assert!(!vec2d.insert_row(1, [10, 11]));
+        assert!(!vec2d.insert_row(4, [10, 11, 12]));
+        assert_eq!(vec![0, 1, 2, 10, 11, 12, 3, 4, 5], vec2d.iter().copied().collect::<Vec<i32>>());
+
+        assert!(vec2d.insert_column(3, [-1, -2, -3]));
+        assert!(vec2d.insert_column(0, [7, 8, 9]));
+        assert!(!vec2d.insert_column(1, [7, 8]));
+        assert_eq!(Size2D::new(3, 5), vec2d.size);
+        assert_eq!(vec![8, 10, 11, 12, -2], vec2d.get_row(1).unwrap().to_vec());
+
+        assert_eq!(Some(vec![1, 11, 4]), vec2d.remove_column(2));
+        assert_eq!(Some(vec![8, 10, 12, -2]), vec2d.remove_row(1));
+        assert!(vec2d.remove_row(2).is_none());
+        assert!(vec2d.remove_column(4).is_none());
+        assert_eq!(vec![7, 0, 2, -1, 9, 3, 5, -3], vec2d.iter().copied().collect::<Vec<i32>>());
+
+        //an empty grid takes its shape from the first row or column
+        let mut empty: Vec2D<i32> = Vec2D::new_items_size(Vec::new(), Size2D::new(0, 0)).unwrap();
+        assert!(empty.insert_column(0, [1, 2]));
+        assert!(empty.insert_row(2, [3]));
+        assert_eq!(Size2D::new(3, 1), empty.size);
+        assert_eq!(vec![1, 2, 3], empty.into_iter().collect::<Vec<i32>>());
+    }
+
+    #[test]
+    fn test_resize_crop_pad() {
+        let grid = Vec2D::from_vec((1..=6).collect::<Vec<i32>>(), 3).unwrap();
+
+        let mut padded = grid.clone();
+        assert!(padded.pad(1, 0, 2, 1, &0));
+        assert_eq!(Size2D::new(3, 6), padded.size);
+        assert_eq!(vec![0, 0, 4, 5, 6, 0], padded.get_row(2).unwrap().to_vec());
+
+        assert!(padded.crop(&MemSpan2D::new_from_usize(1, 2, 3, 5)));
+        assert_eq!(grid.iter().collect::<Vec<&i32>>(), padded.iter().collect::<Vec<&i32>>());
+        assert!(!padded.crop(&MemSpan2D::new_from_usize(1, 2, 3, 5)));
+
+        let mut grown = grid.clone();
+        assert!(grown.resize(Size2D::new(4, 4), &0, ResizeAnchor::BottomRight));
+        assert_eq!(vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 0, 4, 5, 6], grown.iter().copied().collect::<Vec<i32>>());
+
+        let mut centered = grid.clone();
+        assert!(centered.resize(Size2D::new(4, 1), &0, ResizeAnchor::Center));
+        assert_eq!(vec![0, 2, 5, 0], centered.iter().copied().collect::<Vec<i32>>());
+
+        let mut shrunk = grid.clone();
+        assert!(shrunk.resize(Size2D::new(1, 2), &0, ResizeAnchor::TopRight));
+        assert_eq!(vec![2, 3], shrunk.iter().copied().collect::<Vec<i32>>());
+    }
 }
 
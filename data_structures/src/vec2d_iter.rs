@@ -1,28 +1,247 @@
-use std::{usize, vec::IntoIter};
+use std::{option, ptr, slice, vec};
+use std::iter::Map;
 use std::marker::PhantomData;
-use std::ops::Index;
+
 use memory_math::{memory_index2d::MemIndex2D, memory_range_iter::HasCurMemIndex};
 use memory_math::memory_iterators::{LinearMemoryIterator, MemoryIterator};
-use crate::vec2d::{Vec2D};
+use memory_math::memory_span2d::MemSpan2D;
+use memory_math::size_2d::Size2D;
 
-trait Vec2DIterator<'a, I: MemoryIterator, T>
-{
-    fn span_iterator(&mut self) -> &mut I;
+/// True if every index visited by order lies inside a view of size
+fn order_fits<I: MemoryIterator>(order: &I, size: Size2D) -> bool {
+    !order.extents().valid() || size.contains_span2d(order.extents())
+}
+
+/// Width shared by all rows, None if they differ
+fn common_width(row_lengths: impl Iterator<Item = usize>) -> Option<usize> {
+    let mut row_lengths = row_lengths.peekable();
+    let width: usize = row_lengths.peek().copied().unwrap_or(0);
+    row_lengths.all(|len| len == width).then_some(width)
+}
+
+fn cur_mem_index<I: MemoryIterator>(order: &I) -> MemIndex2D {
+    order.current_index().unwrap_or(order.extents().min_absolute_index2d())
+}
+
+/// Shared references to the cells of a 2d view, visited in the order of a MemoryIterator.
+/// The order runs over view relative indexes, so any traversal of the view's size can drive it.
+pub struct Vec2DIter<'a, I, T> {
+    order: I,
+    rows: Vec<&'a [T]>,
+}
+
+impl<'a, I: MemoryIterator, T> Vec2DIter<'a, I, T> {
+
+    /// rows are the equally wide rows of the view, None if order reaches outside of them
+    pub fn new(order: I, rows: Vec<&'a [T]>) -> Option<Self> {
+        let width: usize = common_width(rows.iter().map(|row| row.len()))?;
+        if !order_fits(&order, Size2D::new(rows.len(), width)) {
+            return None;
+        }
+
+        Some(Vec2DIter { order, rows })
+    }
+
+    pub fn order(&self) -> &I {
+        &self.order
+    }
+
+    fn get(&self, index2d: MemIndex2D) -> Option<&'a T> {
+        let row: &'a [T] = self.rows.get(index2d.row)?;
+        row.get(index2d.col)
+    }
+}
+
+impl<'a, I: MemoryIterator, T> Iterator for Vec2DIter<'a, I, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index2d: MemIndex2D = self.order.next()?;
+        self.get(index2d)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let index2d: MemIndex2D = self.order.nth(n)?;
+        self.get(index2d)
+    }
+}
+
+impl<'a, I: MemoryIterator, T> DoubleEndedIterator for Vec2DIter<'a, I, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index2d: MemIndex2D = self.order.next_back()?;
+        self.get(index2d)
+    }
+}
+
+impl<'a, I: MemoryIterator, T> ExactSizeIterator for Vec2DIter<'a, I, T> {}
+
+impl<'a, I: MemoryIterator, T> HasCurMemIndex for Vec2DIter<'a, I, T> {
+    fn get_cur_mem_index(&self) -> MemIndex2D {
+        cur_mem_index(&self.order)
+    }
+}
 
-    fn get(&self, index2d: MemIndex2D) -> Option<&'a T>;
+/// Ranks along the order of the cells handed out so far from either end.
+/// A MemoryIterator visits every cell of its extents exactly once, so each new cell has to rank strictly
+/// between them; debug builds check that, since the iterators below hand out cells on the strength of it.
+struct VisitedRanks {
+    front: usize,
+    back: usize,
+}
+
+impl VisitedRanks {
+
+    fn new() -> Self {
+        VisitedRanks { front: 0, back: usize::MAX }
+    }
+
+    fn check<I: MemoryIterator>(&mut self, order: &I, index2d: MemIndex2D, from_back: bool) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+
+        let rank: Option<usize> = order.rank(index2d);
+        debug_assert!(
+            rank.is_some_and(|rank| self.front <= rank && rank < self.back),
+            "order visited {} twice or outside of its own ranks", index2d
+        );
+
+        let rank: usize = rank.unwrap_or(self.front);
+        if from_back {
+            self.back = rank;
+        } else {
+            self.front = rank + 1;
+        }
+    }
+}
+
+/// Mutable references to the cells of a 2d view, visited in the order of a MemoryIterator.
+/// Cells are reached straight through the row starts, which is sound because the order visits each one once.
+pub struct Vec2DIterMut<'a, I, T> {
+    order: I,
+    rows: Vec<*mut T>,
+    column_count: usize,
+    visited: VisitedRanks,
+    marker: PhantomData<&'a mut T>,
+}
+
+// SAFETY: the iterator only hands out disjoint &'a mut T, exactly like slice::IterMut
+unsafe impl<'a, I: Send, T: Send> Send for Vec2DIterMut<'a, I, T> {}
+unsafe impl<'a, I: Sync, T: Sync> Sync for Vec2DIterMut<'a, I, T> {}
+
+impl<'a, I: MemoryIterator, T> Vec2DIterMut<'a, I, T> {
+
+    /// rows are the equally wide rows of the view, None if order reaches outside of them
+    pub fn new(order: I, rows: Vec<&'a mut [T]>) -> Option<Self> {
+        let column_count: usize = common_width(rows.iter().map(|row| row.len()))?;
+        if !order_fits(&order, Size2D::new(rows.len(), column_count)) {
+            return None;
+        }
+
+        let rows: Vec<*mut T> = rows.into_iter().map(|row| row.as_mut_ptr()).collect();
+        Some(Vec2DIterMut { order, rows, column_count, visited: VisitedRanks::new(), marker: PhantomData })
+    }
+
+    pub fn order(&self) -> &I {
+        &self.order
+    }
+
+    fn take(&mut self, index2d: MemIndex2D, from_back: bool) -> Option<&'a mut T> {
+        if index2d.col >= self.column_count {
+            return None;
+        }
 
-    fn next_item(&mut self) -> Option<&'a T>
-    {
-        self.span_iterator().next().and_then(|i| self.get(i))
+        let row: *mut T = *self.rows.get(index2d.row)?;
+        self.visited.check(&self.order, index2d, from_back);
+        // SAFETY: the cell lies inside a row borrowed for 'a, and the order hands out each index once
+        Some(unsafe { &mut *row.add(index2d.col) })
     }
 }
 
+impl<'a, I: MemoryIterator, T> Iterator for Vec2DIterMut<'a, I, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index2d: MemIndex2D = self.order.next()?;
+        self.take(index2d, false)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let index2d: MemIndex2D = self.order.nth(n)?;
+        self.take(index2d, false)
+    }
+}
+
+impl<'a, I: MemoryIterator, T> DoubleEndedIterator for Vec2DIterMut<'a, I, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index2d: MemIndex2D = self.order.next_back()?;
+        self.take(index2d, true)
+    }
+}
+
+impl<'a, I: MemoryIterator, T> ExactSizeIterator for Vec2DIterMut<'a, I, T> {}
+
+impl<'a, I: MemoryIterator, T> HasCurMemIndex for Vec2DIterMut<'a, I, T> {
+    fn get_cur_mem_index(&self) -> MemIndex2D {
+        cur_mem_index(&self.order)
+    }
+}
+
+/// Owned cells of a Vec2D, moved out in the order of a MemoryIterator.
+/// Cells outside of the order's extents are dropped up front, cells it has not reached yet with the iterator.
 pub struct Vec2DIntoIter<I, T>
     where
     I: MemoryIterator
 {
     iter: I,
-    items: Vec2D<T>,
+    /// Always empty, the cells in its buffer are owned by the order and moved out as it visits them
+    items: Vec<T>,
+    size: Size2D,
+    visited: VisitedRanks,
+}
+
+impl<I: MemoryIterator, T> Vec2DIntoIter<I, T> {
+
+    /// items are the cells of a row major grid of size, None if iter reaches outside of it
+    pub fn new(iter: I, mut items: Vec<T>, size: Size2D) -> Option<Self> {
+        if Some(items.len()) != size.area() || !order_fits(&iter, size) {
+            return None;
+        }
+
+        let extents: MemSpan2D = iter.extents().clone();
+        // SAFETY: the length is cleared before any cell is dropped or moved, so the Vec only ever frees its buffer
+        unsafe { items.set_len(0) };
+        for (row, col) in (0..size.row_count).flat_map(|row| (0..size.column_count).map(move |col| (row, col))) {
+            if !extents.valid() || !extents.contains_index2d(&MemIndex2D::new(row, col)) {
+                // SAFETY: the order never visits this cell, so nothing else will read or drop it
+                unsafe { ptr::drop_in_place(items.as_mut_ptr().add(row * size.column_count + col)) };
+            }
+        }
+
+        Some(Vec2DIntoIter { iter, items, size, visited: VisitedRanks::new() })
+    }
+
+    fn take(&mut self, index2d: MemIndex2D, from_back: bool) -> Option<T> {
+        let index: usize = self.size.index2d_in_bounds(&index2d).then(|| Size2D::index2d_to_index_unchecked(self.size.column_count, index2d))?;
+        self.visited.check(&self.iter, index2d, from_back);
+        // SAFETY: the cell lies inside the buffer, is still initialized and the order moves it out only once
+        Some(unsafe { ptr::read(self.items.as_ptr().add(index)) })
+    }
+}
+
+impl<I: MemoryIterator, T> Drop for Vec2DIntoIter<I, T> {
+    fn drop(&mut self) {
+        //whatever the order has not reached yet is still owned here
+        for _ in self.by_ref() {}
+    }
 }
 
 impl<I: MemoryIterator, T> Iterator for Vec2DIntoIter<I, T>
@@ -30,7 +249,160 @@ impl<I: MemoryIterator, T> Iterator for Vec2DIntoIter<I, T>
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().and_then(|i| Some(&self.items[i] ))
+        let index2d: MemIndex2D = self.iter.next()?;
+        self.take(index2d, false)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        //skipped cells still have to be dropped, so walk them rather than seeking the order
+        for _ in 0..n {
+            self.next()?;
+        }
+
+        self.next()
     }
 }
 
+impl<I: MemoryIterator, T> DoubleEndedIterator for Vec2DIntoIter<I, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index2d: MemIndex2D = self.iter.next_back()?;
+        self.take(index2d, true)
+    }
+}
+
+impl<I: MemoryIterator, T> ExactSizeIterator for Vec2DIntoIter<I, T> {}
+
+impl<I: MemoryIterator, T> HasCurMemIndex for Vec2DIntoIter<I, T> {
+    fn get_cur_mem_index(&self) -> MemIndex2D {
+        cur_mem_index(&self.iter)
+    }
+}
+
+/// Cells of a 2d view in row major order, walked straight through each row's own slice iterator.
+/// Unlike the order driven iterators nothing is gathered up front, the order only follows along
+/// to report the current index and the remaining length.
+pub struct RowMajorIter<R: Iterator> {
+    rows: R,
+    front: R::Item,
+    back: R::Item,
+    order: LinearMemoryIterator,
+}
+
+/// Row major cells of a whole Vec2D, a single run over its buffer
+pub type Vec2DLinearIter<'a, T> = RowMajorIter<option::IntoIter<slice::Iter<'a, T>>>;
+pub type Vec2DLinearIterMut<'a, T> = RowMajorIter<option::IntoIter<slice::IterMut<'a, T>>>;
+pub type Vec2DLinearIntoIter<T> = RowMajorIter<option::IntoIter<vec::IntoIter<T>>>;
+
+/// Row major cells of a Vec2DSlice, one run per row of the parent buffer
+pub type Vec2DSliceIter<'a, T> = RowMajorIter<StridedRows<'a, T>>;
+
+/// Row major cells of a Vec2DMutSlice, walking the row slices it already holds
+pub type Vec2DMutSliceIter<'b, 'a, T> = RowMajorIter<Map<slice::Iter<'b, &'a mut [T]>, for<'r> fn(&'r &'a mut [T]) -> slice::Iter<'r, T>>>;
+pub type Vec2DMutSliceIterMut<'b, 'a, T> = RowMajorIter<Map<slice::IterMut<'b, &'a mut [T]>, for<'r> fn(&'r mut &'a mut [T]) -> slice::IterMut<'r, T>>>;
+pub type Vec2DMutSliceIntoIter<'a, T> = RowMajorIter<Map<vec::IntoIter<&'a mut [T]>, fn(&'a mut [T]) -> slice::IterMut<'a, T>>>;
+
+impl<R> RowMajorIter<R>
+where
+    R: DoubleEndedIterator,
+    R::Item: DoubleEndedIterator + Default,
+{
+    /// rows hand out the cells of each row left to right, order is the linear traversal of the view they make up
+    pub fn new(rows: R, order: LinearMemoryIterator) -> Self {
+        RowMajorIter { rows, front: R::Item::default(), back: R::Item::default(), order }
+    }
+
+}
+
+impl<R> Iterator for RowMajorIter<R>
+where
+    R: DoubleEndedIterator,
+    R::Item: DoubleEndedIterator,
+{
+    type Item = <R::Item as Iterator>::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.order.next()?;
+        loop {
+            if let Some(cell) = self.front.next() {
+                return Some(cell);
+            }
+
+            match self.rows.next() {
+                Some(row) => self.front = row,
+                None => return self.back.next(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+}
+
+impl<R> DoubleEndedIterator for RowMajorIter<R>
+where
+    R: DoubleEndedIterator,
+    R::Item: DoubleEndedIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.order.next_back()?;
+        loop {
+            if let Some(cell) = self.back.next_back() {
+                return Some(cell);
+            }
+
+            match self.rows.next_back() {
+                Some(row) => self.back = row,
+                None => return self.front.next_back(),
+            }
+        }
+    }
+}
+
+impl<R> ExactSizeIterator for RowMajorIter<R>
+where
+    R: DoubleEndedIterator,
+    R::Item: DoubleEndedIterator,
+{}
+
+impl<R: Iterator> HasCurMemIndex for RowMajorIter<R> {
+    fn get_cur_mem_index(&self) -> MemIndex2D {
+        cur_mem_index(&self.order)
+    }
+}
+
+/// Rows of width cells that start stride cells apart in one buffer, each handed out as an iterator over its cells
+pub struct StridedRows<'a, T> {
+    rows: slice::Chunks<'a, T>,
+    width: usize,
+}
+
+impl<'a, T> StridedRows<'a, T> {
+
+    /// cells runs from the first cell of the first row to the last cell of the last row
+    pub fn new(cells: &'a [T], stride: usize, width: usize) -> Self {
+        StridedRows { rows: cells.chunks(stride.max(1)), width }
+    }
+}
+
+impl<'a, T> Iterator for StridedRows<'a, T> {
+    type Item = slice::Iter<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rows.next().map(|row| row[..self.width].iter())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for StridedRows<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.rows.next_back().map(|row| row[..self.width].iter())
+    }
+}
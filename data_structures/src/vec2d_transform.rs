@@ -0,0 +1,289 @@
+use std::ops::Index;
+
+use memory_math::memory_index2d::MemIndex2D;
+use memory_math::memory_iterators::LinearMemoryIterator;
+use memory_math::size_2d::{HasSize2D, Size2D};
+use memory_math::transform_2d::Transform2D;
+use crate::vec2d::{Vec2D, Vec2DMutSlice, Vec2DSlice};
+
+/// Swap rows top to bottom, one swap_with_slice per pair of rows
+fn flip_row_order<T>(rows: &mut [&mut [T]]) {
+    let row_count: usize = rows.len();
+    for row in 0..row_count / 2 {
+        let (top, bottom) = rows.split_at_mut(row_count - 1 - row);
+        top[row].swap_with_slice(bottom[0]);
+    }
+}
+
+impl<T> Vec2D<T> {
+
+    /// Apply transform in place without allocating a second grid. Square grids swap across the diagonal,
+    /// other grids rotate each cycle of the transpose permutation starting from its smallest offset.
+    pub fn transform(&mut self, transform: Transform2D) {
+        let (transpose, flip_rows, flip_columns) = transform.parts();
+        if transpose {
+            self.transpose_in_place();
+        }
+
+        let mut rows: Vec<&mut [T]> = self.rows_mut().collect();
+        if flip_rows {
+            flip_row_order(&mut rows);
+        }
+        if flip_columns {
+            rows.iter_mut().for_each(|row| row.reverse());
+        }
+    }
+
+    fn transpose_in_place(&mut self) {
+        let (row_count, column_count) = (self.row_count(), self.column_count());
+        let items: &mut [T] = self.items_mut();
+
+        if row_count == column_count {
+            for row in 0..row_count {
+                for col in row + 1..column_count {
+                    items.swap(row * column_count + col, col * column_count + row);
+                }
+            }
+        } else if items.len() > 2 {
+            //the cell at offset i moves to i * row_count modulo the last offset, the first and last cells stay put
+            let last: usize = items.len() - 1;
+            let moved_to = |offset: usize| (offset as u128 * row_count as u128 % last as u128) as usize;
+
+            for start in 1..last {
+                //every cycle is rotated once, from its smallest offset, so nothing has to remember visited cells
+                let mut target: usize = moved_to(start);
+                while target > start {
+                    target = moved_to(target);
+                }
+                if target != start {
+                    continue;
+                }
+
+                target = moved_to(start);
+                while target != start {
+                    items.swap(start, target);
+                    target = moved_to(target);
+                }
+            }
+        }
+
+        self.size = Size2D::new(column_count, row_count);
+    }
+
+    pub fn transpose(&mut self) {
+        self.transform(Transform2D::Transpose);
+    }
+
+    /// Rotate clockwise by a quarter turn
+    pub fn rotate_90(&mut self) {
+        self.transform(Transform2D::Rotate90);
+    }
+
+    pub fn rotate_180(&mut self) {
+        self.transform(Transform2D::Rotate180);
+    }
+
+    pub fn rotate_270(&mut self) {
+        self.transform(Transform2D::Rotate270);
+    }
+
+    /// Mirror the columns, left becomes right
+    pub fn flip_horizontal(&mut self) {
+        self.transform(Transform2D::FlipHorizontal);
+    }
+
+    /// Mirror the rows, top becomes bottom
+    pub fn flip_vertical(&mut self) {
+        self.transform(Transform2D::FlipVertical);
+    }
+
+    /// Read only view of the grid as it would look after transform, nothing is moved
+    pub fn transformed_view(&self, transform: Transform2D) -> TransformedView<'_, T> {
+        TransformedView::new(Vec2DSlice::new(self.column_count(), self.size.into(), self.items()), transform)
+    }
+}
+
+impl<'a, T> Vec2DMutSlice<'a, T> {
+
+    /// Apply transform to the cells of the slice in place.
+    /// False if the transform swaps the axes of a slice that is not square, as its shape cannot change.
+    pub fn transform(&mut self, transform: Transform2D) -> bool {
+        let (transpose, flip_rows, flip_columns) = transform.parts();
+        if transpose && self.row_count() != self.column_count() {
+            return false;
+        }
+
+        let mut rows: Vec<&mut [T]> = self.rows_mut().collect();
+        if transpose {
+            for row in 1..rows.len() {
+                let (above, below) = rows.split_at_mut(row);
+                for (col, cell) in below[0][..row].iter_mut().enumerate() {
+                    std::mem::swap(cell, &mut above[col][row]);
+                }
+            }
+        }
+        if flip_rows {
+            flip_row_order(&mut rows);
+        }
+        if flip_columns {
+            rows.iter_mut().for_each(|row| row.reverse());
+        }
+
+        true
+    }
+}
+
+impl<'a, T> Vec2DSlice<'a, T> {
+
+    /// Read only view of the slice as it would look after transform, nothing is moved
+    pub fn transformed(&self, transform: Transform2D) -> TransformedView<'a, T> {
+        TransformedView::new(self.clone(), transform)
+    }
+}
+
+/// Read only view of a 2D slice as it would look after a Transform2D.
+/// Every lookup maps the index back through the inverse transform, so the source is never copied.
+pub struct TransformedView<'a, T> {
+    source: Vec2DSlice<'a, T>,
+    transform: Transform2D,
+}
+
+impl<'a, T> Clone for TransformedView<'a, T> {
+    fn clone(&self) -> Self {
+        TransformedView::new(self.source.clone(), self.transform)
+    }
+}
+
+impl<'a, T> HasSize2D for TransformedView<'a, T> {
+    fn row_count(&self) -> usize {
+        self.size().row_count
+    }
+
+    fn column_count(&self) -> usize {
+        self.size().column_count
+    }
+
+    fn size(&self) -> Size2D {
+        self.transform.map_size(self.source.span2d.size())
+    }
+}
+
+impl<'a, T> Index<MemIndex2D> for TransformedView<'a, T> {
+    type Output = T;
+
+    fn index(&self, index: MemIndex2D) -> &Self::Output {
+        self.get(index).unwrap_or_else(|| panic!("index {} out of bounds of {}", index, self.size()))
+    }
+}
+
+impl<'a, T> TransformedView<'a, T> {
+
+    pub fn new(source: Vec2DSlice<'a, T>, transform: Transform2D) -> Self {
+        TransformedView { source, transform }
+    }
+
+    pub fn transform(&self) -> Transform2D {
+        self.transform
+    }
+
+    /// The same source seen through transform and then next
+    pub fn then(&self, next: Transform2D) -> TransformedView<'a, T> {
+        TransformedView::new(self.source.clone(), self.transform.then(next))
+    }
+
+    pub fn get(&self, index2d: MemIndex2D) -> Option<&'a T> {
+        let source_index: MemIndex2D = self.transform.inverse().map_index2d(index2d, self.size())?;
+        self.source.get(source_index)
+    }
+
+    /// Cells of the view in row major order
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + use<'a, T> {
+        let view: TransformedView<'a, T> = self.clone();
+        LinearMemoryIterator::new(self.size().into()).map(move |index2d| view.get(index2d).unwrap())
+    }
+
+    /// Copy the view out into a grid that really is transformed
+    pub fn to_vec2d(&self) -> Vec2D<T>
+    where
+        T: Clone,
+    {
+        Vec2D::new_items_size(self.iter().cloned().collect(), self.size()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use memory_math::memory_span2d::MemSpan2D;
+
+    fn cells(vec2d: &Vec2D<i32>) -> Vec<i32> {
+        vec2d.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_in_place_matches_mapping() {
+        for (rows, columns) in [(1, 1), (1, 5), (4, 1), (3, 3), (2, 3), (3, 7), (6, 4), (5, 8), (12, 5), (9, 16)] {
+            let grid = Vec2D::new_items_size((0..(rows * columns) as i32).collect(), Size2D::new(rows, columns)).unwrap();
+
+            for transform in Transform2D::ALL {
+                let mut transformed = grid.clone();
+                transformed.transform(transform);
+                assert_eq!(transform.map_size(grid.size), transformed.size);
+
+                for (index2d, value) in grid.indexed_iter() {
+                    let target = transform.map_index2d(index2d, grid.size).unwrap();
+                    assert_eq!(*value, transformed[target], "{:?} of {}x{}", transform, rows, columns);
+                }
+
+                assert_eq!(cells(&transformed), grid.transformed_view(transform).iter().copied().collect::<Vec<i32>>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_named_rotations() {
+        let mut grid = Vec2D::from_vec((1..=6).collect::<Vec<i32>>(), 3).unwrap();
+        grid.rotate_90();
+        assert_eq!(vec![4, 1, 5, 2, 6, 3], cells(&grid));
+        grid.rotate_270();
+        grid.flip_horizontal();
+        assert_eq!(vec![3, 2, 1, 6, 5, 4], cells(&grid));
+        grid.flip_vertical();
+        grid.rotate_180();
+        grid.transpose();
+        assert_eq!(Size2D::new(3, 2), grid.size);
+        assert_eq!(vec![1, 4, 2, 5, 3, 6], cells(&grid));
+    }
+
+    #[test]
+    fn test_square_slice_transform() {
+        let mut grid = Vec2D::from_vec((0..16).collect::<Vec<i32>>(), 4).unwrap();
+        {
+            let mut slice = grid.get_slice_mut(MemSpan2D::new_from_usize(1, 1, 4, 4)).unwrap();
+            assert!(slice.transform(Transform2D::Rotate90));
+        }
+        assert_eq!(vec![4, 13, 9, 5], grid.get_row_mut(1).unwrap().to_vec());
+        assert_eq!(vec![12, 15, 11, 7], grid.get_row_mut(3).unwrap().to_vec());
+
+        let mut wide = grid.get_slice_mut(MemSpan2D::new_from_usize(0, 0, 1, 4)).unwrap();
+        assert!(!wide.transform(Transform2D::Transpose));
+        assert!(wide.transform(Transform2D::FlipHorizontal));
+        assert_eq!(Some(&3), wide.get(MemIndex2D::origin()));
+    }
+
+    #[test]
+    fn test_lazy_view() {
+        let grid = Vec2D::from_vec((0..12).collect::<Vec<i32>>(), 4).unwrap();
+        let slice = grid.get_slice(MemSpan2D::new_from_usize(1, 1, 3, 4)).unwrap();
+
+        let view = slice.transformed(Transform2D::Rotate90);
+        assert_eq!(Size2D::new(3, 2), view.size());
+        assert_eq!(vec![9, 5, 10, 6, 11, 7], view.iter().copied().collect::<Vec<i32>>());
+        assert_eq!(9, view[MemIndex2D::origin()]);
+        assert!(view.get(MemIndex2D::new(0, 2)).is_none());
+
+        let back = view.then(Transform2D::Rotate270);
+        assert_eq!(Transform2D::Identity, back.transform());
+        assert_eq!(vec![5, 6, 7, 9, 10, 11], back.to_vec2d().iter().copied().collect::<Vec<i32>>());
+    }
+}
@@ -0,0 +1,611 @@
+use std::iter::Map;
+use std::ops::{Index, IndexMut};
+use std::slice;
+
+use memory_math::memory_index2d::MemIndex2D;
+use memory_math::memory_iterators::{LinearMemoryIterator, MemoryIterator};
+use memory_math::memory_range_iter::{IndexEnumerate, IterateWithMemIndex};
+use memory_math::memory_span::MemSpan;
+use memory_math::memory_span2d::MemSpan2D;
+use memory_math::size_2d::{HasSize2D, Size2D};
+use crate::vec2d::Vec2DMutSlice;
+use crate::vec2d_iter::{RowMajorIter, Vec2DIter};
+
+/// Where the rows of a strided view sit inside its buffer.
+/// Row r starts at origin + r * row_stride, a negative stride walks the buffer bottom up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct RowPitchLayout {
+    size: Size2D,
+    origin: usize,
+    row_stride: isize,
+}
+
+impl RowPitchLayout {
+    /// Buffer offsets touched by the view, None if rows overlap or the offsets overflow.
+    /// Rows without columns still have to start inside the buffer, so only a view without rows skips the stride
+    fn extent(&self) -> Option<MemSpan> {
+        if self.size.row_count == 0 {
+            return Some(MemSpan::new_range(self.origin..self.origin));
+        }
+
+        let stride: usize = self.row_stride.unsigned_abs();
+        if self.size.row_count > 1 && stride < self.size.column_count {
+            return None;
+        }
+
+        let last_row_distance: usize = (self.size.row_count - 1).checked_mul(stride)?;
+        let lowest: usize = if self.row_stride < 0 { self.origin.checked_sub(last_row_distance)? } else { self.origin };
+        let upper: usize = lowest.checked_add(last_row_distance)?.checked_add(self.size.column_count)?;
+        Some(MemSpan::new_range(lowest..upper))
+    }
+
+    fn fits(&self, len: usize) -> bool {
+        self.extent().is_some_and(|extent| extent.upper_bound() <= len)
+    }
+
+    fn row_offset(&self, row: usize) -> Option<usize> {
+        if row >= self.size.row_count {
+            return None;
+        }
+
+        self.origin.checked_add_signed(isize::try_from(row).ok()?.checked_mul(self.row_stride)?)
+    }
+
+    fn index2d_to_offset(&self, index2d: MemIndex2D) -> Option<usize> {
+        if !self.size.index2d_in_bounds(&index2d) {
+            return None;
+        }
+
+        Some(self.row_offset(index2d.row)? + index2d.col)
+    }
+
+    /// Layout of a span given relative to this one, sharing its stride
+    fn sub_layout(&self, span2d: &MemSpan2D) -> Option<Self> {
+        if !span2d.valid() || !self.size.contains_span2d(span2d) {
+            return None;
+        }
+
+        Some(RowPitchLayout {
+            size: span2d.size(),
+            origin: self.row_offset(span2d.min_row())? + span2d.min_column(),
+            row_stride: self.row_stride,
+        })
+    }
+
+    fn flip_rows(&self) -> Self {
+        RowPitchLayout {
+            size: self.size,
+            origin: self.row_offset(self.size.row_count.saturating_sub(1)).unwrap_or(self.origin),
+            row_stride: -self.row_stride,
+        }
+    }
+
+    /// Items from the start of one row in the buffer to the start of the next, never less than a row
+    fn pitch(&self) -> usize {
+        self.row_stride.unsigned_abs().max(self.size.column_count)
+    }
+}
+
+/// Rows of a strided view from top to bottom, cut out of the buffer one at a time without their padding
+pub struct ViewRows<'a, T> {
+    cells: &'a [T],
+    pitch: usize,
+    column_count: usize,
+    row_count: usize,
+    bottom_up: bool,
+}
+
+impl<'a, T> Clone for ViewRows<'a, T> {
+    fn clone(&self) -> Self {
+        ViewRows { ..*self }
+    }
+}
+
+impl<'a, T> ViewRows<'a, T> {
+
+    fn new(layout: RowPitchLayout, data: &'a [T]) -> Self {
+        let extent = layout.extent().map_or(0..0, |extent: MemSpan| extent.min..extent.upper_bound());
+        ViewRows {
+            cells: &data[extent],
+            pitch: layout.pitch(),
+            column_count: layout.size.column_count,
+            row_count: layout.size.row_count,
+            bottom_up: layout.row_stride < 0,
+        }
+    }
+
+    /// Row stored first in the buffer
+    fn take_low(&mut self) -> &'a [T] {
+        let (row, rest) = self.cells.split_at(self.pitch.min(self.cells.len()));
+        self.cells = rest;
+        &row[..self.column_count]
+    }
+
+    /// Row stored last in the buffer
+    fn take_high(&mut self) -> &'a [T] {
+        let (rest, row) = self.cells.split_at((self.row_count - 1) * self.pitch);
+        self.cells = rest;
+        &row[..self.column_count]
+    }
+}
+
+impl<'a, T> Iterator for ViewRows<'a, T> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.row_count == 0 {
+            return None;
+        }
+
+        let row: &'a [T] = if self.bottom_up { self.take_high() } else { self.take_low() };
+        self.row_count -= 1;
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.row_count, Some(self.row_count))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ViewRows<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.row_count == 0 {
+            return None;
+        }
+
+        let row: &'a [T] = if self.bottom_up { self.take_low() } else { self.take_high() };
+        self.row_count -= 1;
+        Some(row)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ViewRows<'a, T> {}
+
+/// Mutable rows of a strided view from top to bottom, split off the buffer one at a time so they never overlap
+pub struct ViewRowsMut<'a, T> {
+    cells: &'a mut [T],
+    pitch: usize,
+    column_count: usize,
+    row_count: usize,
+    bottom_up: bool,
+}
+
+impl<'a, T> ViewRowsMut<'a, T> {
+
+    fn new(layout: RowPitchLayout, data: &'a mut [T]) -> Self {
+        let extent = layout.extent().map_or(0..0, |extent: MemSpan| extent.min..extent.upper_bound());
+        ViewRowsMut {
+            cells: &mut data[extent],
+            pitch: layout.pitch(),
+            column_count: layout.size.column_count,
+            row_count: layout.size.row_count,
+            bottom_up: layout.row_stride < 0,
+        }
+    }
+
+    fn take_low(&mut self) -> &'a mut [T] {
+        let cells: &'a mut [T] = std::mem::take(&mut self.cells);
+        let (row, rest) = cells.split_at_mut(self.pitch.min(cells.len()));
+        self.cells = rest;
+        &mut row[..self.column_count]
+    }
+
+    fn take_high(&mut self) -> &'a mut [T] {
+        let cells: &'a mut [T] = std::mem::take(&mut self.cells);
+        let (rest, row) = cells.split_at_mut((self.row_count - 1) * self.pitch);
+        self.cells = rest;
+        &mut row[..self.column_count]
+    }
+}
+
+impl<'a, T> Iterator for ViewRowsMut<'a, T> {
+    type Item = &'a mut [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.row_count == 0 {
+            return None;
+        }
+
+        let row: &'a mut [T] = if self.bottom_up { self.take_high() } else { self.take_low() };
+        self.row_count -= 1;
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.row_count, Some(self.row_count))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ViewRowsMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.row_count == 0 {
+            return None;
+        }
+
+        let row: &'a mut [T] = if self.bottom_up { self.take_low() } else { self.take_high() };
+        self.row_count -= 1;
+        Some(row)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ViewRowsMut<'a, T> {}
+
+/// Row major cells of a strided view, walking each row's own slice iterator
+pub type Vec2DViewIter<'a, T> = RowMajorIter<Map<ViewRows<'a, T>, fn(&'a [T]) -> slice::Iter<'a, T>>>;
+pub type Vec2DViewIterMut<'a, T> = RowMajorIter<Map<ViewRowsMut<'a, T>, fn(&'a mut [T]) -> slice::IterMut<'a, T>>>;
+
+/// Read only 2D view over a foreign row major buffer whose rows are row_stride items apart.
+/// Indexes are relative to the view, padding between rows is never visited.
+pub struct Vec2DView<'a, T> {
+    layout: RowPitchLayout,
+    data: &'a [T],
+}
+
+impl<'a, T> Clone for Vec2DView<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for Vec2DView<'a, T> {}
+
+impl<'a, T> HasSize2D for Vec2DView<'a, T> {
+    fn row_count(&self) -> usize {
+        self.layout.size.row_count
+    }
+
+    fn column_count(&self) -> usize {
+        self.layout.size.column_count
+    }
+}
+
+impl<'a, T> Index<MemIndex2D> for Vec2DView<'a, T> {
+    type Output = T;
+
+    fn index(&self, index: MemIndex2D) -> &Self::Output {
+        self.get(index).unwrap_or_else(|| panic!("index {} out of bounds of {}", index, self.layout.size))
+    }
+}
+
+impl<'a, T> Vec2DView<'a, T> {
+
+    /// View with rows starting every row_stride items from the start of data.
+    /// None if the strides overlap rows or data is too short for the last row
+    pub fn new(data: &'a [T], size: Size2D, row_stride: usize) -> Option<Self> {
+        Vec2DView::new_strided(data, size, 0, isize::try_from(row_stride).ok()?)
+    }
+
+    /// View whose first row starts at origin, a negative row_stride stores the rows bottom up
+    pub fn new_strided(data: &'a [T], size: Size2D, origin: usize, row_stride: isize) -> Option<Self> {
+        let layout = RowPitchLayout { size, origin, row_stride };
+        if !layout.fits(data.len()) {
+            return None;
+        }
+
+        Some(Vec2DView { layout, data })
+    }
+
+    /// Items between the starts of two neighbouring rows, negative when rows go bottom up
+    pub fn row_stride(&self) -> isize {
+        self.layout.row_stride
+    }
+
+    pub fn get(&self, index2d: MemIndex2D) -> Option<&'a T> {
+        self.data.get(self.layout.index2d_to_offset(index2d)?)
+    }
+
+    pub fn get_span_row(&self, row: usize) -> Option<&'a [T]> {
+        let start: usize = self.layout.row_offset(row)?;
+        Some(&self.data[start..start + self.column_count()])
+    }
+
+    /// Rows from top to bottom, without their padding
+    pub fn rows(&self) -> ViewRows<'a, T> {
+        ViewRows::new(self.layout, self.data)
+    }
+
+    /// Columns left to right, each walked top to bottom
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &'a T>> + use<'a, T> {
+        let rows: ViewRows<'a, T> = self.rows();
+        (0..self.column_count()).map(move |col| rows.clone().map(move |row| &row[col]))
+    }
+
+    /// Cells in row major order
+    pub fn iter(&self) -> Vec2DViewIter<'a, T> {
+        RowMajorIter::new(self.rows().map(<[T]>::iter as fn(_) -> _), LinearMemoryIterator::new(self.layout.size.into()))
+    }
+
+    /// Cells in the order of any MemoryIterator over view relative indexes, None if it reaches outside of the view
+    pub fn iter_with<I: MemoryIterator>(&self, order: I) -> Option<Vec2DIter<'a, I, T>> {
+        Vec2DIter::new(order, self.rows().collect())
+    }
+
+    /// Cells in row major order together with their view relative index
+    pub fn indexed_iter(&self) -> IndexEnumerate<Vec2DViewIter<'a, T>> {
+        self.iter().iterate_with_mem_index()
+    }
+
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.rows().any(|row| row.contains(x))
+    }
+
+    /// View of a span given relative to this view, None if it reaches outside of it
+    pub fn sub_slice(&self, span2d: MemSpan2D) -> Option<Vec2DView<'a, T>> {
+        Some(Vec2DView { layout: self.layout.sub_layout(&span2d)?, data: self.data })
+    }
+
+    /// The same cells upside down
+    pub fn flip_rows(&self) -> Vec2DView<'a, T> {
+        Vec2DView { layout: self.layout.flip_rows(), data: self.data }
+    }
+}
+
+/// Mutable 2D view over a foreign row major buffer whose rows are row_stride items apart
+pub struct Vec2DViewMut<'a, T> {
+    layout: RowPitchLayout,
+    data: &'a mut [T],
+}
+
+impl<'a, T> HasSize2D for Vec2DViewMut<'a, T> {
+    fn row_count(&self) -> usize {
+        self.layout.size.row_count
+    }
+
+    fn column_count(&self) -> usize {
+        self.layout.size.column_count
+    }
+}
+
+impl<'a, T> Index<MemIndex2D> for Vec2DViewMut<'a, T> {
+    type Output = T;
+
+    fn index(&self, index: MemIndex2D) -> &Self::Output {
+        self.get(index).unwrap_or_else(|| panic!("index {} out of bounds of {}", index, self.layout.size))
+    }
+}
+
+impl<'a, T> IndexMut<MemIndex2D> for Vec2DViewMut<'a, T> {
+    fn index_mut(&mut self, index: MemIndex2D) -> &mut Self::Output {
+        let size: Size2D = self.layout.size;
+        self.get_mut(index).unwrap_or_else(|| panic!("index {} out of bounds of {}", index, size))
+    }
+}
+
+impl<'a, T> Vec2DViewMut<'a, T> {
+
+    /// View with rows starting every row_stride items from the start of data.
+    /// None if the strides overlap rows or data is too short for the last row
+    pub fn new(data: &'a mut [T], size: Size2D, row_stride: usize) -> Option<Self> {
+        Vec2DViewMut::new_strided(data, size, 0, isize::try_from(row_stride).ok()?)
+    }
+
+    /// View whose first row starts at origin, a negative row_stride stores the rows bottom up
+    pub fn new_strided(data: &'a mut [T], size: Size2D, origin: usize, row_stride: isize) -> Option<Self> {
+        let layout = RowPitchLayout { size, origin, row_stride };
+        if !layout.fits(data.len()) {
+            return None;
+        }
+
+        Some(Vec2DViewMut { layout, data })
+    }
+
+    pub fn row_stride(&self) -> isize {
+        self.layout.row_stride
+    }
+
+    /// Read only view of the same cells
+    pub fn as_view(&self) -> Vec2DView<'_, T> {
+        Vec2DView { layout: self.layout, data: self.data }
+    }
+
+    /// Shorter lived mutable view of the same cells
+    pub fn reborrow(&mut self) -> Vec2DViewMut<'_, T> {
+        Vec2DViewMut { layout: self.layout, data: self.data }
+    }
+
+    pub fn get(&self, index2d: MemIndex2D) -> Option<&T> {
+        self.data.get(self.layout.index2d_to_offset(index2d)?)
+    }
+
+    pub fn get_mut(&mut self, index2d: MemIndex2D) -> Option<&mut T> {
+        self.data.get_mut(self.layout.index2d_to_offset(index2d)?)
+    }
+
+    pub fn get_span_row(&self, row: usize) -> Option<&[T]> {
+        let start: usize = self.layout.row_offset(row)?;
+        Some(&self.data[start..start + self.column_count()])
+    }
+
+    pub fn get_span_row_mut(&mut self, row: usize) -> Option<&mut [T]> {
+        let start: usize = self.layout.row_offset(row)?;
+        let end: usize = start + self.column_count();
+        Some(&mut self.data[start..end])
+    }
+
+    pub fn rows(&self) -> ViewRows<'_, T> {
+        ViewRows::new(self.layout, self.data)
+    }
+
+    /// All rows at once from top to bottom, rows never overlap
+    pub fn rows_mut(&mut self) -> ViewRowsMut<'_, T> {
+        ViewRowsMut::new(self.layout, self.data)
+    }
+
+    /// Columns left to right, each walked top to bottom
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        self.as_view().columns()
+    }
+
+    pub fn iter(&self) -> Vec2DViewIter<'_, T> {
+        self.as_view().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> Vec2DViewIterMut<'_, T> {
+        let order = LinearMemoryIterator::new(self.layout.size.into());
+        RowMajorIter::new(self.rows_mut().map(<[T]>::iter_mut as fn(_) -> _), order)
+    }
+
+    /// Cells in the order of any MemoryIterator over view relative indexes, None if it reaches outside of the view
+    pub fn iter_with<I: MemoryIterator>(&self, order: I) -> Option<Vec2DIter<'_, I, T>> {
+        self.as_view().iter_with(order)
+    }
+
+    /// Cells in row major order together with their view relative index
+    pub fn indexed_iter(&self) -> IndexEnumerate<Vec2DViewIter<'_, T>> {
+        self.iter().iterate_with_mem_index()
+    }
+
+    /// View of a span given relative to this view, None if it reaches outside of it
+    pub fn sub_slice(self, span2d: MemSpan2D) -> Option<Self> {
+        Some(Vec2DViewMut { layout: self.layout.sub_layout(&span2d)?, data: self.data })
+    }
+
+    /// The same cells upside down
+    pub fn flip_rows(self) -> Self {
+        Vec2DViewMut { layout: self.layout.flip_rows(), data: self.data }
+    }
+
+    /// Row segment view of the same cells, which can be split into views that live side by side
+    pub fn into_mut_slice(self) -> Option<Vec2DMutSlice<'a, T>> {
+        let span2d: MemSpan2D = self.layout.size.into();
+        Vec2DMutSlice::new(span2d, ViewRowsMut::new(self.layout, self.data).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use memory_math::traversal_iterators::ColumnMajorIterator;
+
+    /// 3 rows of 4 cells padded to a pitch of 6, padding holds -1
+    fn padded_buffer() -> Vec<i32> {
+        (0..18).map(|i| if i % 6 < 4 { (i / 6) * 10 + i % 6 } else { -1 }).collect()
+    }
+
+    #[test]
+    fn test_view_skips_padding() {
+        let buffer = padded_buffer();
+        let view = Vec2DView::new(&buffer, Size2D::new(3, 4), 6).unwrap();
+
+        assert_eq!(Some(&12), view.get(MemIndex2D::new(1, 2)));
+        assert!(view.get(MemIndex2D::new(1, 4)).is_none());
+        assert_eq!(vec![0, 1, 2, 3, 10, 11, 12, 13, 20, 21, 22, 23], view.iter().copied().collect::<Vec<i32>>());
+        assert!(!view.contains(&-1));
+
+        let sub = view.sub_slice(MemSpan2D::new_from_usize(1, 1, 3, 3)).unwrap();
+        assert_eq!(vec![[11, 12].as_slice(), [21, 22].as_slice()], sub.rows().collect::<Vec<&[i32]>>());
+        assert_eq!(22, sub.sub_slice(MemSpan2D::new_from_usize(1, 1, 2, 2)).unwrap()[MemIndex2D::origin()]);
+        assert!(sub.sub_slice(MemSpan2D::new_from_usize(0, 0, 3, 1)).is_none());
+
+        //a padded last row may be cut short, anything shorter is rejected
+        assert!(Vec2DView::new(&buffer[..16], Size2D::new(3, 4), 6).is_some());
+        assert!(Vec2DView::new(&buffer[..15], Size2D::new(3, 4), 6).is_none());
+        assert!(Vec2DView::new(&buffer, Size2D::new(3, 4), 3).is_none());
+
+        //rows without columns still need their starts inside the buffer
+        assert!(Vec2DView::<i32>::new(&[], Size2D::new(3, 0), 100).is_none());
+        let empty_rows = Vec2DView::new(&buffer, Size2D::new(3, 0), 6).unwrap();
+        assert_eq!(3, empty_rows.rows().filter(|row| row.is_empty()).count());
+        assert!(Vec2DView::new_strided(&buffer, Size2D::new(2, 0), 0, isize::MAX).is_none());
+    }
+
+    #[test]
+    fn test_bottom_up_view() {
+        let buffer = padded_buffer();
+        let bottom_up = Vec2DView::new_strided(&buffer, Size2D::new(3, 4), 12, -6).unwrap();
+        assert_eq!(Some([20, 21, 22, 23].as_slice()), bottom_up.get_span_row(0));
+        assert_eq!(Some(&3), bottom_up.get(MemIndex2D::new(2, 3)));
+
+        let flipped = bottom_up.flip_rows();
+        assert_eq!(6, flipped.row_stride());
+        assert_eq!(Some(&1), flipped.get(MemIndex2D::new(0, 1)));
+        assert_eq!(Some(&11), bottom_up.sub_slice(MemSpan2D::new_from_usize(1, 1, 3, 2)).unwrap().get(MemIndex2D::origin()));
+
+        assert!(Vec2DView::new_strided(&buffer, Size2D::new(3, 4), 11, -6).is_none());
+    }
+
+    #[test]
+    fn test_view_mut() {
+        let mut buffer = padded_buffer();
+        {
+            let mut view = Vec2DViewMut::new(&mut buffer, Size2D::new(3, 4), 6).unwrap();
+            view[MemIndex2D::new(0, 0)] = 100;
+            view.reborrow().flip_rows().rows_mut().next().unwrap().fill(7);
+            assert_eq!(Some([7, 7, 7, 7].as_slice()), view.get_span_row(2));
+
+            let mut sub = view.sub_slice(MemSpan2D::new_from_usize(0, 1, 2, 3)).unwrap();
+            sub.iter_mut().for_each(|v| *v *= 2);
+            assert_eq!(vec![2, 4, 22, 24], sub.as_view().iter().copied().collect::<Vec<i32>>());
+        }
+
+        assert_eq!(vec![100, 2, 4, 3, -1, -1], buffer[0..6].to_vec());
+        assert_eq!(vec![7, 7, 7, 7, -1, -1], buffer[12..18].to_vec());
+    }
+
+    #[test]
+    fn test_view_mut_into_mut_slice() {
+        let mut buffer = padded_buffer();
+        let view = Vec2DViewMut::new_strided(&mut buffer, Size2D::new(3, 4), 12, -6).unwrap();
+        let (top, mut bottom) = view.into_mut_slice().unwrap().split_at_row(1).unwrap();
+
+        assert_eq!(Some([20, 21, 22, 23].as_slice()), top.get_span_row(0));
+        bottom.iter_mut().for_each(|v| *v = 0);
+        drop(top);
+
+        assert_eq!(vec![0, 0, 0, 0, -1, -1, 0, 0, 0, 0, -1, -1, 20], buffer[0..13].to_vec());
+    }
+
+    #[test]
+    fn test_view_accessors() {
+        let buffer = padded_buffer();
+        let bottom_up = Vec2DView::new_strided(&buffer, Size2D::new(3, 4), 12, -6).unwrap();
+
+        let rows = bottom_up.rows();
+        assert_eq!(3, rows.len());
+        assert_eq!(vec![[0, 1, 2, 3].as_slice(), [10, 11, 12, 13].as_slice(), [20, 21, 22, 23].as_slice()], rows.rev().collect::<Vec<&[i32]>>());
+        assert_eq!(vec![3, 2, 1, 0, 13], bottom_up.iter().rev().take(5).copied().collect::<Vec<i32>>());
+        assert_eq!(vec![20, 21, 22, 23], bottom_up.columns().map(|mut column| *column.next().unwrap()).collect::<Vec<i32>>());
+        assert_eq!(vec![21, 11, 1], bottom_up.columns().nth(1).unwrap().copied().collect::<Vec<i32>>());
+
+        let indexed: Vec<(MemIndex2D, i32)> = bottom_up.indexed_iter().map(|(index2d, v)| (index2d, *v)).collect();
+        assert_eq!(12, indexed.len());
+        assert_eq!((MemIndex2D::new(1, 2), 12), indexed[6]);
+
+        assert!(bottom_up.iter_with(LinearMemoryIterator::new(MemSpan2D::new_from_usize(0, 0, 4, 4))).is_none());
+        let column_major = bottom_up.iter_with(ColumnMajorIterator::new(bottom_up.size().into())).unwrap();
+        assert_eq!(vec![20, 10, 0, 21, 11], column_major.take(5).copied().collect::<Vec<i32>>());
+
+        let empty_rows = Vec2DView::new(&buffer, Size2D::new(3, 0), 6).unwrap();
+        assert_eq!(3, empty_rows.rows().rev().filter(|row| row.is_empty()).count());
+        assert_eq!(0, empty_rows.iter().count());
+    }
+
+    #[test]
+    fn test_view_rows_mut() {
+        let mut buffer = padded_buffer();
+        {
+            let mut bottom_up = Vec2DViewMut::new_strided(&mut buffer, Size2D::new(3, 4), 12, -6).unwrap();
+            let mut rows = bottom_up.rows_mut();
+            assert_eq!(3, rows.len());
+            rows.next().unwrap()[0] = 200;
+            rows.next_back().unwrap()[0] = 0;
+            assert_eq!(Some(&mut [10, 11, 12, 13][..]), rows.next());
+            assert!(rows.next().is_none() && rows.next_back().is_none());
+
+            let mut cells = bottom_up.iter_mut();
+            assert_eq!(12, cells.len());
+            *cells.next_back().unwrap() = 30;
+            assert_eq!(Some((MemIndex2D::new(2, 2), &2)), bottom_up.indexed_iter().nth(10));
+        }
+
+        assert_eq!(vec![0, 1, 2, 30], buffer[0..4].to_vec());
+        assert_eq!(200, buffer[12]);
+
+        let mut empty_rows = Vec2DViewMut::new(&mut buffer, Size2D::new(3, 0), 6).unwrap();
+        assert_eq!(3, empty_rows.rows_mut().filter(|row| row.is_empty()).count());
+        assert_eq!(0, empty_rows.iter_mut().count());
+    }
+}
@@ -0,0 +1,268 @@
+use std::ops::{Index, IndexMut};
+
+use memory_math::memory_index3d::MemIndex3D;
+use memory_math::memory_span2d::MemSpan2D;
+use memory_math::memory_span3d::MemSpan3D;
+use memory_math::size_3d::{HasSize3D, Size3D};
+use crate::vec2d::{Vec2D, Vec2DMutSlice, Vec2DSlice};
+
+///Owning stack of equally sized 2d planes, stored layer by layer in row major order
+#[derive(Clone)]
+pub struct Vec3D<T> {
+    pub size: Size3D,
+    items: Vec<T>,
+}
+
+impl<T> HasSize3D for Vec3D<T> {
+    fn layer_count(&self) -> usize {
+        self.size.layer_count
+    }
+
+    fn row_count(&self) -> usize {
+        self.size.row_count
+    }
+
+    fn column_count(&self) -> usize {
+        self.size.column_count
+    }
+
+    fn size3d(&self) -> Size3D {
+        self.size
+    }
+}
+
+impl<T> Index<MemIndex3D> for Vec3D<T> {
+    type Output = T;
+
+    fn index(&self, index: MemIndex3D) -> &Self::Output {
+        match self.get_index3d(index) {
+            Some(val) => val,
+            None => panic!(
+                "Index3d out of bounds. Index was {} but the size is {}",
+                index,
+                self.size
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<MemIndex3D> for Vec3D<T> {
+    fn index_mut(&mut self, index: MemIndex3D) -> &mut Self::Output {
+        let extents = self.size;
+        match self.get_mut_index3d(index) {
+            Some(v) => v,
+            None => panic!(
+                "Index3d out of bounds. Index was {} but the size is {}",
+                index, extents
+            ),
+        }
+    }
+}
+
+impl<T> Vec3D<T> {
+
+    pub fn new_size_reference(size: Size3D, ref_item: &T) -> Self
+    where
+        T: Clone,
+    {
+        Vec3D {
+            items: vec![ref_item.clone(); size.volume()],
+            size,
+        }
+    }
+
+    pub fn new_items_size(items: Vec<T>, size: Size3D) -> Option<Self> {
+        if items.len() != size.volume() {
+            return None;
+        }
+
+        Some(Vec3D { items, size })
+    }
+
+    pub fn get_index3d(&self, index3d: MemIndex3D) -> Option<&T> {
+        self.size.index3d_to_index(index3d).and_then(|i| self.items.get(i))
+    }
+
+    pub fn get_mut_index3d(&mut self, index3d: MemIndex3D) -> Option<&mut T> {
+        self.size.index3d_to_index(index3d).and_then(|i| self.items.get_mut(i))
+    }
+
+    /// All items of a layer, in row major order
+    pub fn layer_items(&self, layer: usize) -> Option<&[T]> {
+        if layer >= self.size.layer_count {
+            return None;
+        }
+
+        let plane_area: usize = self.size.plane_area();
+        Some(&self.items[layer * plane_area..(layer + 1) * plane_area])
+    }
+
+    pub fn layer_items_mut(&mut self, layer: usize) -> Option<&mut [T]> {
+        if layer >= self.size.layer_count {
+            return None;
+        }
+
+        let plane_area: usize = self.size.plane_area();
+        Some(&mut self.items[layer * plane_area..(layer + 1) * plane_area])
+    }
+
+    /// 2D view of a complete layer
+    pub fn plane(&self, layer: usize) -> Option<Vec2DSlice<'_, T>> {
+        self.plane_slice(layer, self.size.plane_size().into())
+    }
+
+    /// 2D view of a region of a layer, span2d is given in plane coordinates
+    pub fn plane_slice(&self, layer: usize, span2d: MemSpan2D) -> Option<Vec2DSlice<'_, T>> {
+        if !span2d.valid() || !self.size.contains_span3d(&MemSpan3D::new((layer..layer + 1).into(), span2d.clone())) {
+            return None;
+        }
+
+        let column_count: usize = self.size.column_count;
+        Some(Vec2DSlice::new(column_count, span2d, self.layer_items(layer)?))
+    }
+
+    /// Mutable 2D view of a complete layer
+    pub fn plane_mut(&mut self, layer: usize) -> Option<Vec2DMutSlice<'_, T>> {
+        let span2d: MemSpan2D = self.size.plane_size().into();
+        if !span2d.valid() {
+            return None;
+        }
+
+        let column_count: usize = self.size.column_count;
+        Vec2DMutSlice::from_buffer(self.layer_items_mut(layer)?, column_count, span2d)
+    }
+
+    /// Copy a layer out into its own Vec2D
+    pub fn plane_to_vec2d(&self, layer: usize) -> Option<Vec2D<T>>
+    where
+        T: Clone,
+    {
+        Vec2D::new_items_size(self.layer_items(layer)?.to_vec(), self.size.plane_size())
+    }
+
+    /// 3D view of a box inside the volume
+    pub fn get_slice(&self, span3d: MemSpan3D) -> Option<Vec3DSlice<'_, T>> {
+        if !span3d.valid() || !self.size.contains_span3d(&span3d) {
+            return None;
+        }
+
+        Some(Vec3DSlice {
+            vec_size: self.size,
+            span3d,
+            data: self.items.as_slice(),
+        })
+    }
+}
+
+/// Read only view of a box of a Vec3D, indexes are relative to the box
+pub struct Vec3DSlice<'a, T> {
+    pub vec_size: Size3D,
+    pub span3d: MemSpan3D,
+    data: &'a [T],
+}
+
+impl<'a, T> HasSize3D for Vec3DSlice<'a, T> {
+    fn layer_count(&self) -> usize {
+        self.span3d.layer_count()
+    }
+
+    fn row_count(&self) -> usize {
+        self.span3d.row_count()
+    }
+
+    fn column_count(&self) -> usize {
+        self.span3d.column_count()
+    }
+}
+
+impl<'a, T> Vec3DSlice<'a, T> {
+
+    pub fn get(&self, index3d: MemIndex3D) -> Option<&'a T> {
+        if !self.span3d.size().index3d_in_bounds(&index3d) {
+            return None;
+        }
+
+        let vec_index3d: MemIndex3D = self.span3d.relative_index3d_to_absolute_index3d(index3d)?;
+        self.data.get(self.vec_size.index3d_to_index(vec_index3d)?)
+    }
+
+    /// 2D view of one layer of the box, compatible with every Vec2DSlice consumer
+    pub fn plane(&self, layer: usize) -> Option<Vec2DSlice<'a, T>> {
+        if layer >= self.span3d.layer_count() {
+            return None;
+        }
+
+        let plane_area: usize = self.vec_size.plane_area();
+        let vec_layer: usize = self.span3d.min_layer() + layer;
+        let layer_items: &'a [T] = &self.data[vec_layer * plane_area..(vec_layer + 1) * plane_area];
+
+        Some(Vec2DSlice::new(self.vec_size.column_count, self.span3d.plane_span(), layer_items))
+    }
+
+    pub fn planes(&self) -> impl Iterator<Item = Vec2DSlice<'a, T>> + '_ {
+        (0..self.span3d.layer_count()).filter_map(move |layer| self.plane(layer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use memory_math::memory_index2d::MemIndex2D;
+
+    fn counting_volume() -> Vec3D<i32> {
+        Vec3D::new_items_size((0..24).collect(), Size3D::new(2, 3, 4)).unwrap()
+    }
+
+    #[test]
+    fn test_index3d() {
+        let mut volume = counting_volume();
+        assert_eq!(13, volume[MemIndex3D::new(1, 0, 1)]);
+        assert!(volume.get_index3d(MemIndex3D::new(2, 0, 0)).is_none());
+
+        volume[MemIndex3D::new(0, 2, 3)] = -1;
+        assert_eq!(Some(&-1), volume.layer_items(0).unwrap().last());
+    }
+
+    #[test]
+    fn test_plane_views() {
+        let volume = counting_volume();
+
+        let plane = volume.plane(1).unwrap();
+        assert_eq!(Some(&12), plane.get(MemIndex2D::origin()));
+        assert_eq!(Some(&23), plane.get(MemIndex2D::new(2, 3)));
+
+        let center = volume.plane_slice(0, MemSpan2D::new_from_usize(1, 1, 3, 3)).unwrap();
+        assert_eq!(Some(&5), center.get(MemIndex2D::origin()));
+        assert_eq!(Some(&10), center.get(MemIndex2D::new(1, 1)));
+
+        assert!(volume.plane(2).is_none());
+        assert!(volume.plane_slice(0, MemSpan2D::new_from_usize(0, 0, 4, 4)).is_none());
+
+        let layer = volume.plane_to_vec2d(1).unwrap();
+        assert_eq!(16, layer[MemIndex2D::new(1, 0)]);
+    }
+
+    #[test]
+    fn test_box_slice() {
+        let volume = counting_volume();
+        let span3d = MemSpan3D::new_from_index3d(MemIndex3D::new(0, 1, 2), MemIndex3D::new(2, 3, 4));
+        let slice = volume.get_slice(span3d).unwrap();
+
+        assert_eq!(Some(&6), slice.get(MemIndex3D::origin()));
+        assert_eq!(Some(&23), slice.get(MemIndex3D::new(1, 1, 1)));
+        assert!(slice.get(MemIndex3D::new(0, 2, 0)).is_none());
+
+        let corners: Vec<i32> = slice.planes().map(|p| *p.get(MemIndex2D::origin()).unwrap()).collect();
+        assert_eq!(vec![6, 18], corners);
+    }
+
+    #[test]
+    fn test_plane_mut() {
+        let mut volume = counting_volume();
+        {
+            let mut plane = volume.plane_mut(1).unwrap();
+            plane[MemIndex2D::new(0, 0)] = 100;
+        }
+        assert_eq!(100, volume[MemIndex3D::new(1, 0, 0)]);
+    }
+}
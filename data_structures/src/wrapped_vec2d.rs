@@ -0,0 +1,165 @@
+use std::ops::Index;
+
+use memory_math::memory_index2d::MemIndex2D;
+use memory_math::memory_offset2d::MemOffset2D;
+use memory_math::size_2d::{HasSize2D, Size2D};
+use memory_math::wrapping_2d::{WrappedPiece, WrappedSpan2D};
+use crate::vec2d::{Vec2D, Vec2DSlice};
+
+impl<T> Vec2D<T> {
+
+    /// Cell at index2d moved by offset, wrapping around the edges as if the grid were a torus.
+    /// None only if the grid has no cells
+    pub fn get_wrapped(&self, index2d: MemIndex2D, offset: MemOffset2D) -> Option<&T> {
+        let wrapped: MemIndex2D = self.size.wrapping_add(index2d, offset)?;
+        self.get_index2d(wrapped)
+    }
+
+    pub fn get_wrapped_mut(&mut self, index2d: MemIndex2D, offset: MemOffset2D) -> Option<&mut T> {
+        let wrapped: MemIndex2D = self.size.wrapping_add(index2d, offset)?;
+        let index: usize = self.size.index2d_to_index(wrapped)?;
+        self.items_mut().get_mut(index)
+    }
+
+    /// View of a region that may run over the edges of the grid and continue on the opposite side.
+    /// None if the region is empty or larger than the grid, as it would then see some cells twice
+    pub fn get_wrapped_slice(&self, region: WrappedSpan2D) -> Option<WrappedVec2DSlice<'_, T>> {
+        region.pieces(self.size)?;
+        Some(WrappedVec2DSlice { source: self, region })
+    }
+}
+
+/// Read only view of a wrapped region of a Vec2D, indexed as one rectangle starting at its own origin.
+/// Each row of the view is made of at most two contiguous runs of the grid.
+pub struct WrappedVec2DSlice<'a, T> {
+    source: &'a Vec2D<T>,
+    region: WrappedSpan2D,
+}
+
+impl<'a, T> Clone for WrappedVec2DSlice<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for WrappedVec2DSlice<'a, T> {}
+
+impl<'a, T> HasSize2D for WrappedVec2DSlice<'a, T> {
+    fn row_count(&self) -> usize {
+        self.region.size.row_count
+    }
+
+    fn column_count(&self) -> usize {
+        self.region.size.column_count
+    }
+
+    fn size(&self) -> Size2D {
+        self.region.size
+    }
+}
+
+impl<'a, T> Index<MemIndex2D> for WrappedVec2DSlice<'a, T> {
+    type Output = T;
+
+    fn index(&self, index: MemIndex2D) -> &Self::Output {
+        self.get(index).unwrap_or_else(|| panic!("index {} out of bounds of {}", index, self.size()))
+    }
+}
+
+impl<'a, T> WrappedVec2DSlice<'a, T> {
+
+    pub fn region(&self) -> WrappedSpan2D {
+        self.region
+    }
+
+    pub fn get(&self, index2d: MemIndex2D) -> Option<&'a T> {
+        let source: &'a Vec2D<T> = self.source;
+        source.get_index2d(self.region.region_index2d_to_torus(index2d, source.size)?)
+    }
+
+    /// In bounds slices of the grid that make up the view, each with the view index of its top left cell
+    pub fn pieces(&self) -> Vec<(MemIndex2D, Vec2DSlice<'a, T>)> {
+        let source: &'a Vec2D<T> = self.source;
+        self.region
+            .pieces(source.size)
+            .unwrap()
+            .into_iter()
+            .map(|WrappedPiece { span2d, region_index2d }| (region_index2d, source.get_slice(span2d).unwrap()))
+            .collect()
+    }
+
+    /// The contiguous runs of the grid that make up row, left to right
+    pub fn row_segments(&self, row: usize) -> Option<impl Iterator<Item = &'a [T]> + use<'a, T>> {
+        let start: MemIndex2D = self.region.region_index2d_to_torus(MemIndex2D::new(row, 0), self.source.size)?;
+        let grid_row: &'a [T] = &self.source.items()[start.row * self.source.column_count()..][..self.source.column_count()];
+
+        let before_edge: usize = (grid_row.len() - start.col).min(self.column_count());
+        let (first, second) = (&grid_row[start.col..start.col + before_edge], &grid_row[..self.column_count() - before_edge]);
+        Some([first, second].into_iter().filter(|segment| !segment.is_empty()))
+    }
+
+    /// Cells in row major order of the view
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + use<'a, T> {
+        let view: WrappedVec2DSlice<'a, T> = *self;
+        (0..self.row_count()).flat_map(move |row| view.row_segments(row).unwrap().flatten())
+    }
+
+    /// Copy the view out into a grid of its own
+    pub fn to_vec2d(&self) -> Vec2D<T>
+    where
+        T: Clone,
+    {
+        Vec2D::new_items_size(self.iter().cloned().collect(), self.size()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counting(rows: usize, columns: usize) -> Vec2D<i32> {
+        Vec2D::new_items_size((0..(rows * columns) as i32).collect(), Size2D::new(rows, columns)).unwrap()
+    }
+
+    #[test]
+    fn test_get_wrapped() {
+        let mut grid = counting(3, 4);
+        assert_eq!(Some(&11), grid.get_wrapped(MemIndex2D::origin(), MemOffset2D::new(-1, -1)));
+        assert_eq!(Some(&4), grid.get_wrapped(MemIndex2D::new(0, 3), MemOffset2D::new(1, 1)));
+        assert_eq!(Some(&6), grid.get_wrapped(MemIndex2D::new(1, 2), MemOffset2D::new(30, -40)));
+
+        *grid.get_wrapped_mut(MemIndex2D::new(2, 3), MemOffset2D::new(1, 0)).unwrap() = 100;
+        assert_eq!(100, grid[MemIndex2D::new(0, 3)]);
+
+        let empty: Vec2D<i32> = Vec2D::new_items_size(Vec::new(), Size2D::new(0, 0)).unwrap();
+        assert!(empty.get_wrapped(MemIndex2D::origin(), MemOffset2D::new(0, 0)).is_none());
+    }
+
+    #[test]
+    fn test_wrapped_slice() {
+        let grid = counting(3, 4);
+        let view = grid.get_wrapped_slice(WrappedSpan2D::new(MemOffset2D::new(2, -1), Size2D::new(2, 3))).unwrap();
+
+        assert_eq!(Size2D::new(2, 3), view.size());
+        assert_eq!(vec![11, 8, 9, 3, 0, 1], view.iter().copied().collect::<Vec<i32>>());
+        assert_eq!(1, view[MemIndex2D::new(1, 2)]);
+        assert!(view.get(MemIndex2D::new(2, 0)).is_none());
+
+        let segments: Vec<&[i32]> = view.row_segments(1).unwrap().collect();
+        assert_eq!(vec![&[3][..], &[0, 1][..]], segments);
+
+        let pieces = view.pieces();
+        assert_eq!(4, pieces.len());
+        for (at, piece) in &pieces {
+            for (index2d, value) in piece.indexed_iter() {
+                assert_eq!(*value, view[MemIndex2D::new(at.row + index2d.row, at.col + index2d.col)]);
+            }
+        }
+
+        let inside = grid.get_wrapped_slice(WrappedSpan2D::new(MemOffset2D::new(4, 5), Size2D::new(2, 2))).unwrap();
+        assert_eq!(1, inside.pieces().len());
+        assert_eq!(vec![5, 6, 9, 10], inside.to_vec2d().iter().copied().collect::<Vec<i32>>());
+
+        assert!(grid.get_wrapped_slice(WrappedSpan2D::new(MemOffset2D::new(0, 0), Size2D::new(4, 1))).is_none());
+    }
+}
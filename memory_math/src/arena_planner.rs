@@ -0,0 +1,284 @@
+use crate::memory_span::MemSpan;
+
+///A buffer that must live in the arena from first_use to last_use (both inclusive)
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BufferRequest
+{
+    pub size: usize,
+    pub alignment: usize,
+    pub first_use: usize,
+    pub last_use: usize
+}
+
+impl BufferRequest
+{
+    ///None if the alignment is not a power of two or the lifetime ends before it starts or does not fit in a usize
+    pub fn new(size: usize, alignment: usize, first_use: usize, last_use: usize) -> Option<Self>
+    {
+        let request = BufferRequest { size, alignment, first_use, last_use };
+        if !request.is_valid()
+        {
+            return None;
+        }
+
+        Some(request)
+    }
+
+    pub fn is_valid(&self) -> bool
+    {
+        self.alignment.is_power_of_two() && self.lifetime().is_some()
+    }
+
+    ///The steps during which the buffer is alive, as a span of time.
+    /// None if the lifetime ends before it starts or its step count does not fit in a usize
+    #[inline]
+    pub fn lifetime(&self) -> Option<MemSpan>
+    {
+        let count: usize = self.last_use.checked_sub(self.first_use)?.checked_add(1)?;
+        Some(MemSpan { min: self.first_use, count })
+    }
+
+    ///Compared on the inclusive bounds, so it holds for any pair of requests without building their spans
+    #[inline]
+    pub fn lifetime_overlaps(&self, other: &BufferRequest) -> bool
+    {
+        self.first_use <= other.last_use && other.first_use <= self.last_use
+    }
+}
+
+///Offsets chosen by an ArenaPlanner, spans are in the same order as the requests
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArenaPlan
+{
+    pub spans: Vec<MemSpan>,
+    pub arena_size: usize,
+    pub peak_live_size: usize
+}
+
+impl ArenaPlan
+{
+    pub fn span(&self, request_id: usize) -> Option<&MemSpan>
+    {
+        self.spans.get(request_id)
+    }
+
+    ///Share of the arena which is never needed, even at the busiest step.
+    /// 0.0 means the arena is as small as the peak of simultaneously live bytes.
+    pub fn fragmentation(&self) -> f64
+    {
+        if self.arena_size == 0
+        {
+            return 0.0;
+        }
+
+        1.0 - (self.peak_live_size as f64 / self.arena_size as f64)
+    }
+
+    ///Check that buffers whose lifetimes overlap never share memory and that every offset is aligned
+    pub fn validate(&self, requests: &[BufferRequest]) -> bool
+    {
+        if requests.len() != self.spans.len()
+        {
+            return false;
+        }
+
+        for i in 0..requests.len()
+        {
+            let span: &MemSpan = &self.spans[i];
+            if span.len() != requests[i].size || span.upper_bound() > self.arena_size
+            {
+                return false;
+            }
+
+            if requests[i].size != 0 && !span.min.is_multiple_of(requests[i].alignment)
+            {
+                return false;
+            }
+
+            for j in (i + 1)..requests.len()
+            {
+                if requests[i].lifetime_overlaps(&requests[j]) && span.overlaps(&self.spans[j])
+                {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+///Plans a static memory layout for buffers whose lifetimes are known up front.
+/// Buffers which are never alive at the same time may share memory.
+#[derive(Clone, Debug, Default)]
+pub struct ArenaPlanner
+{
+    requests: Vec<BufferRequest>
+}
+
+impl ArenaPlanner
+{
+    pub fn new() -> Self
+    {
+        ArenaPlanner { requests: Vec::new() }
+    }
+
+    ///Add a buffer and return its id, the index of its span in the plan
+    pub fn add(&mut self, request: BufferRequest) -> usize
+    {
+        self.requests.push(request);
+        self.requests.len() - 1
+    }
+
+    pub fn requests(&self) -> &[BufferRequest]
+    {
+        &self.requests
+    }
+
+    ///Greedy by size: the largest buffers are placed first, each one at the lowest
+    /// aligned offset that does not collide with an already placed buffer it shares time with.
+    /// None if any request is invalid.
+    pub fn plan(&self) -> Option<ArenaPlan>
+    {
+        if self.requests.iter().any(|r| !r.is_valid())
+        {
+            return None;
+        }
+
+        let mut order: Vec<usize> = (0..self.requests.len()).collect();
+        order.sort_by(|&a, &b| self.requests[b].size.cmp(&self.requests[a].size).then(a.cmp(&b)));
+
+        let mut spans: Vec<Option<MemSpan>> = vec![None; self.requests.len()];
+        let mut arena_size: usize = 0;
+
+        for id in order
+        {
+            let request: &BufferRequest = &self.requests[id];
+
+            let mut conflicts: Vec<MemSpan> = live_conflicts(&self.requests, &spans, request);
+            conflicts.sort();
+
+            let mut offset: usize = 0;
+            for conflict in conflicts
+            {
+                let candidate = MemSpan { min: offset, count: request.size };
+                if !candidate.overlaps(&conflict)
+                {
+                    if candidate.upper_bound() <= conflict.min
+                    {
+                        break;
+                    }
+                    continue;
+                }
+
+                offset = align_up(conflict.upper_bound(), request.alignment)?;
+            }
+
+            let span = MemSpan { min: offset, count: request.size };
+            arena_size = arena_size.max(span.upper_bound());
+            spans[id] = Some(span);
+        }
+
+        Some(ArenaPlan
+        {
+            spans: spans.into_iter().collect::<Option<Vec<MemSpan>>>()?,
+            arena_size,
+            peak_live_size: peak_live_size(&self.requests)
+        })
+    }
+}
+
+fn live_conflicts(requests: &[BufferRequest], spans: &[Option<MemSpan>], request: &BufferRequest) -> Vec<MemSpan>
+{
+    requests.iter().zip(spans.iter())
+        .filter_map(|(other, span)| Some((other, (*span)?)))
+        .filter(|(other, span)| !span.is_empty() && other.lifetime_overlaps(request))
+        .map(|(_, span)| span)
+        .collect()
+}
+
+fn peak_live_size(requests: &[BufferRequest]) -> usize
+{
+    let mut steps: Vec<usize> = requests.iter().map(|r| r.first_use).collect();
+    steps.sort();
+    steps.dedup();
+
+    steps.into_iter()
+        .map(|step| requests.iter().filter(|r| r.first_use <= step && step <= r.last_use).map(|r| r.size).sum())
+        .max()
+        .unwrap_or(0)
+}
+
+#[inline]
+fn align_up(offset: usize, alignment: usize) -> Option<usize>
+{
+    let mask: usize = alignment - 1;
+    Some(offset.checked_add(mask)? & !mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disjoint_lifetimes_share_memory()
+    {
+        let mut planner = ArenaPlanner::new();
+        let a = planner.add(BufferRequest::new(64, 1, 0, 1).unwrap());
+        let b = planner.add(BufferRequest::new(64, 1, 2, 3).unwrap());
+        let c = planner.add(BufferRequest::new(32, 1, 4, 5).unwrap());
+
+        let plan = planner.plan().unwrap();
+        assert!(plan.validate(planner.requests()));
+        assert_eq!(64, plan.arena_size);
+        assert_eq!(plan.span(a).unwrap().min, plan.span(b).unwrap().min);
+        assert_eq!(0, plan.span(c).unwrap().min);
+        assert_eq!(0.0, plan.fragmentation());
+    }
+
+    #[test]
+    fn test_overlapping_lifetimes_do_not_share()
+    {
+        let mut planner = ArenaPlanner::new();
+        planner.add(BufferRequest::new(10, 1, 0, 2).unwrap());
+        planner.add(BufferRequest::new(20, 1, 2, 4).unwrap());
+        planner.add(BufferRequest::new(5, 1, 3, 6).unwrap());
+
+        let plan = planner.plan().unwrap();
+        assert!(plan.validate(planner.requests()));
+        assert!(!plan.spans[0].overlaps(&plan.spans[1]));
+        assert!(!plan.spans[1].overlaps(&plan.spans[2]));
+        assert_eq!(30, plan.peak_live_size);
+        assert_eq!(30, plan.arena_size);
+    }
+
+    #[test]
+    fn test_alignment_is_respected()
+    {
+        let mut planner = ArenaPlanner::new();
+        planner.add(BufferRequest::new(10, 1, 0, 3).unwrap());
+        let aligned = planner.add(BufferRequest::new(8, 16, 0, 3).unwrap());
+
+        let plan = planner.plan().unwrap();
+        assert!(plan.validate(planner.requests()));
+        assert_eq!(16, plan.span(aligned).unwrap().min);
+        assert_eq!(24, plan.arena_size);
+        assert!(plan.fragmentation() > 0.0);
+    }
+
+    #[test]
+    fn test_invalid_requests()
+    {
+        assert!(BufferRequest::new(8, 3, 0, 1).is_none());
+        assert!(BufferRequest::new(8, 4, 2, 1).is_none());
+        assert!(BufferRequest::new(8, 4, 0, usize::MAX).is_none());
+        assert_eq!(Some(MemSpan { min: 1, count: usize::MAX }), BufferRequest::new(8, 4, 1, usize::MAX).unwrap().lifetime());
+
+        let reversed = BufferRequest { size: 8, alignment: 4, first_use: 2, last_use: 1 };
+        assert!(reversed.lifetime().is_none());
+
+        let mut planner = ArenaPlanner::new();
+        planner.add(BufferRequest { size: 8, alignment: 0, first_use: 0, last_use: 0 });
+        assert!(planner.plan().is_none());
+    }
+}
@@ -0,0 +1,383 @@
+use std::fmt::Display;
+use crate::memory_span2d::MemSpan2D;
+use crate::size_2d::{HasSize2D, Size2D};
+
+///Handle to a region handed out by an AtlasAllocator.
+/// The generation is bumped every time a slot is freed, so a handle
+/// kept after its region was released no longer resolves.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AtlasHandle
+{
+    index: usize,
+    generation: u32
+}
+
+impl Display for AtlasHandle
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AtlasHandle({}, gen {})", self.index, self.generation)
+    }
+}
+
+///A region that changed place during a repack
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtlasMove
+{
+    pub handle: AtlasHandle,
+    pub from: MemSpan2D,
+    pub to: MemSpan2D
+}
+
+struct AtlasSlot
+{
+    generation: u32,
+    span: Option<MemSpan2D>
+}
+
+///Hands out non overlapping MemSpan2D regions of a fixed Size2D area
+/// using guillotine packing: every placement cuts the free rectangle it
+/// lands in into (at most) two smaller free rectangles.
+pub struct AtlasAllocator
+{
+    size: Size2D,
+    free_rects: Vec<MemSpan2D>,
+    slots: Vec<AtlasSlot>,
+    free_slots: Vec<usize>
+}
+
+impl HasSize2D for AtlasAllocator
+{
+    fn row_count(&self) -> usize {
+        self.size.row_count
+    }
+
+    fn column_count(&self) -> usize {
+        self.size.column_count
+    }
+
+    fn size(&self) -> Size2D {
+        self.size
+    }
+}
+
+impl AtlasAllocator
+{
+    pub fn new(size: Size2D) -> Self
+    {
+        AtlasAllocator
+        {
+            size,
+            free_rects: Self::initial_free_rects(size),
+            slots: Vec::new(),
+            free_slots: Vec::new()
+        }
+    }
+
+    ///Reserve a region of the requested size, None if no free rectangle can hold it
+    pub fn allocate(&mut self, request: Size2D) -> Option<AtlasHandle>
+    {
+        let span: MemSpan2D = self.place(request)?;
+        Some(self.insert_slot(span))
+    }
+
+    ///Span currently held by the handle, None if the handle is stale
+    pub fn get(&self, handle: AtlasHandle) -> Option<&MemSpan2D>
+    {
+        let slot: &AtlasSlot = self.slots.get(handle.index)?;
+        if slot.generation != handle.generation
+        {
+            return None;
+        }
+
+        slot.span.as_ref()
+    }
+
+    #[inline]
+    pub fn is_valid(&self, handle: AtlasHandle) -> bool
+    {
+        self.get(handle).is_some()
+    }
+
+    ///Release the region held by the handle and return it, None if the handle is stale
+    pub fn free(&mut self, handle: AtlasHandle) -> Option<MemSpan2D>
+    {
+        let slot: &mut AtlasSlot = self.slots.get_mut(handle.index)?;
+        if slot.generation != handle.generation
+        {
+            return None;
+        }
+
+        let span: MemSpan2D = slot.span.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free_slots.push(handle.index);
+
+        self.free_rects.push(span.clone());
+        self.merge_free_rects();
+
+        Some(span)
+    }
+
+    ///Number of live allocations
+    pub fn allocation_count(&self) -> usize
+    {
+        self.slots.len() - self.free_slots.len()
+    }
+
+    pub fn used_area(&self) -> usize
+    {
+        //a request is only placed once its area fits in usize
+        self.slots.iter().filter_map(|s| s.span.as_ref()).filter_map(|s| s.area()).sum()
+    }
+
+    ///None if the area of the atlas does not fit in usize
+    pub fn free_area(&self) -> Option<usize>
+    {
+        self.size.area()?.checked_sub(self.used_area())
+    }
+
+    ///Area of the biggest free rectangle, a request larger than this can not succeed.
+    /// None if that area does not fit in usize
+    pub fn largest_free_area(&self) -> Option<usize>
+    {
+        self.free_rects.iter().try_fold(0, |largest: usize, r| Some(largest.max(r.area()?)))
+    }
+
+    ///Iterate over live (handle, span) pairs
+    pub fn allocations(&self) -> impl Iterator<Item = (AtlasHandle, &MemSpan2D)>
+    {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let span: &MemSpan2D = slot.span.as_ref()?;
+            Some((AtlasHandle { index, generation: slot.generation }, span))
+        })
+    }
+
+    ///Pack every live allocation again from scratch, tallest first.
+    /// Handles stay valid, the returned list reports the regions whose span changed
+    /// so the caller can move their contents. If the allocations can not all be
+    /// placed the allocator is left untouched and None is returned.
+    pub fn repack(&mut self) -> Option<Vec<AtlasMove>>
+    {
+        let mut live: Vec<(usize, MemSpan2D)> = self.slots.iter().enumerate()
+            .filter_map(|(index, slot)| Some((index, slot.span.clone()?)))
+            .collect();
+
+        live.sort_by(|(_, a), (_, b)| {
+            b.row_count().cmp(&a.row_count()).then(b.column_count().cmp(&a.column_count()))
+        });
+
+        let previous_free_rects: Vec<MemSpan2D> = std::mem::replace(&mut self.free_rects, Self::initial_free_rects(self.size));
+        let mut placements: Vec<(usize, MemSpan2D)> = Vec::with_capacity(live.len());
+
+        for (index, span) in live.iter()
+        {
+            match self.place(span.size())
+            {
+                Some(placed) => placements.push((*index, placed)),
+                None => {
+                    self.free_rects = previous_free_rects;
+                    return None;
+                }
+            }
+        }
+
+        let mut moves: Vec<AtlasMove> = Vec::new();
+        for (index, placed) in placements
+        {
+            let slot: &mut AtlasSlot = &mut self.slots[index];
+            let from: MemSpan2D = slot.span.replace(placed.clone())?;
+            if from != placed
+            {
+                moves.push(AtlasMove { handle: AtlasHandle { index, generation: slot.generation }, from, to: placed });
+            }
+        }
+
+        Some(moves)
+    }
+
+    fn initial_free_rects(size: Size2D) -> Vec<MemSpan2D>
+    {
+        let extents: MemSpan2D = size.into();
+        if !extents.valid()
+        {
+            return Vec::new();
+        }
+
+        vec![extents]
+    }
+
+    fn insert_slot(&mut self, span: MemSpan2D) -> AtlasHandle
+    {
+        if let Some(index) = self.free_slots.pop()
+        {
+            let slot: &mut AtlasSlot = &mut self.slots[index];
+            slot.span = Some(span);
+            return AtlasHandle { index, generation: slot.generation };
+        }
+
+        self.slots.push(AtlasSlot { generation: 0, span: Some(span) });
+        AtlasHandle { index: self.slots.len() - 1, generation: 0 }
+    }
+
+    ///Best area fit: choose the free rectangle that leaves the least area unused
+    fn place(&mut self, request: Size2D) -> Option<MemSpan2D>
+    {
+        let request_area: usize = request.area().filter(|&area| area != 0)?;
+
+        //a free rectangle too large to count its area wastes the most
+        let (best, _) = self.free_rects.iter().enumerate()
+            .filter(|(_, r)| r.row_count() >= request.row_count && r.column_count() >= request.column_count)
+            .map(|(i, r)| (i, r.area().map_or(usize::MAX, |area| area - request_area)))
+            .min_by_key(|(_, waste)| *waste)?;
+
+        let rect: MemSpan2D = self.free_rects.swap_remove(best);
+        let min_row: usize = rect.min_row();
+        let min_col: usize = rect.min_column();
+        let placed = MemSpan2D::new_from_usize(min_row, min_col, min_row + request.row_count, min_col + request.column_count);
+
+        let leftover_rows: usize = rect.row_count() - request.row_count;
+        let leftover_cols: usize = rect.column_count() - request.column_count;
+        let row_upper_bound: usize = rect.row_span.upper_bound();
+        let col_upper_bound: usize = rect.col_span.upper_bound();
+
+        //split along the shorter leftover axis, so the bigger leftover stays in one piece
+        let (right, below) = if leftover_cols <= leftover_rows
+        {
+            (
+                MemSpan2D::new_from_usize(min_row, placed.col_span.upper_bound(), placed.row_span.upper_bound(), col_upper_bound),
+                MemSpan2D::new_from_usize(placed.row_span.upper_bound(), min_col, row_upper_bound, col_upper_bound)
+            )
+        }
+        else
+        {
+            (
+                MemSpan2D::new_from_usize(min_row, placed.col_span.upper_bound(), row_upper_bound, col_upper_bound),
+                MemSpan2D::new_from_usize(placed.row_span.upper_bound(), min_col, row_upper_bound, placed.col_span.upper_bound())
+            )
+        };
+
+        for leftover in [right, below]
+        {
+            if leftover.valid()
+            {
+                self.free_rects.push(leftover);
+            }
+        }
+
+        Some(placed)
+    }
+
+    ///Join free rectangles which share a complete edge, until none are left
+    fn merge_free_rects(&mut self)
+    {
+        let mut merged: bool = true;
+        while merged
+        {
+            merged = false;
+
+            'search: for i in 0..self.free_rects.len()
+            {
+                for j in (i + 1)..self.free_rects.len()
+                {
+                    if let Some(joined) = self.free_rects[i].join_aligned(&self.free_rects[j])
+                    {
+                        self.free_rects[i] = joined;
+                        self.free_rects.swap_remove(j);
+                        merged = true;
+                        break 'search;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_disjoint(atlas: &AtlasAllocator)
+    {
+        let spans: Vec<MemSpan2D> = atlas.allocations().map(|(_, s)| s.clone()).collect();
+        assert!(!MemSpan2D::spans_overlap_or_invalid(&spans));
+        for span in spans
+        {
+            assert!(span.row_span.upper_bound() <= atlas.row_count());
+            assert!(span.col_span.upper_bound() <= atlas.column_count());
+        }
+    }
+
+    #[test]
+    fn test_allocate_until_full()
+    {
+        let mut atlas = AtlasAllocator::new(Size2D::new(8, 8));
+        let mut handles: Vec<AtlasHandle> = Vec::new();
+
+        for _ in 0..4
+        {
+            handles.push(atlas.allocate(Size2D::new(4, 4)).unwrap());
+        }
+
+        assert!(atlas.allocate(Size2D::new(1, 1)).is_none());
+        assert_eq!(Some(0), atlas.free_area());
+        assert_eq!(4, atlas.allocation_count());
+        assert_disjoint(&atlas);
+    }
+
+    #[test]
+    fn test_free_and_stale_handle()
+    {
+        let mut atlas = AtlasAllocator::new(Size2D::new(4, 8));
+        let first = atlas.allocate(Size2D::new(4, 4)).unwrap();
+        let second = atlas.allocate(Size2D::new(4, 4)).unwrap();
+
+        let freed = atlas.free(first).unwrap();
+        assert!(atlas.get(first).is_none());
+        assert!(atlas.free(first).is_none());
+        assert!(atlas.is_valid(second));
+
+        //slot is reused with a new generation
+        let third = atlas.allocate(Size2D::new(4, 4)).unwrap();
+        assert_ne!(first, third);
+        assert!(atlas.get(first).is_none());
+        assert_eq!(&freed, atlas.get(third).unwrap());
+    }
+
+    #[test]
+    fn test_free_merges_back_to_whole_area()
+    {
+        let mut atlas = AtlasAllocator::new(Size2D::new(6, 6));
+        let handles: Vec<AtlasHandle> = (0..9).map(|_| atlas.allocate(Size2D::new(2, 2)).unwrap()).collect();
+        assert_disjoint(&atlas);
+
+        for handle in handles
+        {
+            atlas.free(handle).unwrap();
+        }
+
+        assert_eq!(Some(36), atlas.largest_free_area());
+        assert!(atlas.allocate(Size2D::new(6, 6)).is_some());
+    }
+
+    #[test]
+    fn test_repack_reports_moves()
+    {
+        let mut atlas = AtlasAllocator::new(Size2D::new(4, 8));
+        let small = atlas.allocate(Size2D::new(2, 2)).unwrap();
+        let tall = atlas.allocate(Size2D::new(4, 3)).unwrap();
+        let other = atlas.allocate(Size2D::new(2, 2)).unwrap();
+        atlas.free(small).unwrap();
+
+        let moves = atlas.repack().unwrap();
+        assert_disjoint(&atlas);
+
+        for m in moves.iter()
+        {
+            assert_eq!(Some(&m.to), atlas.get(m.handle));
+            assert_eq!(m.from.size(), m.to.size());
+        }
+
+        assert!(atlas.is_valid(tall));
+        assert!(atlas.is_valid(other));
+        assert!(atlas.allocate(Size2D::new(4, 3)).is_some());
+    }
+}
@@ -0,0 +1,393 @@
+use crate::memory_index2d::MemIndex2D;
+use crate::memory_span2d::MemSpan2D;
+use crate::size_2d::HasSize2D;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SplitAxis
+{
+    ///Cut between two rows, producing a top and a bottom half
+    Rows,
+    ///Cut between two columns, producing a left and a right half
+    Columns
+}
+
+///Where to cut a span: `at` is the number of rows (or columns) kept in the first half
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Split
+{
+    pub axis: SplitAxis,
+    pub at: usize
+}
+
+impl Split
+{
+    ///Cut span in two, None if the split does not leave both halves non empty
+    pub fn apply(&self, span: &MemSpan2D) -> Option<(MemSpan2D, MemSpan2D)>
+    {
+        let length: usize = match self.axis
+        {
+            SplitAxis::Rows => span.row_count(),
+            SplitAxis::Columns => span.column_count()
+        };
+
+        if self.at == 0 || self.at >= length
+        {
+            return None;
+        }
+
+        let keep: isize = self.at as isize;
+        let cut: isize = (length - self.at) as isize;
+
+        match self.axis
+        {
+            SplitAxis::Rows => Some((span.shift_max_rows(-cut)?, span.shift_min_rows(keep)?)),
+            SplitAxis::Columns => Some((span.shift_max_columns(-cut)?, span.shift_min_columns(keep)?))
+        }
+    }
+}
+
+///Decides how (and whether) a span is split while building a BspTree.
+/// Returning None makes the span a leaf.
+pub trait SplitPolicy
+{
+    fn split(&mut self, span: &MemSpan2D, depth: usize) -> Option<Split>;
+}
+
+fn longest_axis(span: &MemSpan2D) -> (SplitAxis, usize)
+{
+    if span.row_count() >= span.column_count()
+    {
+        (SplitAxis::Rows, span.row_count())
+    }
+    else
+    {
+        (SplitAxis::Columns, span.column_count())
+    }
+}
+
+///Halve the longest axis while both halves keep at least min_size rows or columns
+pub struct LongestAxisSplit
+{
+    pub min_size: usize
+}
+
+impl SplitPolicy for LongestAxisSplit
+{
+    fn split(&mut self, span: &MemSpan2D, _depth: usize) -> Option<Split>
+    {
+        let (axis, length) = longest_axis(span);
+        if length < 2 * self.min_size.max(1)
+        {
+            return None;
+        }
+
+        Some(Split { axis, at: length / 2 })
+    }
+}
+
+///Cut the longest axis at a fixed ratio of its length, clamped so both halves keep min_size
+pub struct FixedRatioSplit
+{
+    pub ratio: f64,
+    pub min_size: usize
+}
+
+impl SplitPolicy for FixedRatioSplit
+{
+    fn split(&mut self, span: &MemSpan2D, _depth: usize) -> Option<Split>
+    {
+        let (axis, length) = longest_axis(span);
+        let min_size: usize = self.min_size.max(1);
+        if length < 2 * min_size
+        {
+            return None;
+        }
+
+        let at: usize = (length as f64 * self.ratio).round() as usize;
+        Some(Split { axis, at: at.clamp(min_size, length - min_size) })
+    }
+}
+
+///Random axis and cut position, reproducible from the seed (xorshift64*)
+pub struct RandomSplit
+{
+    state: u64,
+    pub min_size: usize
+}
+
+impl RandomSplit
+{
+    pub fn new(seed: u64, min_size: usize) -> Self
+    {
+        //xorshift gets stuck on a zero state
+        let state: u64 = if seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { seed };
+        RandomSplit { state, min_size }
+    }
+
+    fn next_u64(&mut self) -> u64
+    {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+impl SplitPolicy for RandomSplit
+{
+    fn split(&mut self, span: &MemSpan2D, _depth: usize) -> Option<Split>
+    {
+        let min_size: usize = self.min_size.max(1);
+        let can_split_rows: bool = span.row_count() >= 2 * min_size;
+        let can_split_columns: bool = span.column_count() >= 2 * min_size;
+
+        let axis: SplitAxis = match (can_split_rows, can_split_columns)
+        {
+            (false, false) => return None,
+            (true, false) => SplitAxis::Rows,
+            (false, true) => SplitAxis::Columns,
+            (true, true) => if self.next_u64().is_multiple_of(2) { SplitAxis::Rows } else { SplitAxis::Columns }
+        };
+
+        let length: usize = match axis
+        {
+            SplitAxis::Rows => span.row_count(),
+            SplitAxis::Columns => span.column_count()
+        };
+
+        let choices: u64 = (length - 2 * min_size + 1) as u64;
+        let at: usize = min_size + (self.next_u64() % choices) as usize;
+        Some(Split { axis, at })
+    }
+}
+
+enum BspNode
+{
+    Leaf { span: MemSpan2D, leaf: usize },
+    Branch { span: MemSpan2D, split: Split, children: [usize; 2] }
+}
+
+impl BspNode
+{
+    fn span(&self) -> &MemSpan2D
+    {
+        match self
+        {
+            BspNode::Leaf { span, .. } => span,
+            BspNode::Branch { span, .. } => span
+        }
+    }
+}
+
+///Binary space partition of a MemSpan2D, leaves are numbered in depth first order
+pub struct BspTree
+{
+    nodes: Vec<BspNode>,
+    leaves: Vec<usize>
+}
+
+impl BspTree
+{
+    pub fn build<P: SplitPolicy>(span: MemSpan2D, policy: &mut P, max_depth: usize) -> Self
+    {
+        let mut tree = BspTree { nodes: Vec::new(), leaves: Vec::new() };
+        tree.build_node(span, policy, 0, max_depth);
+        tree
+    }
+
+    fn build_node<P: SplitPolicy>(&mut self, span: MemSpan2D, policy: &mut P, depth: usize, max_depth: usize) -> usize
+    {
+        let node_index: usize = self.nodes.len();
+
+        let halves = if depth < max_depth
+        {
+            policy.split(&span, depth).and_then(|split| Some((split, split.apply(&span)?)))
+        }
+        else
+        {
+            None
+        };
+
+        let Some((split, (first, second))) = halves else {
+            self.nodes.push(BspNode::Leaf { span, leaf: self.leaves.len() });
+            self.leaves.push(node_index);
+            return node_index;
+        };
+
+        self.nodes.push(BspNode::Branch { span, split, children: [0, 0] });
+        let first_index: usize = self.build_node(first, policy, depth + 1, max_depth);
+        let second_index: usize = self.build_node(second, policy, depth + 1, max_depth);
+
+        if let BspNode::Branch { children, .. } = &mut self.nodes[node_index]
+        {
+            *children = [first_index, second_index];
+        }
+
+        node_index
+    }
+
+    pub fn root_span(&self) -> &MemSpan2D
+    {
+        self.nodes[0].span()
+    }
+
+    pub fn leaf_count(&self) -> usize
+    {
+        self.leaves.len()
+    }
+
+    pub fn leaf(&self, leaf: usize) -> Option<&MemSpan2D>
+    {
+        Some(self.nodes[*self.leaves.get(leaf)?].span())
+    }
+
+    pub fn leaves(&self) -> impl Iterator<Item = &MemSpan2D>
+    {
+        self.leaves.iter().map(|&node| self.nodes[node].span())
+    }
+
+    ///Walk from the root to the leaf containing index2d
+    pub fn leaf_for(&self, index2d: &MemIndex2D) -> Option<usize>
+    {
+        if !self.root_span().contains_index2d(index2d)
+        {
+            return None;
+        }
+
+        let mut node: &BspNode = &self.nodes[0];
+        loop
+        {
+            match node
+            {
+                BspNode::Leaf { leaf, .. } => return Some(*leaf),
+                BspNode::Branch { span, split, children } => {
+                    let second: bool = match split.axis
+                    {
+                        SplitAxis::Rows => index2d.row >= span.min_row() + split.at,
+                        SplitAxis::Columns => index2d.col >= span.min_column() + split.at
+                    };
+                    node = &self.nodes[children[second as usize]];
+                }
+            }
+        }
+    }
+
+    ///Pairs of leaves (lower leaf first) which share a stretch of edge
+    pub fn adjacency(&self) -> Vec<(usize, usize)>
+    {
+        let leaves: Vec<&MemSpan2D> = self.leaves().collect();
+        let mut pairs: Vec<(usize, usize)> = Vec::new();
+        for a in 0..leaves.len()
+        {
+            for b in (a + 1)..leaves.len()
+            {
+                if Self::share_edge(leaves[a], leaves[b])
+                {
+                    pairs.push((a, b));
+                }
+            }
+        }
+
+        pairs
+    }
+
+    ///Leaves which share a stretch of edge with leaf, only leaf is compared against the others
+    pub fn neighbours(&self, leaf: usize) -> Vec<usize>
+    {
+        let Some(span) = self.leaf(leaf) else {
+            return Vec::new();
+        };
+
+        self.leaves().enumerate()
+            .filter(|&(other, other_span)| other != leaf && Self::share_edge(span, other_span))
+            .map(|(other, _)| other)
+            .collect()
+    }
+
+    fn share_edge(a: &MemSpan2D, b: &MemSpan2D) -> bool
+    {
+        let touch_rows: bool = a.row_span.upper_bound() == b.min_row() || b.row_span.upper_bound() == a.min_row();
+        let touch_columns: bool = a.col_span.upper_bound() == b.min_column() || b.col_span.upper_bound() == a.min_column();
+
+        (touch_rows && a.col_span.overlaps(&b.col_span)) || (touch_columns && a.row_span.overlaps(&b.row_span))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_exact_cover(tree: &BspTree)
+    {
+        let leaves: Vec<MemSpan2D> = tree.leaves().cloned().collect();
+        assert!(!MemSpan2D::spans_overlap_or_invalid(&leaves));
+        assert_eq!(tree.root_span().area(), leaves.iter().map(|l| l.area()).sum::<Option<usize>>());
+    }
+
+    #[test]
+    fn test_longest_axis()
+    {
+        let tree = BspTree::build(MemSpan2D::new_from_usize(2, 3, 10, 7), &mut LongestAxisSplit { min_size: 1 }, 2);
+        assert_exact_cover(&tree);
+        assert_eq!(4, tree.leaf_count());
+
+        for leaf in tree.leaves()
+        {
+            assert_eq!(2, leaf.row_count());
+            assert_eq!(4, leaf.column_count());
+        }
+
+        assert_eq!(vec![(0, 1), (1, 2), (2, 3)], tree.adjacency());
+        assert_eq!(vec![0, 2], tree.neighbours(1));
+        assert_eq!(vec![2], tree.neighbours(3));
+        assert!(tree.neighbours(4).is_empty());
+    }
+
+    #[test]
+    fn test_leaf_for()
+    {
+        let span = MemSpan2D::new_from_usize(5, 5, 21, 13);
+        let tree = BspTree::build(span.clone(), &mut FixedRatioSplit { ratio: 0.3, min_size: 2 }, 4);
+        assert_exact_cover(&tree);
+
+        for row in span.row_span
+        {
+            for col in span.col_span
+            {
+                let index2d = MemIndex2D::new(row, col);
+                let leaf = tree.leaf_for(&index2d).unwrap();
+                assert!(tree.leaf(leaf).unwrap().contains_index2d(&index2d));
+            }
+        }
+
+        assert!(tree.leaf_for(&MemIndex2D::new(4, 5)).is_none());
+        assert!(tree.leaf_for(&MemIndex2D::new(5, 13)).is_none());
+    }
+
+    #[test]
+    fn test_random_is_reproducible()
+    {
+        let span = MemSpan2D::new_row_columns(40, 30);
+        let first = BspTree::build(span.clone(), &mut RandomSplit::new(7, 3), 6);
+        let second = BspTree::build(span.clone(), &mut RandomSplit::new(7, 3), 6);
+
+        assert_exact_cover(&first);
+        assert_eq!(first.leaves().cloned().collect::<Vec<_>>(), second.leaves().cloned().collect::<Vec<_>>());
+
+        for leaf in first.leaves()
+        {
+            assert!(leaf.row_count() >= 3 && leaf.column_count() >= 3);
+        }
+    }
+
+    #[test]
+    fn test_split_apply()
+    {
+        let span = MemSpan2D::new_from_usize(0, 0, 4, 6);
+        let (left, right) = Split { axis: SplitAxis::Columns, at: 2 }.apply(&span).unwrap();
+        assert_eq!(MemSpan2D::new_from_usize(0, 0, 4, 2), left);
+        assert_eq!(MemSpan2D::new_from_usize(0, 2, 4, 6), right);
+
+        assert!(Split { axis: SplitAxis::Rows, at: 4 }.apply(&span).is_none());
+    }
+}
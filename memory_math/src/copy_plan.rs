@@ -0,0 +1,135 @@
+use crate::memory_span::MemSpan;
+use crate::memory_span2d::MemSpan2D;
+use crate::size_2d::{HasSize2D, Size2D};
+
+///Linear offset runs covering the cells of span inside a row major buffer, in buffer order.
+/// Rows merge into a single run when the span covers the full buffer width.
+/// None if the span is invalid or does not fit in the buffer.
+pub fn span_row_runs(span: &MemSpan2D, buffer: &Size2D) -> Option<Vec<MemSpan>>
+{
+    if !span.valid() || span.max_row()? >= buffer.row_count() || span.max_column()? >= buffer.column_count()
+    {
+        return None;
+    }
+
+    let width: usize = span.column_count();
+    let row_start = |row: usize| row * buffer.column_count() + span.min_column();
+
+    if width == buffer.column_count()
+    {
+        let start: usize = row_start(span.min_row());
+        return Some(vec![MemSpan::new_range(start..start + span.area()?)]);
+    }
+
+    Some(span.row_span.into_iter()
+        .map(|row| MemSpan::new_range(row_start(row)..row_start(row) + width))
+        .collect())
+}
+
+///One contiguous copy between two linear buffers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CopyOp
+{
+    pub src_offset: usize,
+    pub dst_offset: usize,
+    pub len: usize
+}
+
+///The fewest contiguous copies moving a span of one row major buffer onto an equally sized span of another.
+/// Cells are paired row major, so the same relative cell lands in the same relative place.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CopyPlan
+{
+    ops: Vec<CopyOp>
+}
+
+impl CopyPlan
+{
+    ///None if the spans differ in size or either does not fit in its buffer
+    pub fn new(src_span: &MemSpan2D, src_size: &Size2D, dst_span: &MemSpan2D, dst_size: &Size2D) -> Option<Self>
+    {
+        if src_span.size() != dst_span.size()
+        {
+            return None;
+        }
+
+        let src_runs: Vec<MemSpan> = span_row_runs(src_span, src_size)?;
+        let dst_runs: Vec<MemSpan> = span_row_runs(dst_span, dst_size)?;
+
+        //walk both run lists together, every op ends where either side's run ends
+        let mut ops: Vec<CopyOp> = Vec::new();
+        let (mut src_iter, mut dst_iter) = (src_runs.into_iter(), dst_runs.into_iter());
+        let (mut src_run, mut dst_run) = (src_iter.next(), dst_iter.next());
+
+        while let (Some(src), Some(dst)) = (src_run, dst_run)
+        {
+            let len: usize = src.len().min(dst.len());
+            ops.push(CopyOp { src_offset: src.min, dst_offset: dst.min, len });
+
+            src_run = if len == src.len() { src_iter.next() } else { Some(MemSpan::new_range(src.min + len..src.upper_bound())) };
+            dst_run = if len == dst.len() { dst_iter.next() } else { Some(MemSpan::new_range(dst.min + len..dst.upper_bound())) };
+        }
+
+        Some(CopyPlan { ops })
+    }
+
+    pub fn ops(&self) -> &[CopyOp]
+    {
+        &self.ops
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.ops.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.ops.is_empty()
+    }
+
+    ///Number of cells moved by the whole plan
+    pub fn cell_count(&self) -> usize
+    {
+        self.ops.iter().map(|op| op.len).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_runs()
+    {
+        let buffer = Size2D::new(4, 6);
+        let runs = span_row_runs(&MemSpan2D::new_from_usize(1, 2, 3, 5), &buffer).unwrap();
+        assert_eq!(vec![MemSpan::new_range(8..11), MemSpan::new_range(14..17)], runs);
+
+        let full_width = span_row_runs(&MemSpan2D::new_from_usize(1, 0, 4, 6), &buffer).unwrap();
+        assert_eq!(vec![MemSpan::new_range(6..24)], full_width);
+
+        assert!(span_row_runs(&MemSpan2D::new_from_usize(1, 2, 5, 5), &buffer).is_none());
+        assert!(span_row_runs(&MemSpan2D::new_from_usize(1, 2, 1, 5), &buffer).is_none());
+    }
+
+    #[test]
+    fn test_plans()
+    {
+        //full width rows on both sides copy in one go
+        let plan = CopyPlan::new(&MemSpan2D::new_from_usize(2, 0, 5, 4), &Size2D::new(8, 4), &MemSpan2D::new_from_usize(0, 0, 3, 4), &Size2D::new(3, 4)).unwrap();
+        assert_eq!(&[CopyOp { src_offset: 8, dst_offset: 0, len: 12 }], plan.ops());
+
+        //a full width source spread over a narrower window of a wider buffer splits per destination row
+        let plan = CopyPlan::new(&MemSpan2D::new_from_usize(0, 0, 2, 3), &Size2D::new(2, 3), &MemSpan2D::new_from_usize(1, 1, 3, 4), &Size2D::new(4, 5)).unwrap();
+        assert_eq!(&[CopyOp { src_offset: 0, dst_offset: 6, len: 3 }, CopyOp { src_offset: 3, dst_offset: 11, len: 3 }], plan.ops());
+        assert_eq!(6, plan.cell_count());
+
+        //windows on both sides copy row by row
+        let plan = CopyPlan::new(&MemSpan2D::new_from_usize(1, 1, 4, 3), &Size2D::new(5, 5), &MemSpan2D::new_from_usize(0, 2, 3, 4), &Size2D::new(3, 7)).unwrap();
+        assert_eq!(3, plan.len());
+        assert_eq!(CopyOp { src_offset: 16, dst_offset: 16, len: 2 }, plan.ops()[2]);
+
+        assert!(CopyPlan::new(&MemSpan2D::new_from_usize(0, 0, 2, 2), &Size2D::new(4, 4), &MemSpan2D::new_from_usize(0, 0, 2, 3), &Size2D::new(4, 4)).is_none());
+    }
+}
@@ -7,4 +7,22 @@ pub mod vector_math;
 pub mod memory_line;
 pub mod memory_iterators;
 pub mod mem_grid;
-pub mod size_2d;
\ No newline at end of file
+pub mod size_2d;
+pub mod atlas_allocator;
+pub mod arena_planner;
+pub mod span_allocator;
+pub mod bsp_tree;
+pub mod region_adjacency;
+pub mod memory_index3d;
+pub mod memory_offset3d;
+pub mod size_3d;
+pub mod memory_span3d;
+pub mod mem_coord;
+pub mod world_coord;
+pub mod morton;
+pub mod traversal_iterators;
+pub mod copy_plan;
+pub mod span_partition;
+pub mod transform_2d;
+pub mod wrapping_2d;
+pub mod neighborhood;
\ No newline at end of file
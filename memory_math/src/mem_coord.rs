@@ -0,0 +1,202 @@
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+///Unsigned integer usable as a coordinate of MemIndex2D, MemSpan and Size2D.
+/// Implemented for u16, u32, u64 and usize, usize is the default everywhere.
+pub trait MemCoord:
+    Copy + Eq + Ord + Hash + Debug + Display + Default
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Rem<Output = Self>
+{
+    ///Signed integer of the same width, used for offsets
+    type Signed: MemSignedCoord<Unsigned = Self>;
+
+    const ZERO: Self;
+    const ONE: Self;
+    const MAX: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    fn checked_add_signed(self, rhs: Self::Signed) -> Option<Self>;
+
+    ///Reinterpret as the signed type of the same width, like an `as` cast
+    fn as_signed(self) -> Self::Signed;
+
+    fn to_u128(self) -> u128;
+    fn from_u128(value: u128) -> Option<Self>;
+
+    ///Widen to usize like an `as` cast, for linear indexes already known to fit
+    fn as_usize(self) -> usize;
+
+    #[inline]
+    fn to_usize(self) -> Option<usize>
+    {
+        usize::try_from(self.to_u128()).ok()
+    }
+
+    #[inline]
+    fn from_usize(value: usize) -> Option<Self>
+    {
+        Self::from_u128(value as u128)
+    }
+
+    ///Convert to another coordinate width, None if the value does not fit
+    #[inline]
+    fn try_cast<J: MemCoord>(self) -> Option<J>
+    {
+        J::from_u128(self.to_u128())
+    }
+}
+
+///Signed integer usable as a component of MemOffset2D
+pub trait MemSignedCoord:
+    Copy + Eq + Ord + Hash + Debug + Display + Default
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Neg<Output = Self>
+{
+    type Unsigned: MemCoord;
+
+    const ZERO: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn is_negative(self) -> bool;
+    fn unsigned_abs(self) -> Self::Unsigned;
+
+    fn to_i128(self) -> i128;
+    fn from_i128(value: i128) -> Option<Self>;
+
+    ///Convert to another offset width, None if the value does not fit
+    #[inline]
+    fn try_cast<J: MemSignedCoord>(self) -> Option<J>
+    {
+        J::from_i128(self.to_i128())
+    }
+}
+
+macro_rules! impl_mem_coord {
+    ($unsigned:ty, $signed:ty) => {
+        impl MemCoord for $unsigned
+        {
+            type Signed = $signed;
+
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const MAX: Self = <$unsigned>::MAX;
+
+            #[inline]
+            fn checked_add(self, rhs: Self) -> Option<Self> { <$unsigned>::checked_add(self, rhs) }
+            #[inline]
+            fn checked_sub(self, rhs: Self) -> Option<Self> { <$unsigned>::checked_sub(self, rhs) }
+            #[inline]
+            fn checked_mul(self, rhs: Self) -> Option<Self> { <$unsigned>::checked_mul(self, rhs) }
+            #[inline]
+            fn wrapping_add(self, rhs: Self) -> Self { <$unsigned>::wrapping_add(self, rhs) }
+            #[inline]
+            fn wrapping_sub(self, rhs: Self) -> Self { <$unsigned>::wrapping_sub(self, rhs) }
+            #[inline]
+            fn checked_add_signed(self, rhs: $signed) -> Option<Self> { <$unsigned>::checked_add_signed(self, rhs) }
+            #[inline]
+            fn as_signed(self) -> $signed { self as $signed }
+            #[inline]
+            fn to_u128(self) -> u128 { self as u128 }
+            #[inline]
+            fn from_u128(value: u128) -> Option<Self> { <$unsigned>::try_from(value).ok() }
+            #[inline]
+            fn as_usize(self) -> usize { self as usize }
+        }
+
+        impl MemSignedCoord for $signed
+        {
+            type Unsigned = $unsigned;
+
+            const ZERO: Self = 0;
+
+            #[inline]
+            fn checked_add(self, rhs: Self) -> Option<Self> { <$signed>::checked_add(self, rhs) }
+            #[inline]
+            fn checked_sub(self, rhs: Self) -> Option<Self> { <$signed>::checked_sub(self, rhs) }
+            #[inline]
+            fn is_negative(self) -> bool { self < 0 }
+            #[inline]
+            fn unsigned_abs(self) -> $unsigned { <$signed>::unsigned_abs(self) }
+            #[inline]
+            fn to_i128(self) -> i128 { self as i128 }
+            #[inline]
+            fn from_i128(value: i128) -> Option<Self> { <$signed>::try_from(value).ok() }
+        }
+    };
+}
+
+impl_mem_coord!(u16, i16);
+impl_mem_coord!(u32, i32);
+impl_mem_coord!(u64, i64);
+impl_mem_coord!(usize, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_index2d::MemIndex2D;
+    use crate::memory_offset2d::MemOffset2D;
+    use crate::memory_span2d::MemSpan2D;
+    use crate::size_2d::Size2D;
+
+    #[test]
+    fn test_checked_width_conversion()
+    {
+        assert_eq!(Some(300u16), 300usize.try_cast::<u16>());
+        assert_eq!(None, 70_000u32.try_cast::<u16>());
+        assert_eq!(Some(u32::MAX as u64), u32::MAX.try_cast::<u64>());
+        assert_eq!(None, u64::MAX.try_cast::<u32>());
+
+        assert_eq!(Some(-5i16), (-5isize).try_cast::<i16>());
+        assert_eq!(None, (i32::MIN).try_cast::<i16>());
+        assert_eq!(7u16, (-7i16).unsigned_abs());
+    }
+
+    #[test]
+    fn test_narrow_coordinate_types()
+    {
+        let size: Size2D<u16> = Size2D::from_counts(200, 300);
+        let index2d: MemIndex2D<u16> = MemIndex2D::new(199, 299);
+        assert_eq!(Some(59_999), size.index2d_to_index(index2d));
+        assert_eq!(Some(index2d), size.index_to_index2d(59_999));
+
+        let moved = (index2d + MemOffset2D::new(-10i16, 1)).unwrap();
+        assert_eq!(MemIndex2D::new(189u16, 300), moved);
+        assert!(Some(MemOffset2D::new(10i16, -1)) == index2d - moved);
+        assert!((MemIndex2D::new(u16::MAX, 0) - MemIndex2D::new(0u16, 0)).is_none());
+        assert!(Some(MemOffset2D::new(i16::MIN, 0)) == MemIndex2D::new(0u16, 0) - MemIndex2D::new(32_768u16, 0));
+        assert!((MemIndex2D::new(u16::MAX, 0) + MemOffset2D::row_offset(1i16)).is_none());
+
+        let span: MemSpan2D<u32> = MemSpan2D::from_bounds(10, 10, 20, 30);
+        let shifted = (span.clone() + MemOffset2D::new(-10i32, 5)).unwrap();
+        assert_eq!(MemIndex2D::new(0u32, 15), shifted.min_absolute_index2d());
+        assert_eq!(Some(200), shifted.area());
+
+        let wide: MemSpan2D<u64> = span.try_cast().unwrap();
+        assert_eq!(Some(MemIndex2D::new(19u64, 29)), wide.max_absolute_index2d());
+        assert!(MemSpan2D::<u64>::from_bounds(0, 0, 1, u32::MAX as u64 + 1).try_cast::<u32>().is_none());
+        assert!(Size2D::new(70_000usize, 1).try_cast::<u16>().is_none());
+    }
+
+    #[test]
+    fn test_narrow_area_and_index_do_not_overflow()
+    {
+        let size: Size2D<u16> = Size2D::from_counts(4096, 4096);
+        assert_eq!(Some(16_777_216), size.area());
+        assert_eq!(Some(16_777_215), size.max_index());
+
+        let last: MemIndex2D<u16> = MemIndex2D::new(4095, 4095);
+        assert_eq!(Some(16_777_215), size.index2d_to_index(last));
+        assert_eq!(16_777_215, Size2D::index2d_to_index_unchecked(size.column_count, last));
+        assert_eq!(Some(last), size.index_to_index2d(16_777_215));
+        assert_eq!(None, size.index_to_index2d(16_777_216));
+
+        let full: Size2D<u16> = Size2D::from_counts(u16::MAX, u16::MAX);
+        assert_eq!(Some(65_535 * 65_535), full.area());
+        assert_eq!(Some(65_535 * 65_535), MemSpan2D::<u16>::from_row_columns(u16::MAX, u16::MAX).area());
+    }
+}
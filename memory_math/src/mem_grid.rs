@@ -269,11 +269,11 @@ impl MemoryGrid for NonUniformMemGrid2D
 
         if grid_row_index >= self.grid_row_count()
         {
-            return None;
+            None
         }
         else if grid_row_index == 0
         {
-            return Some(0);
+            Some(0)
         }
         else
         {
@@ -298,15 +298,15 @@ impl MemoryGrid for NonUniformMemGrid2D
     fn grid_column_index_to_column_index(&self, grid_column_index: usize) -> Option<usize> {
         if grid_column_index >= self.grid_column_count()
         {
-            return None;
+            None
         }
         else if grid_column_index == 0
         {
-            return Some(0);
+            Some(0)
         }
         else
         {
-            Some(self.row_offsets[grid_column_index - 1])
+            Some(self.column_offsets[grid_column_index - 1])
         }
     }
 
@@ -332,7 +332,7 @@ impl NonUniformMemGrid2D
         }
     }
 
-    fn index_search(indexes: &Vec<usize>, search_for: usize) -> usize
+    fn index_search(indexes: &[usize], search_for: usize) -> usize
     {
         match indexes.binary_search(&search_for) {
             Ok(index) => index + 1,
@@ -340,34 +340,6 @@ impl NonUniformMemGrid2D
         }
     }
 
-    //binary search on row offsets to find the tile row which
-    //contains the current row index
-    fn row_index_to_tile_row_index(&self, cell_row_index: usize) -> Option<usize>
-    {
-        if cell_row_index >= self.size.row_count()
-        {
-            return None;
-        }
-
-        Some(Self::index_search(
-            &self.row_offsets,
-            cell_row_index
-        ))
-    }
-
-    fn column_index_to_tile_column_index(&self, cell_column_index: usize) -> Option<usize>
-    {
-        if cell_column_index >= self.size.column_count()
-        {
-            return None;
-        }
-
-        Some(Self::index_search(
-            &self.column_offsets,
-            cell_column_index
-        ))
-    }
-
 }
 
 #[cfg(test)]
@@ -470,6 +442,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(non_snake_case)]
     fn test_non_uniform_mem_grid2d()
     {
         let size = Size2D::new(10, 10);
@@ -505,6 +478,21 @@ mod tests {
         assert_eq!(expected_span2D, actual_span2D);
     }
 
+    #[test]
+    fn test_non_uniform_column_offsets()
+    {
+        //rows and columns are cut at different places, so mixing them up shows
+        let grid = NonUniformMemGrid2D::new(Size2D::new(10, 10), vec![3], vec![6]);
+
+        assert_eq!(Some(3), grid.grid_row_index_to_row_index(1));
+        assert_eq!(Some(6), grid.grid_column_index_to_column_index(1));
+
+        let grid_index = grid.index2d_to_grid_index(&MemIndex2D::new(4, 7)).unwrap();
+        assert_eq!(MemIndex2D::new(1, 1), grid_index.0);
+        assert_eq!(MemIndex2D::new(1, 1), grid.index2d_relative_to_grid(&MemIndex2D::new(4, 7), &grid_index).unwrap());
+        assert_eq!(MemIndex2D::new(3, 6), grid.grid_index_to_index2d(&grid_index).unwrap());
+    }
+
     #[test]
     fn test_grid_intersections_partial_overlap() {
         let size: Size2D = Size2D::new(30,30);
@@ -3,85 +3,92 @@ use std::ops::Sub;
 use std::{fmt, ops::Add};
 use std::cmp::Ordering;
 use super::memory_offset2d::MemOffset2D;
+use super::mem_coord::{MemCoord, MemSignedCoord};
 
 
 //TODO: Write lexicographic comparer
 #[derive(Copy, Clone)]
-pub struct MemIndex2D
+pub struct MemIndex2D<I = usize>
 {
-    pub row: usize,
-    pub col: usize
+    pub row: I,
+    pub col: I
 }
 
-impl fmt::Display for MemIndex2D
+impl<I: MemCoord> fmt::Display for MemIndex2D<I>
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result{
         write!(f, "({}, {})", self.row, self.col)
     }
 }
 
-impl fmt::Debug for MemIndex2D
+impl<I: MemCoord> fmt::Debug for MemIndex2D<I>
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("MemIndex2D").field("row", &self.row).field("col", &self.col).finish()
     }
 }
 
-impl PartialEq for MemIndex2D{
+impl<I: MemCoord> PartialEq for MemIndex2D<I>{
     fn eq(&self, other: &Self) -> bool {
         self.row == other.row && self.col == other.col
     }
 }
 
-impl Eq for MemIndex2D{}
+impl<I: MemCoord> Eq for MemIndex2D<I>{}
 
-impl PartialOrd for MemIndex2D{
+impl<I: MemCoord> PartialOrd for MemIndex2D<I>{
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<I: MemCoord> Ord for MemIndex2D<I>{
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
 
         let col_compare = self.col.cmp(&other.col);
         if col_compare != Ordering::Equal
         {
-            return Some(col_compare);
+            return col_compare;
         }
         
-        return self.row.partial_cmp(&other.row);
+        self.row.cmp(&other.row)
     }
 }
 
-impl Ord for MemIndex2D{
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
-    }
-}
-
-impl TryFrom<MemOffset2D> for MemIndex2D
+impl<I: MemCoord> TryFrom<MemOffset2D<I::Signed>> for MemIndex2D<I>
 {
     type Error = &'static str;
 
-    fn try_from(value: MemOffset2D) -> Result<Self, Self::Error> {
+    fn try_from(value: MemOffset2D<I::Signed>) -> Result<Self, Self::Error> {
 
-        if value.row < 0 || value.col < 0
+        if value.row.is_negative() || value.col.is_negative()
         {
             return Err("MemOffset2D must be positive");
         }
 
-        Ok(MemIndex2D::new(value.row as usize, value.col as usize))
+        Ok(MemIndex2D::new(value.row.unsigned_abs(), value.col.unsigned_abs()))
     }
 }
 
-impl Sub for MemIndex2D{
-    type Output = MemOffset2D;
+///Difference of two unsigned coordinates, None if it does not fit in the signed type of the same width
+fn signed_difference<I: MemCoord>(lhs: I, rhs: I) -> Option<I::Signed>
+{
+    I::Signed::from_i128((lhs.to_u128() as i128).checked_sub(rhs.to_u128() as i128)?)
+}
+
+impl<I: MemCoord> Sub for MemIndex2D<I>{
+    type Output = Option<MemOffset2D<I::Signed>>;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        let row: isize = self.row as isize - rhs.row as isize;
-        let col: isize = self.col as isize - rhs.col as isize;
+        let row: I::Signed = signed_difference(self.row, rhs.row)?;
+        let col: I::Signed = signed_difference(self.col, rhs.col)?;
 
-        MemOffset2D::new(row, col)
+        Some(MemOffset2D::new(row, col))
     }
 }
 
 
-impl TryFrom<(f32, f32)> for MemIndex2D
+impl<I: MemCoord + TryFrom<i32>> TryFrom<(f32, f32)> for MemIndex2D<I>
 {
     type Error = String;
 
@@ -93,7 +100,7 @@ impl TryFrom<(f32, f32)> for MemIndex2D
             return Err(format!("row value: {} is not a number", row));
         }
 
-        let row_val: usize = match usize::try_from(row.round() as i32) {
+        let row_val: I = match I::try_from(row.round() as i32) {
             Ok(v) => v,
             Err(_) => {return Err(format!("row is not a usize: {}", row));}
         };
@@ -103,7 +110,7 @@ impl TryFrom<(f32, f32)> for MemIndex2D
             return Err(format!("col value: {} is not a number", col));
         }
 
-        let col_val: usize = match usize::try_from(col.round() as i32)
+        let col_val: I = match I::try_from(col.round() as i32)
         {
             Ok(c) => c,
             Err(_) => {return Err(format!("col is not a usize: {}", col));}
@@ -114,7 +121,7 @@ impl TryFrom<(f32, f32)> for MemIndex2D
     }
 }
 
-impl TryFrom<(i32, i32)> for MemIndex2D
+impl<I: MemCoord + TryFrom<i32, Error = TryFromIntError>> TryFrom<(i32, i32)> for MemIndex2D<I>
 {
     type Error = TryFromIntError;
 
@@ -122,7 +129,7 @@ impl TryFrom<(i32, i32)> for MemIndex2D
         
         let (row, col) = value;
         
-        let row_val = match usize::try_from(row)
+        let row_val = match I::try_from(row)
         {
             Ok(r) => r,
             Err(try_err) => 
@@ -131,7 +138,7 @@ impl TryFrom<(i32, i32)> for MemIndex2D
             }
         };
 
-        let col_val = match usize::try_from(col)
+        let col_val = match I::try_from(col)
         {
             Ok(c) => c,
             Err(try_err) => 
@@ -151,13 +158,13 @@ impl TryFrom<(i32, i32)> for MemIndex2D
 /// This is possible only if the OffsetVect2D lies entriely within the first quadrant,
 /// which then proceeds to act like addition with a MemVect2D.
 /// return None if impossible.
-impl Add<MemOffset2D> for MemIndex2D{
-    type Output = Option<MemIndex2D>;
+impl<I: MemCoord> Add<MemOffset2D<I::Signed>> for MemIndex2D<I>{
+    type Output = Option<MemIndex2D<I>>;
 
-    fn add(self, rhs: MemOffset2D) -> Self::Output {
+    fn add(self, rhs: MemOffset2D<I::Signed>) -> Self::Output {
 
-        let row: usize = self.row.checked_add_signed(rhs.row)?;
-        let col: usize = self.col.checked_add_signed(rhs.col)?;
+        let row: I = self.row.checked_add_signed(rhs.row)?;
+        let col: I = self.col.checked_add_signed(rhs.col)?;
 
         Some(MemIndex2D{
             row,
@@ -167,23 +174,29 @@ impl Add<MemOffset2D> for MemIndex2D{
 }
 
 
-impl Sub<MemOffset2D> for MemIndex2D
+impl<I: MemCoord> Sub<MemOffset2D<I::Signed>> for MemIndex2D<I>
 {
-    type Output = Option<MemIndex2D>;
+    type Output = Option<MemIndex2D<I>>;
 
-    fn sub(self, rhs: MemOffset2D) -> Self::Output {
+    fn sub(self, rhs: MemOffset2D<I::Signed>) -> Self::Output {
         let flipped = -rhs;
         self + flipped
     }
 }
 
-impl MemIndex2D
+impl<I: MemCoord> MemIndex2D<I>
 {
-    pub fn new(row: usize, col: usize) -> Self{
+    pub fn new(row: I, col: I) -> Self{
         MemIndex2D { row, col}
     }
 
     pub fn origin() -> Self{
-        MemIndex2D { row: 0, col: 0 }
+        MemIndex2D { row: I::ZERO, col: I::ZERO }
+    }
+
+    ///Convert to another coordinate width, None if either coordinate does not fit
+    pub fn try_cast<J: MemCoord>(&self) -> Option<MemIndex2D<J>>
+    {
+        Some(MemIndex2D::new(self.row.try_cast()?, self.col.try_cast()?))
     }
 }
\ No newline at end of file
@@ -0,0 +1,96 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+use super::memory_index2d::MemIndex2D;
+use super::memory_offset3d::MemOffset3D;
+
+///Index of a cell in a stack of 2d planes, layer is the slowest moving axis
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MemIndex3D
+{
+    pub layer: usize,
+    pub row: usize,
+    pub col: usize
+}
+
+impl fmt::Display for MemIndex3D
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.layer, self.row, self.col)
+    }
+}
+
+impl TryFrom<MemOffset3D> for MemIndex3D
+{
+    type Error = &'static str;
+
+    fn try_from(value: MemOffset3D) -> Result<Self, Self::Error> {
+        if value.layer < 0 || value.row < 0 || value.col < 0
+        {
+            return Err("MemOffset3D must be positive");
+        }
+
+        Ok(MemIndex3D::new(value.layer as usize, value.row as usize, value.col as usize))
+    }
+}
+
+impl Sub for MemIndex3D
+{
+    type Output = MemOffset3D;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        MemOffset3D::new(
+            self.layer as isize - rhs.layer as isize,
+            self.row as isize - rhs.row as isize,
+            self.col as isize - rhs.col as isize
+        )
+    }
+}
+
+///Add an offset to an index, None if the result would leave the first octant
+impl Add<MemOffset3D> for MemIndex3D
+{
+    type Output = Option<MemIndex3D>;
+
+    fn add(self, rhs: MemOffset3D) -> Self::Output {
+        Some(MemIndex3D
+        {
+            layer: self.layer.checked_add_signed(rhs.layer)?,
+            row: self.row.checked_add_signed(rhs.row)?,
+            col: self.col.checked_add_signed(rhs.col)?
+        })
+    }
+}
+
+impl Sub<MemOffset3D> for MemIndex3D
+{
+    type Output = Option<MemIndex3D>;
+
+    fn sub(self, rhs: MemOffset3D) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl MemIndex3D
+{
+    pub fn new(layer: usize, row: usize, col: usize) -> Self
+    {
+        MemIndex3D { layer, row, col }
+    }
+
+    pub fn origin() -> Self
+    {
+        MemIndex3D { layer: 0, row: 0, col: 0 }
+    }
+
+    pub fn from_index2d(layer: usize, index2d: MemIndex2D) -> Self
+    {
+        MemIndex3D { layer, row: index2d.row, col: index2d.col }
+    }
+
+    ///Position of the index inside its layer
+    #[inline]
+    pub fn index2d(&self) -> MemIndex2D
+    {
+        MemIndex2D::new(self.row, self.col)
+    }
+}
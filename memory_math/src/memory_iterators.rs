@@ -1,282 +1,473 @@
-use std::ops::Range;
-use super::{memory_span2d::{MemSpan2D, HasMemSpan2D}, memory_index2d::MemIndex2D, memory_range_iter::HasCurMemIndex};
+use super::{memory_span2d::MemSpan2D, memory_index2d::MemIndex2D, memory_range_iter::HasCurMemIndex};
+use super::size_2d::{HasSize2D, Size2D};
 
-pub trait MemoryIterator : Iterator<Item=MemIndex2D>
+///A traversal over the cells of a MemSpan2D.
+/// Positions along the traversal map to absolute indexes and back, so it can be
+/// walked from both ends and seeked in constant time.
+pub trait MemoryIterator : ExactSizeIterator<Item=MemIndex2D> + DoubleEndedIterator
 {
+    fn extents(&self) -> &MemSpan2D;
+
+    ///The index most recently returned from either end, None before the first one
     fn current_index(&self) -> Option<MemIndex2D>;
-}
 
-pub struct ClockwiseCornerIterator
-{
-    extents: MemSpan2D,
-    corner: u8
+    ///Position of an absolute index along the whole traversal, None if it is not visited
+    fn rank(&self, index2d: MemIndex2D) -> Option<usize>;
+
+    ///Absolute index at a position along the whole traversal, regardless of how far it has been consumed
+    fn unrank(&self, position: usize) -> Option<MemIndex2D>;
 }
 
-impl Iterator for ClockwiseCornerIterator
+///A visiting order over the cells of a rectangle of a given size.
+/// Orders compute the cell at any position directly, so iterators only keep counters.
+pub trait TraversalOrder
 {
-    type Item = MemIndex2D;
+    fn size(&self) -> Size2D;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let current = self.current_index();
-        self.corner += 1;
-        current
+    ///Index relative to the rectangle origin of the cell visited at position
+    fn unrank(&self, position: usize) -> Option<MemIndex2D>;
+
+    ///Position of an index relative to the rectangle origin
+    fn rank(&self, index2d: MemIndex2D) -> Option<usize>;
+
+    ///Number of visited cells, every cell of the rectangle unless overridden.
+    /// Saturates for rectangles whose area does not fit in usize, those can not be walked to the end anyway
+    #[inline]
+    fn len(&self) -> usize
+    {
+        self.size().area().unwrap_or(usize::MAX)
     }
-}
 
-impl MemoryIterator for ClockwiseCornerIterator
-{
-    fn current_index(&self) -> Option<MemIndex2D>
+    #[inline]
+    fn is_empty(&self) -> bool
     {
-        match self.corner
-        {
-            0 => Some(self.extents.min_absolute_index2d()),
-            1 => Some(MemIndex2D::new(self.extents.min_row(), self.extents.max_column()?)),
-            2 => Some(self.extents.max_absolute_index2d()?),
-            3 => Some(MemIndex2D::new(self.extents.max_row()?, self.extents.max_column()?)),
-            _ => None
-        }
+        self.len() == 0
     }
 }
 
-pub struct LinearMemoryIterator
+///MemoryIterator visiting the cells of extents in the order O, indexes are absolute
+#[derive(Clone)]
+pub struct TraversalIterator<O>
 {
     extents: MemSpan2D,
-    current_index: MemIndex2D
+    order: O,
+    front: usize,
+    back: usize,
+    current: Option<MemIndex2D>
 }
 
-impl MemoryIterator for LinearMemoryIterator
+impl<O: TraversalOrder> TraversalIterator<O>
 {
-    fn current_index(&self) -> Option<MemIndex2D>
+    ///None if the order was built for a different size than extents
+    pub fn with_order(extents: MemSpan2D, order: O) -> Option<Self>
     {
-        if self.current_index.row > self.extents.max_row()?
+        if order.size() != extents.size()
         {
             return None;
         }
 
-        Some(self.current_index)
+        let back: usize = order.len();
+        Some(TraversalIterator { extents, order, front: 0, back, current: None })
+    }
+
+    pub fn order(&self) -> &O
+    {
+        &self.order
+    }
+
+    ///Position of the next index returned from the front
+    pub fn position(&self) -> usize
+    {
+        self.front
+    }
+
+    fn absolute(&self, position: usize) -> Option<MemIndex2D>
+    {
+        let relative: MemIndex2D = self.order.unrank(position)?;
+        self.extents.relative_index2d_to_absolute_index2d(relative)
     }
 }
 
-impl Iterator for LinearMemoryIterator
+impl<O: TraversalOrder> Iterator for TraversalIterator<O>
 {
     type Item = MemIndex2D;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let next_col: usize = self.next_column();
-        let next_row: usize = if next_col == 0 { self.next_row()? } else { self.current_index.row };
+        if self.front >= self.back
+        {
+            return None;
+        }
 
-        self.current_index = MemIndex2D::new(next_row, next_col);
-        Some(self.current_index)
+        let index2d: MemIndex2D = self.absolute(self.front)?;
+        self.front += 1;
+        self.current = Some(index2d);
+        Some(index2d)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining: usize = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.front = self.front.saturating_add(n).min(self.back);
+        self.next()
     }
 }
 
-impl DoubleEndedIterator for LinearMemoryIterator {
+impl<O: TraversalOrder> DoubleEndedIterator for TraversalIterator<O>
+{
     fn next_back(&mut self) -> Option<Self::Item> {
-        let next_col = self.prev_column()?;
-        let next_row: usize;
-
-        if next_col == self.extents.max_column()? {
-            next_row = self.current_index.row.checked_sub(1)?;
-            if next_row < self.extents.min_row() {
-                return None;
-            }
-        }
-        else {
-            next_row = self.current_index.row;
+        if self.front >= self.back
+        {
+            return None;
         }
 
-        self.current_index = MemIndex2D::new(next_row, next_col);
-        Some(self.current_index)
+        let index2d: MemIndex2D = self.absolute(self.back - 1)?;
+        self.back -= 1;
+        self.current = Some(index2d);
+        Some(index2d)
     }
-}
 
-impl HasCurMemIndex for LinearMemoryIterator
-{
-    fn get_cur_mem_index(&self) -> MemIndex2D {
-        self.current_index
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.back = self.back.saturating_sub(n).max(self.front);
+        self.next_back()
     }
 }
 
-impl From<MemSpan2D> for LinearMemoryIterator
+impl<O: TraversalOrder> ExactSizeIterator for TraversalIterator<O> {}
+
+impl<O: TraversalOrder> MemoryIterator for TraversalIterator<O>
 {
-    fn from(extents: MemSpan2D) -> Self {
-        LinearMemoryIterator::new(extents)
+    fn extents(&self) -> &MemSpan2D
+    {
+        &self.extents
     }
-}
 
-impl LinearMemoryIterator
-{
-    pub fn new(extents: MemSpan2D) -> Self
+    fn current_index(&self) -> Option<MemIndex2D>
     {
-        LinearMemoryIterator
-        {
-            extents,
-            current_index: MemIndex2D::new(0, 0)
-        }
+        self.current
     }
 
-    ///TODO: Modify so it exhibits the variable++ behaviour
-    ///check THEN assign
-    fn next_column(&mut self) -> usize
+    fn rank(&self, index2d: MemIndex2D) -> Option<usize>
     {
-        let col: usize = self.current_index.col;
-        self.current_index.col += 1;
-        if col > self.extents.max_column().unwrap()
+        if !self.extents.contains_index2d(&index2d)
         {
-            self.current_index.col = 0;
-            return 0;
+            return None;
         }
 
-        col
+        let relative = MemIndex2D::new(index2d.row - self.extents.min_row(), index2d.col - self.extents.min_column());
+        self.order.rank(relative)
     }
 
-    fn prev_column(&mut self) -> Option<usize>
+    fn unrank(&self, position: usize) -> Option<MemIndex2D>
     {
-        let col: usize = self.current_index.col;
-        let mut set: bool = false;
+        self.absolute(position)
+    }
+}
+
+impl<O: TraversalOrder> HasCurMemIndex for TraversalIterator<O>
+{
+    fn get_cur_mem_index(&self) -> MemIndex2D {
+        self.current.unwrap_or(self.extents.min_absolute_index2d())
+    }
+}
+
+///Generates `new(extents)` and `From<MemSpan2D>` for iterators whose order only depends on the size.
+/// With `fallible` the order may not cover every size, so `new` returns None instead and there is no `From`
+macro_rules! sized_traversal_iterator {
+    ($alias:ident, $order:ident, fallible) => {
+        pub type $alias = TraversalIterator<$order>;
 
-        //slightly more complicated here, treat (None) as < min column
-        if let Some(c) = self.current_index.col.checked_sub(1)
+        impl TraversalIterator<$order>
         {
-            if c >= self.extents.min_column()
+            ///None if the order cannot be built for the size of extents
+            pub fn new(extents: MemSpan2D) -> Option<Self>
             {
-                set = true;
-                self.current_index.col = c;
+                let order = $order::new(extents.size());
+                TraversalIterator::with_order(extents, order)
             }
         }
+    };
+    ($alias:ident, $order:ident) => {
+        pub type $alias = TraversalIterator<$order>;
 
-        if !set
+        impl TraversalIterator<$order>
         {
-            self.current_index.col = self.extents.max_column()?;
+            pub fn new(extents: MemSpan2D) -> Self
+            {
+                let order = $order::new(extents.size());
+                TraversalIterator::with_order(extents, order).unwrap()
+            }
         }
 
-        Some(col)
-    }
-
-    fn next_row(&mut self) -> Option<usize>
-    {
-        let row: usize = self.current_index.row;
-        self.current_index.row += 1;
-        if row > self.extents.max_row()?
+        impl From<MemSpan2D> for TraversalIterator<$order>
         {
-            return None;
+            fn from(extents: MemSpan2D) -> Self {
+                TraversalIterator::<$order>::new(extents)
+            }
         }
+    };
+}
 
-        Some(row)
-    }
+pub(crate) use sized_traversal_iterator;
+
+sized_traversal_iterator!(LinearMemoryIterator, RowMajorOrder);
+sized_traversal_iterator!(BoustrophedonIterator, BoustrophedonOrder);
+sized_traversal_iterator!(ClockwiseCornerIterator, ClockwiseCornerOrder);
+
+///Left to right along each row, rows top to bottom
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowMajorOrder
+{
+    size: Size2D
 }
 
-///"as the ox plows" start (0,0) then read to the end of the row
-/// then move to next row, and read the columns backwards and so on.
-/// TODO: We could make this more efficient by removing the number of
-/// unwrap() checks we have to do.
-pub struct BoustrophedonIterator
+impl RowMajorOrder
 {
-    range2d: MemSpan2D,
-    current_index: MemIndex2D
+    pub fn new(size: Size2D) -> Self
+    {
+        RowMajorOrder { size }
+    }
 }
 
-impl Iterator for BoustrophedonIterator
+impl TraversalOrder for RowMajorOrder
 {
-    type Item = MemIndex2D;
+    fn size(&self) -> Size2D
+    {
+        self.size
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some(c) = self.next_column()
-        {
-            return Some(MemIndex2D::new(self.current_index.row, c));
-        }
-        let row: usize = self.next_row()?;
-        let col: usize = self.range2d.max_column()?;
-        Some(MemIndex2D::new(row, col))
+    fn unrank(&self, position: usize) -> Option<MemIndex2D>
+    {
+        self.size.index_to_index2d(position)
+    }
+
+    fn rank(&self, index2d: MemIndex2D) -> Option<usize>
+    {
+        self.size.index2d_to_index(index2d)
     }
 }
 
-impl DoubleEndedIterator for BoustrophedonIterator
+///"as the ox plows" start at the top left then read to the end of the row,
+/// then move to the next row and read the columns backwards and so on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoustrophedonOrder
 {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        if let Some(c) = self.prev_column()
-        {
-            return Some(MemIndex2D::new(self.current_index.row, c));
-        }
-        let row: usize = self.next_row()?;
-        let col: usize = self.range2d.min_column();
-        Some(MemIndex2D::new(row, col))
+    size: Size2D
+}
+
+impl BoustrophedonOrder
+{
+    pub fn new(size: Size2D) -> Self
+    {
+        BoustrophedonOrder { size }
+    }
+
+    ///Odd rows run backwards, so the column mapping is its own inverse
+    #[inline]
+    fn plowed_column(&self, row: usize, col: usize) -> usize
+    {
+        if row.is_multiple_of(2) { col } else { self.size.column_count - 1 - col }
     }
 }
 
-impl BoustrophedonIterator
+impl TraversalOrder for BoustrophedonOrder
 {
+    fn size(&self) -> Size2D
+    {
+        self.size
+    }
 
-    fn next_row(&self) -> Option<usize>
+    fn unrank(&self, position: usize) -> Option<MemIndex2D>
     {
-        let row: usize = self.current_index.row.checked_add(1)?;
+        let linear: MemIndex2D = self.size.index_to_index2d(position)?;
+        Some(MemIndex2D::new(linear.row, self.plowed_column(linear.row, linear.col)))
+    }
 
-        if row >= self.range2d.max_row()?
+    fn rank(&self, index2d: MemIndex2D) -> Option<usize>
+    {
+        if !self.size.index2d_in_bounds(&index2d)
         {
             return None;
         }
 
-        Some(row)
+        self.size.index2d_to_index(MemIndex2D::new(index2d.row, self.plowed_column(index2d.row, index2d.col)))
     }
+}
 
+///The distinct corners of the rectangle clockwise from the top left,
+/// a single row or column has two corners and a single cell one
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClockwiseCornerOrder
+{
+    size: Size2D,
+    corners: Vec<MemIndex2D>
+}
 
-    fn prev_row(&self) -> Option<usize>
+impl ClockwiseCornerOrder
+{
+    pub fn new(size: Size2D) -> Self
     {
-        let row: usize = self.current_index.row.checked_sub(1)?;
+        let mut corners: Vec<MemIndex2D> = Vec::with_capacity(4);
 
-        if row < self.range2d.min_row()
+        if let Some(max) = size.max_index2d()
         {
-            return None;
+            for corner in [MemIndex2D::origin(), MemIndex2D::new(0, max.col), max, MemIndex2D::new(max.row, 0)]
+            {
+                if !corners.contains(&corner)
+                {
+                    corners.push(corner);
+                }
+            }
         }
 
-        Some(row)
+        ClockwiseCornerOrder { size, corners }
     }
+}
 
-    fn prev_column(&self) -> Option<usize>
+impl TraversalOrder for ClockwiseCornerOrder
+{
+    fn size(&self) -> Size2D
     {
-        let col: usize;
-        if self.current_index.row % 2 == 0
-        {
-            col = self.current_index.col.checked_sub(1)?;
-        }
-        else {
-            col = self.current_index.col.checked_add(1)?;
-        }
+        self.size
+    }
 
-        if col >= self.range2d.max_column()?
-        {
-            return None;
-        }
-        else if col < self.range2d.min_column()
-        {
-            return None;
-        }
+    fn unrank(&self, position: usize) -> Option<MemIndex2D>
+    {
+        self.corners.get(position).copied()
+    }
+
+    fn rank(&self, index2d: MemIndex2D) -> Option<usize>
+    {
+        self.corners.iter().position(|corner| *corner == index2d)
+    }
 
-        Some(col)
+    fn len(&self) -> usize
+    {
+        self.corners.len()
     }
+}
 
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::memory_range_iter::IterateWithMemIndex;
 
-    fn next_column(&self) -> Option<usize>
+    ///Spans every MemoryIterator is checked against, including a single cell, a single row and column and non-origin extents
+    pub(crate) fn conformance_spans() -> Vec<MemSpan2D>
     {
-        let col: usize;
-        if self.current_index.row % 2 == 0
-        {
-            col = self.current_index.col.checked_add(1)?;
-        }
-        else {
-            col = self.current_index.col.checked_sub(1)?;
-        }
+        vec![
+            MemSpan2D::new_from_usize(0, 0, 1, 1),
+            MemSpan2D::new_from_usize(0, 0, 4, 4),
+            MemSpan2D::new_from_usize(3, 5, 4, 12),
+            MemSpan2D::new_from_usize(2, 7, 11, 8),
+            MemSpan2D::new_from_usize(10, 1, 17, 12),
+            MemSpan2D::new_from_usize(4, 4, 20, 20),
+            MemSpan2D::new_from_usize(1, 2, 6, 40),
+        ]
+    }
 
-        if col > self.range2d.max_column()?
-        {
-            return None;
-        }
-        else if col < self.range2d.min_column()
+    ///The contract every MemoryIterator must honour, make builds a fresh iterator over a span
+    pub(crate) fn assert_memory_iterator_conformance<I, F>(make: F)
+    where
+        I: MemoryIterator,
+        F: Fn(MemSpan2D) -> I
+    {
+        for span in conformance_spans()
         {
-            return None;
+            let iter = make(span.clone());
+            assert_eq!(&span, iter.extents());
+            assert_eq!(None, iter.current_index(), "span {}", span);
+
+            let len: usize = iter.len();
+            let forward: Vec<MemIndex2D> = iter.collect();
+            assert_eq!(len, forward.len(), "span {}", span);
+
+            //distinct indexes inside the extents
+            let mut sorted = forward.clone();
+            sorted.sort_by_key(|i| (i.row, i.col));
+            sorted.dedup();
+            assert_eq!(len, sorted.len(), "span {}", span);
+            assert!(forward.iter().all(|i| span.contains_index2d(i)), "span {}", span);
+
+            //rank and unrank agree with the walk and with each other
+            let iter = make(span.clone());
+            for (position, index2d) in forward.iter().enumerate()
+            {
+                assert_eq!(Some(*index2d), iter.unrank(position), "span {} position {}", span, position);
+                assert_eq!(Some(position), iter.rank(*index2d), "span {} index {}", span, index2d);
+            }
+            assert_eq!(None, iter.unrank(len));
+            assert_eq!(None, iter.rank(MemIndex2D::new(span.max_row().unwrap() + 1, span.min_column())));
+            assert_eq!(None, iter.rank(MemIndex2D::new(span.min_row(), span.max_column().unwrap() + 1)));
+
+            //walking backwards is the reversed walk
+            let backward: Vec<MemIndex2D> = make(span.clone()).rev().collect();
+            assert!(backward.iter().eq(forward.iter().rev()), "span {}", span);
+
+            //nth seeks from the current position and leaves the cursor after the result
+            for step in [0, 1, 3, len / 2, len]
+            {
+                let mut iter = make(span.clone());
+                assert_eq!(forward.get(step).copied(), iter.nth(step), "span {} nth {}", span, step);
+                assert_eq!(len.saturating_sub(step + 1), iter.len(), "span {} nth {}", span, step);
+                assert_eq!(forward.get(step + 1).copied(), iter.next(), "span {} nth {}", span, step);
+
+                let mut iter = make(span.clone());
+                assert_eq!(forward.iter().rev().nth(step).copied(), iter.nth_back(step), "span {} nth_back {}", span, step);
+            }
+
+            //both ends meet without overlapping, two steps from the front for every one from the back
+            let mut iter = make(span.clone());
+            let mut front: Vec<MemIndex2D> = Vec::new();
+            let mut back: Vec<MemIndex2D> = Vec::new();
+            for step in 0..=len
+            {
+                let from_back: bool = step % 3 == 2;
+                let remaining: usize = iter.len();
+                let taken: Option<MemIndex2D> = if from_back { iter.next_back() } else { iter.next() };
+                let Some(index2d) = taken else {
+                    assert_eq!(0, remaining, "span {}", span);
+                    break;
+                };
+
+                assert_eq!(Some(index2d), iter.current_index(), "span {}", span);
+                assert_eq!(remaining - 1, iter.len(), "span {}", span);
+                if from_back { back.push(index2d); } else { front.push(index2d); }
+            }
+            back.reverse();
+            front.extend(back);
+            assert_eq!(forward, front, "span {}", span);
+            assert_eq!(None, iter.next());
+            assert_eq!(None, iter.next_back());
         }
+    }
 
+    #[test]
+    fn test_builtin_iterators_conform()
+    {
+        assert_memory_iterator_conformance(LinearMemoryIterator::new);
+        assert_memory_iterator_conformance(BoustrophedonIterator::new);
+        assert_memory_iterator_conformance(ClockwiseCornerIterator::new);
+    }
+
+    #[test]
+    fn test_builtin_orders()
+    {
+        let span = MemSpan2D::new_from_usize(2, 3, 4, 6);
+        let relative = |iter: &mut dyn Iterator<Item=MemIndex2D>| iter.map(|i| (i.row - 2, i.col - 3)).collect::<Vec<_>>();
 
-        Some(col)
+        assert_eq!(vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)], relative(&mut LinearMemoryIterator::new(span.clone())));
+        assert_eq!(vec![(0, 0), (0, 1), (0, 2), (1, 2), (1, 1), (1, 0)], relative(&mut BoustrophedonIterator::new(span.clone())));
+        assert_eq!(vec![(0, 0), (0, 2), (1, 2), (1, 0)], relative(&mut ClockwiseCornerIterator::new(span)));
+        assert_eq!(2, ClockwiseCornerIterator::new(MemSpan2D::new_from_usize(0, 0, 1, 5)).len());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_iterate_with_mem_index()
+    {
+        let span = MemSpan2D::new_from_usize(4, 4, 6, 7);
+        assert!(LinearMemoryIterator::new(span).iterate_with_mem_index().all(|(index2d, visited)| index2d == visited));
+    }
+}
@@ -20,7 +20,7 @@ impl MemLine2D
             return MemLine2D::new(self.max, self.min);
         }
 
-        return self.clone();
+        *self
     }
 
     fn plot_line_high(min: MemIndex2D, max: MemIndex2D) -> Vec<MemIndex2D>
@@ -47,16 +47,16 @@ impl MemLine2D
             {
                 if xi >= 0
                 {
-                    x = x + xi as usize;
+                    x += xi as usize;
                 }
                 else {
                     x = x.wrapping_sub((-xi) as usize);
                 }
-                d = d + (2 * (dx - dy));
+                d += 2 * (dx - dy);
             }
             else
             {
-                d = d + 2*dx;
+                d += 2*dx;
             }
         }
 
@@ -86,21 +86,21 @@ impl MemLine2D
             {
                 if yi >= 0
                 {
-                    y = y + yi as usize;
+                    y += yi as usize;
                 }
                 else {
                     y = y.wrapping_sub((-yi) as usize);
                 }
                 
-                d = d + (2 * (dy - dx));
+                d += 2 * (dy - dx);
             }
             else
             {
-                d = d + 2*dy;
+                d += 2*dy;
             }
         }
 
-        return indexes;
+        indexes
     }
 
     fn plot_line(min: MemIndex2D, max: MemIndex2D) -> Vec<MemIndex2D>
@@ -1,33 +1,34 @@
 use std::ops::{Add, Neg, Sub};
 
 use super::memory_index2d::MemIndex2D;
+use super::mem_coord::{MemCoord, MemSignedCoord};
 
 
 
 #[derive(Clone, Copy)]
-pub struct MemOffset2D {
-    pub row: isize,
-    pub col: isize
+pub struct MemOffset2D<O = isize> {
+    pub row: O,
+    pub col: O
 }
 
-impl From<MemIndex2D> for MemOffset2D {
-    fn from(value: MemIndex2D) -> Self {
+impl<I: MemCoord> From<MemIndex2D<I>> for MemOffset2D<I::Signed> {
+    fn from(value: MemIndex2D<I>) -> Self {
         MemOffset2D
         {
-            row: value.row as isize,
-            col: value.col as isize
+            row: value.row.as_signed(),
+            col: value.col.as_signed()
         }
     }
 }
 
-impl PartialEq for MemOffset2D
+impl<O: MemSignedCoord> PartialEq for MemOffset2D<O>
 {
     fn eq(&self, other: &Self) -> bool
     {
-        return self.row == other.row && self.col == other.col;
+        self.row == other.row && self.col == other.col
     }
 }
-impl PartialOrd for MemOffset2D
+impl<O: MemSignedCoord> PartialOrd for MemOffset2D<O>
 {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         match self.row.partial_cmp(&other.row) {
@@ -38,37 +39,29 @@ impl PartialOrd for MemOffset2D
     }
 }
 
-impl Add<MemIndex2D> for MemOffset2D {
+impl<I: MemCoord> Add<MemIndex2D<I>> for MemOffset2D<I::Signed> {
 
-    type Output = Option<MemIndex2D>;
-    fn add(self, rhs: MemIndex2D) -> Self::Output {
+    type Output = Option<MemIndex2D<I>>;
+    fn add(self, rhs: MemIndex2D<I>) -> Self::Output {
         rhs + self
     }
 }
 
-impl Sub for MemOffset2D
+impl<O: MemSignedCoord> Sub for MemOffset2D<O>
 {
     type Output = Option<Self>;
 
     fn sub(self, rhs: Self) -> Self::Output {
         
-        let row_component = match self.row.checked_sub(rhs.row)
-        {
-            Some(r_val) => r_val,
-            None => return None
-        };
+        let row_component = self.row.checked_sub(rhs.row)?;
 
-        let col_component = match self.col.checked_sub(rhs.col)
-        {
-            Some(c_val) => c_val,
-            None => return None
-        };
+        let col_component = self.col.checked_sub(rhs.col)?;
 
         Some(MemOffset2D {row: row_component, col: col_component})
     }
 }
 
-impl Neg for MemOffset2D
+impl<O: MemSignedCoord> Neg for MemOffset2D<O>
 {
     type Output = Self;
 
@@ -77,9 +70,9 @@ impl Neg for MemOffset2D
     }
 }
 
-impl MemOffset2D
+impl<O: MemSignedCoord> MemOffset2D<O>
 {
-    pub fn new(row: isize, col: isize) -> Self
+    pub fn new(row: O, col: O) -> Self
     {
         MemOffset2D
         {
@@ -88,21 +81,27 @@ impl MemOffset2D
         }
     }
 
-    pub fn row_offset(magnitude: isize) -> Self
+    pub fn row_offset(magnitude: O) -> Self
     {
         MemOffset2D
         {
             row: magnitude,
-            col: 0
+            col: O::ZERO
         }
     }
 
-    pub fn col_offset(magnitude: isize) -> Self
+    pub fn col_offset(magnitude: O) -> Self
     {
         MemOffset2D
         {
-            row: 0,
+            row: O::ZERO,
             col: magnitude
         }
     }
+
+    ///Convert to another offset width, None if either component does not fit
+    pub fn try_cast<J: MemSignedCoord>(&self) -> Option<MemOffset2D<J>>
+    {
+        Some(MemOffset2D::new(self.row.try_cast()?, self.col.try_cast()?))
+    }
 }
\ No newline at end of file
@@ -0,0 +1,80 @@
+use std::ops::{Add, Neg, Sub};
+use super::memory_index3d::MemIndex3D;
+use super::memory_offset2d::MemOffset2D;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MemOffset3D
+{
+    pub layer: isize,
+    pub row: isize,
+    pub col: isize
+}
+
+impl From<MemIndex3D> for MemOffset3D
+{
+    fn from(value: MemIndex3D) -> Self {
+        MemOffset3D
+        {
+            layer: value.layer as isize,
+            row: value.row as isize,
+            col: value.col as isize
+        }
+    }
+}
+
+impl Add<MemIndex3D> for MemOffset3D
+{
+    type Output = Option<MemIndex3D>;
+
+    fn add(self, rhs: MemIndex3D) -> Self::Output {
+        rhs + self
+    }
+}
+
+impl Sub for MemOffset3D
+{
+    type Output = Option<Self>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Some(MemOffset3D
+        {
+            layer: self.layer.checked_sub(rhs.layer)?,
+            row: self.row.checked_sub(rhs.row)?,
+            col: self.col.checked_sub(rhs.col)?
+        })
+    }
+}
+
+impl Neg for MemOffset3D
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        MemOffset3D { layer: -self.layer, row: -self.row, col: -self.col }
+    }
+}
+
+impl MemOffset3D
+{
+    pub fn new(layer: isize, row: isize, col: isize) -> Self
+    {
+        MemOffset3D { layer, row, col }
+    }
+
+    pub fn layer_offset(magnitude: isize) -> Self
+    {
+        MemOffset3D { layer: magnitude, row: 0, col: 0 }
+    }
+
+    pub fn from_offset2d(layer: isize, offset2d: MemOffset2D) -> Self
+    {
+        MemOffset3D { layer, row: offset2d.row, col: offset2d.col }
+    }
+
+    ///The in plane part of the offset
+    #[inline]
+    pub fn offset2d(&self) -> MemOffset2D
+    {
+        MemOffset2D::new(self.row, self.col)
+    }
+}
@@ -1,38 +1,45 @@
 use std::cmp::Ordering;
 use std::fmt::{Debug, Display};
 use std::ops::Range;
-use crate::memory_span2d::MemSpan2D;
+use crate::mem_coord::{MemCoord, MemSignedCoord};
 
 ///half open (inclusive lower_bound, exclusive upper_bound) span of memory indexes
 /// not quite a Range<usize> as that is a destructive range for iteration
 
 #[derive(Clone, Debug, Copy)]
-pub struct MemSpan
+pub struct MemSpan<I = usize>
 {
-    pub min: usize,
-    pub count: usize
+    pub min: I,
+    pub count: I
 }
 
-impl IntoIterator for MemSpan
-{
-    type Item = usize;
-    type IntoIter = std::ops::Range<usize>;
+//Range<I> only iterates for the std integer types, so spell the impls out
+macro_rules! impl_mem_span_into_iter {
+    ($($coord:ty),*) => {$(
+        impl IntoIterator for MemSpan<$coord>
+        {
+            type Item = $coord;
+            type IntoIter = std::ops::Range<$coord>;
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.min..self.upper_bound()
-    }
+            fn into_iter(self) -> Self::IntoIter {
+                self.min..self.upper_bound()
+            }
+        }
+    )*};
 }
 
-impl From<Range<usize>> for MemSpan
+impl_mem_span_into_iter!(u16, u32, u64, usize);
+
+impl<I: MemCoord> From<Range<I>> for MemSpan<I>
 {
-    fn from(range: Range<usize>) -> Self {
+    fn from(range: Range<I>) -> Self {
         MemSpan::new_range(range)
     }
 }
 
-impl Eq for MemSpan {}
+impl<I: MemCoord> Eq for MemSpan<I> {}
 
-impl Ord for MemSpan
+impl<I: MemCoord> Ord for MemSpan<I>
 {
     fn cmp(&self, other: &Self) -> Ordering {
         let lower_cmp = self.min.cmp(&other.min);
@@ -41,54 +48,59 @@ impl Ord for MemSpan
             return lower_cmp;
         }
 
-        return self.count.cmp(&other.count);
+        self.count.cmp(&other.count)
     }
 }
 
-impl PartialOrd for MemSpan
+impl<I: MemCoord> PartialOrd for MemSpan<I>
 {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl MemSpan
+impl<I: MemCoord> MemSpan<I>
 {
-    pub fn lower_bound(&self) -> usize
+    pub fn lower_bound(&self) -> I
     {
         self.min
     }
 
-    pub fn upper_bound(&self) -> usize
+    pub fn upper_bound(&self) -> I
     {
         self.min + self.count
     }
 
-    pub fn max(&self) -> Option<usize>
+    pub fn max(&self) -> Option<I>
     {
-        if self.count == 0 {
+        if self.count == I::ZERO {
             return None;
         }
 
-        Some(self.min + self.count - 1)
+        Some(self.min + self.count - I::ONE)
     }
 
-    pub fn max_value(&self) -> Option<usize>
+    pub fn max_value(&self) -> Option<I>
     {
         self.max()
     }
 
-    pub fn len(&self) -> usize
+    pub fn len(&self) -> I
     {
         self.count
     }
 
-    pub fn min_max(min: usize, max: usize) -> Option<Self>
+    pub fn is_empty(&self) -> bool
     {
-        MemSpan::lower_bound_upper_bound(min, max + 1)
+        self.count == I::ZERO
     }
 
-    pub fn lower_bound_upper_bound(lower: usize, upper: usize) -> Option<Self>
+    pub fn min_max(min: I, max: I) -> Option<Self>
+    {
+        MemSpan::lower_bound_upper_bound(min, max + I::ONE)
+    }
+
+    pub fn lower_bound_upper_bound(lower: I, upper: I) -> Option<Self>
     {
         if lower > upper {
             return None;
@@ -97,14 +109,35 @@ impl MemSpan
         Some(MemSpan{ min: lower, count: upper - lower })
     }
     #[inline]
-    pub fn new_range(range: Range<usize>) -> Self
+    pub fn new_range(range: Range<I>) -> Self
     {
-        MemSpan{min: range.start, count: range.count()}
+        MemSpan{min: range.start, count: range.end.checked_sub(range.start).unwrap_or(I::ZERO)}
+    }
+
+    ///The first len indexes and the rest, None unless both halves are non empty
+    pub fn split_at(&self, len: I) -> Option<(Self, Self)>
+    {
+        if len == I::ZERO || len >= self.count
+        {
+            return None;
+        }
+
+        Some((MemSpan{ min: self.min, count: len }, MemSpan{ min: self.min + len, count: self.count - len }))
+    }
+
+    ///Convert to another coordinate width, None if the bounds do not fit
+    pub fn try_cast<J: MemCoord>(&self) -> Option<MemSpan<J>>
+    {
+        let min: J = self.min.try_cast()?;
+        let count: J = self.count.try_cast()?;
+        min.checked_add(count)?;
+
+        Some(MemSpan{min, count})
     }
 
     // Wrapping versions
     #[inline]
-    pub fn shift_up_wrapping(&self, shift: usize) -> Self {
+    pub fn shift_up_wrapping(&self, shift: I) -> Self {
         MemSpan {
             min: self.min.wrapping_add(shift),
             count: self.count,
@@ -112,37 +145,35 @@ impl MemSpan
     }
 
     #[inline]
-    pub fn shift_down_wrapping(&self, shift: usize) -> Self {
+    pub fn shift_down_wrapping(&self, shift: I) -> Self {
         MemSpan {
             min: self.min.wrapping_sub(shift),
             count: self.count,
         }
     }
 
-    pub fn shift_wrapping(&self, shift: isize) -> Self {
-        if shift < 0 {
-            self.shift_down_wrapping(-shift as usize)
-        } else if shift > 0 {
-            self.shift_up_wrapping(shift as usize)
+    pub fn shift_wrapping(&self, shift: I::Signed) -> Self {
+        if shift < I::Signed::ZERO {
+            self.shift_down_wrapping(shift.unsigned_abs())
+        } else if shift > I::Signed::ZERO {
+            self.shift_up_wrapping(shift.unsigned_abs())
         } else {
-            self.clone()
+            *self
         }
     }
 
     // Checked versions 
     #[inline]
-    pub fn shift_up_checked(&self, shift: usize) -> Option<Self> {
+    pub fn shift_up_checked(&self, shift: I) -> Option<Self> {
         let min = self.min.checked_add(shift)?;
 
-        if min.checked_add(self.count).is_none() {
-            return None;
-        }
+        min.checked_add(self.count)?;
 
         Some(MemSpan {min, count: self.count})
     }
 
     #[inline]
-    pub fn shift_down_checked(&self, shift: usize) -> Option<Self> {
+    pub fn shift_down_checked(&self, shift: I) -> Option<Self> {
         let start = self.min.checked_sub(shift)?;
         Some(MemSpan {
             min: start,
@@ -150,159 +181,146 @@ impl MemSpan
         })
     }
 
-    pub fn shift_checked(&self, shift: isize) -> Option<Self> {
-        if shift < 0 {
-            self.shift_down_checked(-shift as usize)
-        } else if shift > 0 {
-            self.shift_up_checked(shift as usize)
+    pub fn shift_checked(&self, shift: I::Signed) -> Option<Self> {
+        if shift < I::Signed::ZERO {
+            self.shift_down_checked(shift.unsigned_abs())
+        } else if shift > I::Signed::ZERO {
+            self.shift_up_checked(shift.unsigned_abs())
         } else {
-            Some(self.clone())
+            Some(*self)
         }
     }
 
     // Max shift wrapping versions
     #[inline]
-    pub fn shift_max_up_wrapping(&self, shift: usize) -> Self {
+    pub fn shift_max_up_wrapping(&self, shift: I) -> Self {
         MemSpan{ min: self.min, count: self.count.wrapping_add(shift)}
     }
 
     #[inline]
-    pub fn shift_max_down_wrapping(&self, shift: usize) -> Self {
+    pub fn shift_max_down_wrapping(&self, shift: I) -> Self {
         MemSpan{min: self.min, count: self.count.wrapping_sub(shift)}
     }
 
-    pub fn shift_max_wrapping(&self, shift: isize) -> Self {
-        if shift < 0 {
-            self.shift_max_down_wrapping(-shift as usize)
-        } else if shift > 0 {
-            self.shift_max_up_wrapping(shift as usize)
+    pub fn shift_max_wrapping(&self, shift: I::Signed) -> Self {
+        if shift < I::Signed::ZERO {
+            self.shift_max_down_wrapping(shift.unsigned_abs())
+        } else if shift > I::Signed::ZERO {
+            self.shift_max_up_wrapping(shift.unsigned_abs())
         } else {
-            self.clone()
+            *self
         }
     }
 
     // Max shift checked versions
     #[inline]
-    pub fn shift_max_up_checked(&self, shift: usize) -> Option<Self> {
+    pub fn shift_max_up_checked(&self, shift: I) -> Option<Self> {
         let count = self.count.checked_add(shift)?;
 
-        if self.min.checked_add(count).is_none() {
-            return None;
-        }
+        self.min.checked_add(count)?;
 
 
         Some(MemSpan{min: self.min, count})
     }
 
     #[inline]
-    pub fn shift_max_down_checked(&self, shift: usize) -> Option<Self> {
+    pub fn shift_max_down_checked(&self, shift: I) -> Option<Self> {
         let count = self.count.checked_sub(shift)?;
         Some(MemSpan{min: self.min, count})
     }
 
-    pub fn shift_max_checked(&self, shift: isize) -> Option<Self> {
-        if shift < 0 {
-            self.shift_max_down_checked(-shift as usize)
-        } else if shift > 0 {
-            self.shift_max_up_checked(shift as usize)
+    pub fn shift_max_checked(&self, shift: I::Signed) -> Option<Self> {
+        if shift < I::Signed::ZERO {
+            self.shift_max_down_checked(shift.unsigned_abs())
+        } else if shift > I::Signed::ZERO {
+            self.shift_max_up_checked(shift.unsigned_abs())
         } else {
-            Some(self.clone())
+            Some(*self)
         }
     }
 
     // Min shift wrapping versions
     #[inline]
-    pub fn shift_min_up_wrapping(&self, shift: usize) -> Self {
+    pub fn shift_min_up_wrapping(&self, shift: I) -> Self {
         let min = self.min.wrapping_add(shift);
         let count = self.count.wrapping_sub(shift);
         MemSpan{min, count}
     }
 
     #[inline]
-    pub fn shift_min_down_wrapping(&self, shift: usize) -> Self {
+    pub fn shift_min_down_wrapping(&self, shift: I) -> Self {
         MemSpan{min: self.min.wrapping_sub(shift), count: self.count}
     }
 
-    pub fn shift_min_wrapping(&self, shift: isize) -> Self {
-        if shift < 0 {
-            self.shift_min_down_wrapping(-shift as usize)
-        } else if shift > 0 {
-            self.shift_min_up_wrapping(shift as usize)
+    pub fn shift_min_wrapping(&self, shift: I::Signed) -> Self {
+        if shift < I::Signed::ZERO {
+            self.shift_min_down_wrapping(shift.unsigned_abs())
+        } else if shift > I::Signed::ZERO {
+            self.shift_min_up_wrapping(shift.unsigned_abs())
         } else {
-            self.clone()
+            *self
         }
     }
 
     // Min shift checked versions
     #[inline]
-    pub fn shift_min_up_checked(&self, shift: usize) -> Option<Self> {
+    pub fn shift_min_up_checked(&self, shift: I) -> Option<Self> {
         let min = self.min.checked_add(shift)?;
-        let count: usize = self.count.checked_sub(shift)?;
+        let count: I = self.count.checked_sub(shift)?;
 
         Some(MemSpan{min, count})
     }
 
     #[inline]
-    pub fn shift_min_down_checked(&self, shift: usize) -> Option<Self> {
+    pub fn shift_min_down_checked(&self, shift: I) -> Option<Self> {
         let min = self.min.checked_sub(shift)?;
-        let count: usize = self.count.checked_add(shift)?;
+        let count: I = self.count.checked_add(shift)?;
         Some(MemSpan{min, count})
     }
 
-    pub fn shift_min_checked(&self, shift: isize) -> Option<Self> {
-        if shift < 0 {
-            self.shift_min_down_checked(-shift as usize)
-        } else if shift > 0 {
-            self.shift_min_up_checked(shift as usize)
+    pub fn shift_min_checked(&self, shift: I::Signed) -> Option<Self> {
+        if shift < I::Signed::ZERO {
+            self.shift_min_down_checked(shift.unsigned_abs())
+        } else if shift > I::Signed::ZERO {
+            self.shift_min_up_checked(shift.unsigned_abs())
         } else {
-            Some(self.clone())
+            Some(*self)
         }
     }
 
     #[inline]
-    pub fn contains(&self, index: usize) -> bool
+    pub fn contains(&self, index: I) -> bool
     {
-        self.min <= index && index < self.count
+        self.min <= index && index < self.upper_bound()
     }
 
     #[inline]
     pub fn intersect(&self, other: &Self) -> Option<Self>
     {
-        let this_max: usize = self.max()?;
+        let this_max: I = self.max()?;
         let other_max = other.max_value()?;
         Self::min_max(self.min.max(other.min), this_max.min(other_max))
     }
     pub fn overlaps(&self, other: &Self) -> bool
     {
-        self.intersect(other).is_some_and(|s| s.len() > 0)
+        self.intersect(other).is_some_and(|s| !s.is_empty())
     }
 
     ///Check if the sorted list of MemSpans contain any overlap
-    pub fn spans_have_overlap_sorted(spans: &Vec<MemSpan>) -> bool
+    pub fn spans_have_overlap_sorted(spans: &[MemSpan<I>]) -> bool
     {
-        for i in 0..spans.len() - 1
-        {
-            if let Some(max) = spans[i].max_value()
-            {
-                if spans[i+1].min < max
-                {
-                    return true;
-                }
-            }
-        }
-
-        false
+        spans.windows(2).any(|pair| pair[0].max_value().is_some_and(|max| pair[1].min <= max))
     }
 }
 
-impl PartialEq for MemSpan
+impl<I: MemCoord> PartialEq for MemSpan<I>
 {
     fn eq(&self, other: &Self) -> bool {
         self.min == other.min && self.count == other.count
     }
 }
 
-impl Display for MemSpan
+impl<I: MemCoord> Display for MemSpan<I>
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "[{},{})", self.min, self.max().unwrap_or(self.min))
@@ -319,6 +337,7 @@ mod tests {
     ///A_min > B_min && A_max < B_Max -> A_min..A_max
     ///A_min < B_max && A_max > B_max -> A_min..B_max
     #[test]
+    #[allow(non_snake_case)]
     fn test_intersection()
     {
         let mut A = MemSpan::min_max(1, 100).unwrap();
@@ -356,4 +375,34 @@ mod tests {
         assert_eq!(75, intersect.max_value().unwrap());
 
     }
+
+    #[test]
+    fn test_contains_offset_span()
+    {
+        //a span not starting at 0 used to be checked against its count instead of its upper bound
+        let span: MemSpan = MemSpan::lower_bound_upper_bound(10, 15).unwrap();
+        assert!(span.contains(10));
+        assert!(span.contains(14));
+        assert!(!span.contains(9));
+        assert!(!span.contains(15));
+
+        let empty: MemSpan = MemSpan::lower_bound_upper_bound(3, 3).unwrap();
+        assert!(!empty.contains(3));
+    }
+
+    #[test]
+    fn test_spans_have_overlap_sorted()
+    {
+        assert!(!MemSpan::<usize>::spans_have_overlap_sorted(&[]));
+
+        let single: MemSpan = MemSpan::lower_bound_upper_bound(0, 5).unwrap();
+        assert!(!MemSpan::spans_have_overlap_sorted(&[single]));
+
+        let touching: MemSpan = MemSpan::lower_bound_upper_bound(5, 8).unwrap();
+        assert!(!MemSpan::spans_have_overlap_sorted(&[single, touching]));
+
+        //the last cell of the first span is shared
+        let overlapping: MemSpan = MemSpan::lower_bound_upper_bound(4, 8).unwrap();
+        assert!(MemSpan::spans_have_overlap_sorted(&[single, overlapping]));
+    }
 }
\ No newline at end of file
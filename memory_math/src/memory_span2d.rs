@@ -1,29 +1,29 @@
 
 use std::{fmt::Display, ops::{Add, Sub}};
-use std::ops::Range;
 use crate::memory_span::MemSpan;
-use crate::size_2d::{HasSize2D, Size2D};
+use crate::mem_coord::MemCoord;
+use crate::size_2d::HasSize2D;
 use super::{memory_index2d::MemIndex2D, memory_offset2d::MemOffset2D};
 
 ///Represents a the bounds of a 2d view into an area of memory
 /// A Half-open (inclusive lower bound, exclusive upper bound) 2d span
 ///
 #[derive(Clone, Debug)]
-pub struct MemSpan2D
+pub struct MemSpan2D<I = usize>
 {
-    pub row_span: MemSpan,
-    pub col_span: MemSpan
+    pub row_span: MemSpan<I>,
+    pub col_span: MemSpan<I>
 }
 
-impl HasSize2D for MemSpan2D
+impl<I: MemCoord> HasSize2D<I> for MemSpan2D<I>
 {
     #[inline]
-    fn row_count(&self) -> usize {
+    fn row_count(&self) -> I {
         self.row_span.len()
     }
 
     #[inline]
-    fn column_count(&self) -> usize {
+    fn column_count(&self) -> I {
         self.col_span.len()
     }
 }
@@ -36,30 +36,30 @@ pub trait HasMemSpan2D
     fn row_upper_bound(&self) -> usize;
     #[inline]
     fn extents(&self) -> MemSpan2D {
-        MemSpan2D::new_from_index2d(MemIndex2D::new(self.row_lower_bound(), self.column_lower_bound()), MemIndex2D::new(self.row_upper_bound(), self.column_upper_bound()))
+        MemSpan2D::from_index2d(MemIndex2D::new(self.row_lower_bound(), self.column_lower_bound()), MemIndex2D::new(self.row_upper_bound(), self.column_upper_bound()))
     }
 }
-impl PartialEq for MemSpan2D
+impl<I: MemCoord> PartialEq for MemSpan2D<I>
 {
     fn eq(&self, other: &Self) -> bool {
         self.row_span == other.row_span && self.col_span == other.col_span
     }
 }
 
-impl Display for MemSpan2D
+impl<I: MemCoord> Display for MemSpan2D<I>
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({}, {})", self.row_span, self.col_span)
     }
 }
 
-impl Add<MemOffset2D> for MemSpan2D
+impl<I: MemCoord> Add<MemOffset2D<I::Signed>> for MemSpan2D<I>
 {
-    type Output = Option<MemSpan2D>;
+    type Output = Option<MemSpan2D<I>>;
 
-    fn add(self, rhs: MemOffset2D) -> Self::Output {
-        let row_range: MemSpan = self.row_span.shift_checked(rhs.row)?;
-        let col_range: MemSpan = self.col_span.shift_checked(rhs.col)?;
+    fn add(self, rhs: MemOffset2D<I::Signed>) -> Self::Output {
+        let row_range: MemSpan<I> = self.row_span.shift_checked(rhs.row)?;
+        let col_range: MemSpan<I> = self.col_span.shift_checked(rhs.col)?;
 
         Some(MemSpan2D
         {
@@ -69,11 +69,11 @@ impl Add<MemOffset2D> for MemSpan2D
     }
 }
 
-impl Sub<MemOffset2D> for MemSpan2D
+impl<I: MemCoord> Sub<MemOffset2D<I::Signed>> for MemSpan2D<I>
 {
-    type Output = Option<MemSpan2D>;
+    type Output = Option<MemSpan2D<I>>;
 
-    fn sub(self, rhs: MemOffset2D) -> Self::Output {
+    fn sub(self, rhs: MemOffset2D<I::Signed>) -> Self::Output {
         let flipped = -rhs;
         self + flipped
 
@@ -81,106 +81,140 @@ impl Sub<MemOffset2D> for MemSpan2D
 }
 
 
-impl MemSpan2D {
+impl MemSpan2D
+{
+    pub fn new_row_columns(rows: usize, columns: usize) -> Self
+    {
+        MemSpan2D::from_row_columns(rows, columns)
+    }
+
+    pub fn new_from_index2d(min_index2d: MemIndex2D, upper_bound_index2d: MemIndex2D) -> Self
+    {
+        MemSpan2D::from_index2d(min_index2d, upper_bound_index2d)
+    }
+
+    pub fn new_from_usize(row_lower_bound: usize, column_lower_bound: usize, row_upper_bound: usize, column_upper_bound: usize) -> Self
+    {
+        MemSpan2D::from_bounds(row_lower_bound, column_lower_bound, row_upper_bound, column_upper_bound)
+    }
+}
+
+impl<I: MemCoord> MemSpan2D<I> {
 
     #[inline]
-    pub fn min_column(&self) -> usize
+    pub fn min_column(&self) -> I
     {
         self.col_span.min
     }
 
     #[inline]
-    pub fn max_column(&self) -> Option<usize>
+    pub fn max_column(&self) -> Option<I>
     {
          MemSpan::max(&self.col_span)
     }
 
-    pub fn max_span_column(&self) -> usize
+    pub fn max_span_column(&self) -> I
     {
         self.col_span.len()
     }
 
     #[inline]
-    pub fn min_row(&self) -> usize
+    pub fn min_row(&self) -> I
     {
         self.row_span.min
     }
 
     pub fn valid(&self) -> bool
     {
-        self.row_count() != 0 && self.column_count() != 0
+        self.row_count() != I::ZERO && self.column_count() != I::ZERO
     }
 
     #[inline]
-    pub fn contains_row(&self, row: usize) -> bool
+    pub fn contains_row(&self, row: I) -> bool
     {
         self.row_span.contains(row)
     }
 
     #[inline]
-    pub fn max_row(&self) -> Option<usize>
+    pub fn max_row(&self) -> Option<I>
     {
         MemSpan::max(&self.row_span)
     }
 
     #[inline]
-    pub fn relative_index2d_to_absolute_index2d(&self, span_index: MemIndex2D) -> Option<MemIndex2D>
+    pub fn relative_index2d_to_absolute_index2d(&self, span_index: MemIndex2D<I>) -> Option<MemIndex2D<I>>
     {
         MemOffset2D::from(self.min_absolute_index2d()) + span_index
     }
 
-    pub fn min_absolute_index_for_row(&self, row: usize) -> Option<MemIndex2D>
+    ///Place a span given relative to this one in absolute coordinates, None if it reaches outside this span
+    pub fn relative_span2d_to_absolute_span2d(&self, span2d: &MemSpan2D<I>) -> Option<MemSpan2D<I>>
+    {
+        if !span2d.valid() || !self.size().contains_span2d(span2d)
+        {
+            return None;
+        }
+
+        Some(MemSpan2D
+        {
+            row_span: MemSpan{ min: self.min_row() + span2d.min_row(), count: span2d.row_count() },
+            col_span: MemSpan{ min: self.min_column() + span2d.min_column(), count: span2d.column_count() }
+        })
+    }
+
+    pub fn min_absolute_index_for_row(&self, row: I) -> Option<MemIndex2D<I>>
     {
         if row < self.min_row() || row > self.max_row()?
         {
             return None;
         }
 
-        let min_col: usize = self.min_column();
+        let min_col: I = self.min_column();
         Some(MemIndex2D::new(row, min_col))
     }
 
-    pub fn max_absolute_index_for_row(&self, row: usize) -> Option<MemIndex2D>
+    pub fn max_absolute_index_for_row(&self, row: I) -> Option<MemIndex2D<I>>
     {
         if row < self.min_row() || row > self.max_row()?
         {
             return None;
         }
 
-        let max_col: usize = self.max_column()?;
+        let max_col: I = self.max_column()?;
         Some(MemIndex2D::new(row, max_col))
     }
 
-    pub fn min_absolute_index2d(&self) -> MemIndex2D
+    pub fn min_absolute_index2d(&self) -> MemIndex2D<I>
     {
         let min_row = self.min_row();
         let min_col = self.min_column();
         MemIndex2D::new(min_row, min_col)
     }
 
-    pub fn max_absolute_index2d(&self) -> Option<MemIndex2D>
+    pub fn max_absolute_index2d(&self) -> Option<MemIndex2D<I>>
     {
-        let max_row: usize = self.max_row()?;
-        let max_col: usize = self.max_column()?;
+        let max_row: I = self.max_row()?;
+        let max_col: I = self.max_column()?;
         Some(MemIndex2D::new(max_row, max_col))
     }
 
     #[inline]
-    pub fn validate_coordinates(min_coord: MemIndex2D, max_coord: MemIndex2D) -> bool
+    pub fn validate_coordinates(min_coord: MemIndex2D<I>, max_coord: MemIndex2D<I>) -> bool
     {
         MemOffset2D::from(min_coord) <= MemOffset2D::from(max_coord)
     }
 
-    pub fn new_row_columns(rows: usize, columns: usize) -> Self
+    ///Constructor for any coordinate width, new_row_columns stays usize so plain literals keep inferring
+    pub fn from_row_columns(rows: I, columns: I) -> Self
     {
         MemSpan2D
         {
-            row_span: MemSpan::new_range(0..rows),
-            col_span: (0..columns).into()
+            row_span: MemSpan::new_range(I::ZERO..rows),
+            col_span: (I::ZERO..columns).into()
         }
     }
 
-    pub fn new_from_index2d(min_index2d: MemIndex2D, upper_bound_index2d: MemIndex2D) -> Self
+    pub fn from_index2d(min_index2d: MemIndex2D<I>, upper_bound_index2d: MemIndex2D<I>) -> Self
     {
         MemSpan2D
         {
@@ -189,12 +223,24 @@ impl MemSpan2D {
         }
     }
 
-    pub fn area(&self) -> usize
+    ///Convert to another coordinate width, None if any bound does not fit
+    pub fn try_cast<J: MemCoord>(&self) -> Option<MemSpan2D<J>>
+    {
+        Some(MemSpan2D
+        {
+            row_span: self.row_span.try_cast()?,
+            col_span: self.col_span.try_cast()?
+        })
+    }
+
+    ///Number of cells, counted in usize like Size2D::area so narrow coordinate types cannot overflow.
+    /// None if the count does not fit in usize
+    pub fn area(&self) -> Option<usize>
     {
-        self.row_count() * self.column_count()
+        self.size().area()
     }
 
-    pub fn new_from_usize(row_lower_bound: usize, column_lower_bound: usize, row_upper_bound: usize, column_upper_bound: usize) -> Self
+    pub fn from_bounds(row_lower_bound: I, column_lower_bound: I, row_upper_bound: I, column_upper_bound: I) -> Self
     {
         MemSpan2D
         {
@@ -203,10 +249,10 @@ impl MemSpan2D {
         }
     }
 
-    pub fn shift_rows(&self, shift: isize) -> Option<Self>
+    pub fn shift_rows(&self, shift: I::Signed) -> Option<Self>
     {
-        let row_range: MemSpan = self.row_span.shift_checked(shift)?;
-        let col_range: MemSpan = self.col_span.clone();
+        let row_range: MemSpan<I> = self.row_span.shift_checked(shift)?;
+        let col_range: MemSpan<I> = self.col_span;
 
         Some(MemSpan2D
         {
@@ -215,10 +261,10 @@ impl MemSpan2D {
         })
     }
 
-    pub fn shift_columns(&self, shift: isize) -> Option<Self>
+    pub fn shift_columns(&self, shift: I::Signed) -> Option<Self>
     {
-        let row_range: MemSpan = self.row_span.clone();
-        let col_range: MemSpan = self.col_span.shift_checked(shift)?;
+        let row_range: MemSpan<I> = self.row_span;
+        let col_range: MemSpan<I> = self.col_span.shift_checked(shift)?;
 
         Some(MemSpan2D
         {
@@ -227,9 +273,9 @@ impl MemSpan2D {
         })
     }
 
-    pub fn shift(&self, shift: MemOffset2D) -> Option<Self> {
-        let row_range: MemSpan = self.row_span.shift_checked(shift.row)?;
-        let col_range: MemSpan = self.col_span.shift_checked(shift.col)?;
+    pub fn shift(&self, shift: MemOffset2D<I::Signed>) -> Option<Self> {
+        let row_range: MemSpan<I> = self.row_span.shift_checked(shift.row)?;
+        let col_range: MemSpan<I> = self.col_span.shift_checked(shift.col)?;
         Some(MemSpan2D
         {
             row_span: row_range,
@@ -237,10 +283,10 @@ impl MemSpan2D {
         })
     }
 
-    pub fn shift_max_rows(&self, shift: isize) -> Option<Self>
+    pub fn shift_max_rows(&self, shift: I::Signed) -> Option<Self>
     {
-        let row_range: MemSpan = self.row_span.shift_max_checked(shift)?;
-        let col_range: MemSpan = self.col_span.clone();
+        let row_range: MemSpan<I> = self.row_span.shift_max_checked(shift)?;
+        let col_range: MemSpan<I> = self.col_span;
 
         Some(MemSpan2D
         {
@@ -249,10 +295,10 @@ impl MemSpan2D {
         })
     }
 
-    pub fn shift_max_columns(&self, shift: isize) -> Option<Self>
+    pub fn shift_max_columns(&self, shift: I::Signed) -> Option<Self>
     {
-        let row_range: MemSpan = self.row_span.clone();
-        let col_range: MemSpan = self.col_span.shift_max_checked(shift)?;
+        let row_range: MemSpan<I> = self.row_span;
+        let col_range: MemSpan<I> = self.col_span.shift_max_checked(shift)?;
 
         Some(MemSpan2D
         {
@@ -261,10 +307,10 @@ impl MemSpan2D {
         })
     }
 
-    pub fn shift_max(&self, shift: MemOffset2D) -> Option<Self>
+    pub fn shift_max(&self, shift: MemOffset2D<I::Signed>) -> Option<Self>
     {
-        let row_range: MemSpan = self.row_span.shift_max_checked(shift.row)?;
-        let col_range: MemSpan = self.col_span.shift_max_checked(shift.col)?;
+        let row_range: MemSpan<I> = self.row_span.shift_max_checked(shift.row)?;
+        let col_range: MemSpan<I> = self.col_span.shift_max_checked(shift.col)?;
 
         Some(MemSpan2D
         {
@@ -273,10 +319,10 @@ impl MemSpan2D {
         })
     }
 
-    pub fn shift_min_rows(&self, shift: isize) -> Option<Self>
+    pub fn shift_min_rows(&self, shift: I::Signed) -> Option<Self>
     {
-        let row_range: MemSpan = self.row_span.shift_min_checked(shift)?;
-        let col_range: MemSpan = self.col_span.clone();
+        let row_range: MemSpan<I> = self.row_span.shift_min_checked(shift)?;
+        let col_range: MemSpan<I> = self.col_span;
 
         Some(
             MemSpan2D
@@ -287,10 +333,10 @@ impl MemSpan2D {
         )
     }
 
-    pub fn shift_min_columns(&self, shift: isize) -> Option<Self>
+    pub fn shift_min_columns(&self, shift: I::Signed) -> Option<Self>
     {
-        let row_range: MemSpan = self.row_span.clone();
-        let col_range: MemSpan = self.col_span.shift_min_checked(shift)?;
+        let row_range: MemSpan<I> = self.row_span;
+        let col_range: MemSpan<I> = self.col_span.shift_min_checked(shift)?;
 
         Some(
             MemSpan2D
@@ -301,10 +347,10 @@ impl MemSpan2D {
         )
     }
 
-    pub fn shift_min(&self, shift: MemOffset2D) -> Option<Self>
+    pub fn shift_min(&self, shift: MemOffset2D<I::Signed>) -> Option<Self>
     {
-        let row_range: MemSpan = self.row_span.shift_min_checked(shift.row)?;
-        let col_range: MemSpan = self.col_span.shift_min_checked(shift.col)?;
+        let row_range: MemSpan<I> = self.row_span.shift_min_checked(shift.row)?;
+        let col_range: MemSpan<I> = self.col_span.shift_min_checked(shift.col)?;
 
         Some(MemSpan2D
         {
@@ -313,12 +359,12 @@ impl MemSpan2D {
         })
     }
 
-    pub fn contains_index2d(&self, index2d: &MemIndex2D) -> bool
+    pub fn contains_index2d(&self, index2d: &MemIndex2D<I>) -> bool
     {
         self.row_span.contains(index2d.row) && self.col_span.contains(index2d.col)
     }
 
-    pub fn intersect(&self, other: &MemSpan2D) -> Option<MemSpan2D>
+    pub fn intersect(&self, other: &MemSpan2D<I>) -> Option<MemSpan2D<I>>
     {
         let row_range = self.row_span.intersect(&other.row_span)?;
         let col_range = self.col_span.intersect(&other.col_span)?;
@@ -330,17 +376,48 @@ impl MemSpan2D {
         })
     }
 
-    pub fn overlaps(&self, other: &MemSpan2D) -> bool
+    pub fn overlaps(&self, other: &MemSpan2D<I>) -> bool
     {
-        self.intersect(other).is_some_and(|s| s.area() > 0 )
+        self.intersect(other).is_some_and(|s| s.valid())
+    }
+
+    ///Join two spans sharing a complete edge into one span,
+    /// None unless the pair lines up exactly along rows or columns.
+    pub fn join_aligned(&self, other: &MemSpan2D<I>) -> Option<MemSpan2D<I>>
+    {
+        if self.col_span == other.col_span
+        {
+            if self.row_span.upper_bound() == other.min_row()
+            {
+                return Some(MemSpan2D { row_span: self.row_span.shift_max_up_checked(other.row_count())?, col_span: self.col_span });
+            }
+            if other.row_span.upper_bound() == self.min_row()
+            {
+                return Some(MemSpan2D { row_span: other.row_span.shift_max_up_checked(self.row_count())?, col_span: self.col_span });
+            }
+        }
+
+        if self.row_span == other.row_span
+        {
+            if self.col_span.upper_bound() == other.min_column()
+            {
+                return Some(MemSpan2D { row_span: self.row_span, col_span: self.col_span.shift_max_up_checked(other.column_count())? });
+            }
+            if other.col_span.upper_bound() == self.min_column()
+            {
+                return Some(MemSpan2D { row_span: self.row_span, col_span: other.col_span.shift_max_up_checked(self.column_count())? });
+            }
+        }
+
+        None
     }
 
     //TODO: Write the sorted version of this function.
-    pub fn spans_overlap_or_invalid(spans: &Vec<MemSpan2D>) -> bool
+    pub fn spans_overlap_or_invalid(spans: &[MemSpan2D<I>]) -> bool
     {
         for i in 0..spans.len()
         {
-            if spans[i].area() == 0
+            if !spans[i].valid()
             {
                 return true;
             }
@@ -391,7 +468,7 @@ mod tests {
         let expected_intersection: MemSpan2D = MemSpan2D::new_from_usize(0, 10, 10, 10);
         assert_eq!(lhs.intersect(&rhs).unwrap(), expected_intersection);
         assert_eq!(rhs.intersect(&lhs).unwrap(), expected_intersection);
-        assert_eq!(0, expected_intersection.area());
+        assert_eq!(Some(0), expected_intersection.area());
     }
 
     #[test]
@@ -402,7 +479,7 @@ mod tests {
         let expected_intersection: MemSpan2D = MemSpan2D::new_from_usize(0, 5, 10, 10);
         assert_eq!(lhs.intersect(&rhs).unwrap(), expected_intersection);
         assert_eq!(rhs.intersect(&lhs).unwrap(), expected_intersection);
-        assert_eq!(50, expected_intersection.area());
+        assert_eq!(Some(50), expected_intersection.area());
     }
 
     #[test]
@@ -413,7 +490,7 @@ mod tests {
         let expected_intersection: MemSpan2D = MemSpan2D::new_from_usize(10, 0, 10, 10);
         assert_eq!(lhs.intersect(&rhs).unwrap(), expected_intersection);
         assert_eq!(rhs.intersect(&lhs).unwrap(), expected_intersection);
-        assert_eq!(0, expected_intersection.area());
+        assert_eq!(Some(0), expected_intersection.area());
     }
 
     #[test]
@@ -434,7 +511,7 @@ mod tests {
         let expected_intersection: MemSpan2D = MemSpan2D::new_from_usize(5, 10, 10, 20);
         assert_eq!(lhs.intersect(&rhs).unwrap(), expected_intersection);
         assert_eq!(rhs.intersect(&lhs).unwrap(), expected_intersection);
-        assert_eq!(50, expected_intersection.area());
+        assert_eq!(Some(50), expected_intersection.area());
     }
 
     #[test]
@@ -446,4 +523,13 @@ mod tests {
         assert_eq!(lhs.intersect(&rhs).unwrap(), expected_intersection);
         assert_eq!(rhs.intersect(&lhs).unwrap(), expected_intersection);
     }
+
+    #[test]
+    fn test_relative_span2d_to_absolute_span2d()
+    {
+        let span: MemSpan2D = MemSpan2D::new_from_usize(2, 3, 8, 9);
+        assert_eq!(Some(MemSpan2D::new_from_usize(3, 5, 8, 6)), span.relative_span2d_to_absolute_span2d(&MemSpan2D::new_from_usize(1, 2, 6, 3)));
+        assert_eq!(None, span.relative_span2d_to_absolute_span2d(&MemSpan2D::new_from_usize(1, 2, 7, 3)));
+        assert_eq!(None, span.relative_span2d_to_absolute_span2d(&MemSpan2D::new_from_usize(1, 2, 1, 3)));
+    }
 }
\ No newline at end of file
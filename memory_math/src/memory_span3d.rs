@@ -0,0 +1,239 @@
+use std::fmt::Display;
+use std::ops::{Add, Sub};
+use crate::memory_index3d::MemIndex3D;
+use crate::memory_offset3d::MemOffset3D;
+use crate::memory_span::MemSpan;
+use crate::memory_span2d::MemSpan2D;
+use crate::size_3d::{HasSize3D, Size3D};
+
+///Half-open (inclusive lower bound, exclusive upper bound) 3d span,
+/// a MemSpan2D repeated over a span of layers
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemSpan3D
+{
+    pub layer_span: MemSpan,
+    pub row_span: MemSpan,
+    pub col_span: MemSpan
+}
+
+impl HasSize3D for MemSpan3D
+{
+    #[inline]
+    fn layer_count(&self) -> usize {
+        self.layer_span.len()
+    }
+
+    #[inline]
+    fn row_count(&self) -> usize {
+        self.row_span.len()
+    }
+
+    #[inline]
+    fn column_count(&self) -> usize {
+        self.col_span.len()
+    }
+}
+
+impl Display for MemSpan3D
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.layer_span, self.row_span, self.col_span)
+    }
+}
+
+impl Add<MemOffset3D> for MemSpan3D
+{
+    type Output = Option<MemSpan3D>;
+
+    fn add(self, rhs: MemOffset3D) -> Self::Output {
+        self.shift(rhs)
+    }
+}
+
+impl Sub<MemOffset3D> for MemSpan3D
+{
+    type Output = Option<MemSpan3D>;
+
+    fn sub(self, rhs: MemOffset3D) -> Self::Output {
+        self.shift(-rhs)
+    }
+}
+
+impl MemSpan3D
+{
+    pub fn new(layer_span: MemSpan, span2d: MemSpan2D) -> Self
+    {
+        MemSpan3D
+        {
+            layer_span,
+            row_span: span2d.row_span,
+            col_span: span2d.col_span
+        }
+    }
+
+    pub fn new_layers_rows_columns(layers: usize, rows: usize, columns: usize) -> Self
+    {
+        MemSpan3D
+        {
+            layer_span: MemSpan::new_range(0..layers),
+            row_span: MemSpan::new_range(0..rows),
+            col_span: MemSpan::new_range(0..columns)
+        }
+    }
+
+    pub fn new_from_index3d(min_index3d: MemIndex3D, upper_bound_index3d: MemIndex3D) -> Self
+    {
+        MemSpan3D
+        {
+            layer_span: MemSpan::new_range(min_index3d.layer..upper_bound_index3d.layer),
+            row_span: MemSpan::new_range(min_index3d.row..upper_bound_index3d.row),
+            col_span: MemSpan::new_range(min_index3d.col..upper_bound_index3d.col)
+        }
+    }
+
+    ///The rows and columns covered on every layer
+    #[inline]
+    pub fn plane_span(&self) -> MemSpan2D
+    {
+        MemSpan2D { row_span: self.row_span, col_span: self.col_span }
+    }
+
+    #[inline]
+    pub fn min_layer(&self) -> usize
+    {
+        self.layer_span.min
+    }
+
+    #[inline]
+    pub fn max_layer(&self) -> Option<usize>
+    {
+        self.layer_span.max_value()
+    }
+
+    pub fn volume(&self) -> usize
+    {
+        self.layer_count() * self.row_count() * self.column_count()
+    }
+
+    pub fn valid(&self) -> bool
+    {
+        self.volume() != 0
+    }
+
+    pub fn size(&self) -> Size3D
+    {
+        self.size3d()
+    }
+
+    pub fn min_absolute_index3d(&self) -> MemIndex3D
+    {
+        MemIndex3D::new(self.layer_span.min, self.row_span.min, self.col_span.min)
+    }
+
+    pub fn max_absolute_index3d(&self) -> Option<MemIndex3D>
+    {
+        Some(MemIndex3D::new(self.layer_span.max_value()?, self.row_span.max_value()?, self.col_span.max_value()?))
+    }
+
+    #[inline]
+    pub fn relative_index3d_to_absolute_index3d(&self, span_index: MemIndex3D) -> Option<MemIndex3D>
+    {
+        MemOffset3D::from(self.min_absolute_index3d()) + span_index
+    }
+
+    pub fn contains_index3d(&self, index3d: &MemIndex3D) -> bool
+    {
+        self.layer_span.contains(index3d.layer) && self.row_span.contains(index3d.row) && self.col_span.contains(index3d.col)
+    }
+
+    pub fn intersect(&self, other: &MemSpan3D) -> Option<MemSpan3D>
+    {
+        Some(MemSpan3D
+        {
+            layer_span: self.layer_span.intersect(&other.layer_span)?,
+            row_span: self.row_span.intersect(&other.row_span)?,
+            col_span: self.col_span.intersect(&other.col_span)?
+        })
+    }
+
+    pub fn overlaps(&self, other: &MemSpan3D) -> bool
+    {
+        self.intersect(other).is_some_and(|s| s.volume() > 0)
+    }
+
+    pub fn shift(&self, shift: MemOffset3D) -> Option<Self>
+    {
+        Some(MemSpan3D
+        {
+            layer_span: self.layer_span.shift_checked(shift.layer)?,
+            row_span: self.row_span.shift_checked(shift.row)?,
+            col_span: self.col_span.shift_checked(shift.col)?
+        })
+    }
+
+    pub fn shift_layers(&self, shift: isize) -> Option<Self>
+    {
+        Some(MemSpan3D
+        {
+            layer_span: self.layer_span.shift_checked(shift)?,
+            row_span: self.row_span,
+            col_span: self.col_span
+        })
+    }
+
+    pub fn shift_max(&self, shift: MemOffset3D) -> Option<Self>
+    {
+        Some(MemSpan3D
+        {
+            layer_span: self.layer_span.shift_max_checked(shift.layer)?,
+            row_span: self.row_span.shift_max_checked(shift.row)?,
+            col_span: self.col_span.shift_max_checked(shift.col)?
+        })
+    }
+
+    pub fn shift_min(&self, shift: MemOffset3D) -> Option<Self>
+    {
+        Some(MemSpan3D
+        {
+            layer_span: self.layer_span.shift_min_checked(shift.layer)?,
+            row_span: self.row_span.shift_min_checked(shift.row)?,
+            col_span: self.col_span.shift_min_checked(shift.col)?
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersect()
+    {
+        let lhs = MemSpan3D::new_from_index3d(MemIndex3D::origin(), MemIndex3D::new(4, 10, 10));
+        let rhs = MemSpan3D::new_from_index3d(MemIndex3D::new(2, 5, 5), MemIndex3D::new(8, 15, 15));
+        let expected = MemSpan3D::new_from_index3d(MemIndex3D::new(2, 5, 5), MemIndex3D::new(4, 10, 10));
+
+        assert_eq!(expected, lhs.intersect(&rhs).unwrap());
+        assert_eq!(expected, rhs.intersect(&lhs).unwrap());
+        assert_eq!(50, expected.volume());
+        assert!(lhs.overlaps(&rhs));
+
+        let touching = lhs.shift_layers(4).unwrap();
+        assert!(!lhs.overlaps(&touching));
+    }
+
+    #[test]
+    fn test_shift()
+    {
+        let span = MemSpan3D::new_layers_rows_columns(2, 3, 4);
+        let shifted = (span.clone() + MemOffset3D::new(1, 2, 3)).unwrap();
+
+        assert_eq!(MemIndex3D::new(1, 2, 3), shifted.min_absolute_index3d());
+        assert_eq!(Some(MemIndex3D::new(2, 4, 6)), shifted.max_absolute_index3d());
+        assert!((span.clone() - MemOffset3D::layer_offset(1)).is_none());
+
+        let grown = span.shift_max(MemOffset3D::new(1, 0, -1)).unwrap();
+        assert_eq!(Size3D::new(3, 3, 3), grown.size());
+        assert_eq!(span.plane_span(), MemSpan2D::new_row_columns(3, 4));
+    }
+}
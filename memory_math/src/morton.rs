@@ -0,0 +1,372 @@
+use crate::memory_index2d::MemIndex2D;
+use crate::memory_span::MemSpan;
+use crate::memory_span2d::MemSpan2D;
+
+//Columns live in the even bits of a code, rows in the odd bits
+const EVEN_BITS: u64 = 0x5555_5555_5555_5555;
+const ODD_BITS: u64 = 0xAAAA_AAAA_AAAA_AAAA;
+
+///Spread the low 32 bits of value over the even bits of a u64
+#[inline]
+fn part1by1(value: u64) -> u64
+{
+    let mut x = value & 0x0000_0000_FFFF_FFFF;
+    x = (x | (x << 16)) & 0x0000_FFFF_0000_FFFF;
+    x = (x | (x << 8)) & 0x00FF_00FF_00FF_00FF;
+    x = (x | (x << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | (x << 2)) & 0x3333_3333_3333_3333;
+    (x | (x << 1)) & EVEN_BITS
+}
+
+///Gather the even bits of value into the low 32 bits
+#[inline]
+fn compact1by1(value: u64) -> u64
+{
+    let mut x = value & EVEN_BITS;
+    x = (x | (x >> 1)) & 0x3333_3333_3333_3333;
+    x = (x | (x >> 2)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | (x >> 4)) & 0x00FF_00FF_00FF_00FF;
+    x = (x | (x >> 8)) & 0x0000_FFFF_0000_FFFF;
+    (x | (x >> 16)) & 0x0000_0000_FFFF_FFFF
+}
+
+///Morton code of an index, None if a coordinate needs more than 32 bits
+#[inline]
+pub fn encode(index2d: MemIndex2D) -> Option<u64>
+{
+    let row: u64 = u32::try_from(index2d.row).ok()? as u64;
+    let col: u64 = u32::try_from(index2d.col).ok()? as u64;
+    Some(part1by1(col) | (part1by1(row) << 1))
+}
+
+#[inline]
+pub fn decode(code: u64) -> MemIndex2D
+{
+    MemIndex2D::new(compact1by1(code >> 1) as usize, compact1by1(code) as usize)
+}
+
+///Lower bits of the same axis as bit
+#[inline]
+fn axis_bits_below(bit: u32) -> u64
+{
+    let axis: u64 = if bit.is_multiple_of(2) { EVEN_BITS } else { ODD_BITS };
+    axis & ((1u64 << bit) - 1)
+}
+
+///Set bit and clear the lower bits of its axis
+#[inline]
+fn load_1000(value: u64, bit: u32) -> u64
+{
+    (value & !axis_bits_below(bit)) | (1u64 << bit)
+}
+
+///Clear bit and set the lower bits of its axis
+#[inline]
+fn load_0111(value: u64, bit: u32) -> u64
+{
+    (value & !(1u64 << bit)) | axis_bits_below(bit)
+}
+
+///Smallest code greater than code that lies inside the box with corner codes zmin and zmax (inclusive).
+/// code is expected to be between zmin and zmax, None if no such code exists.
+pub fn bigmin(code: u64, zmin: u64, zmax: u64) -> Option<u64>
+{
+    let mut zmin: u64 = zmin;
+    let mut zmax: u64 = zmax;
+    let mut result: Option<u64> = None;
+
+    for bit in (0..64).rev()
+    {
+        let mask: u64 = 1u64 << bit;
+        match (code & mask != 0, zmin & mask != 0, zmax & mask != 0)
+        {
+            (false, false, true) =>
+            {
+                result = Some(load_1000(zmin, bit));
+                zmax = load_0111(zmax, bit);
+            }
+            (false, true, true) => return Some(zmin),
+            (true, false, false) => return result,
+            (true, false, true) => zmin = load_1000(zmin, bit),
+            (false, true, false) | (true, true, false) => return None,
+            _ => {}
+        }
+    }
+
+    result
+}
+
+///Largest code smaller than code that lies inside the box with corner codes zmin and zmax (inclusive).
+/// code is expected to be between zmin and zmax, None if no such code exists.
+pub fn litmax(code: u64, zmin: u64, zmax: u64) -> Option<u64>
+{
+    let mut zmin: u64 = zmin;
+    let mut zmax: u64 = zmax;
+    let mut result: Option<u64> = None;
+
+    for bit in (0..64).rev()
+    {
+        let mask: u64 = 1u64 << bit;
+        match (code & mask != 0, zmin & mask != 0, zmax & mask != 0)
+        {
+            (false, false, true) => zmax = load_0111(zmax, bit),
+            (false, true, true) => return result,
+            (true, false, false) => return Some(zmax),
+            (true, false, true) =>
+            {
+                result = Some(load_0111(zmax, bit));
+                zmin = load_1000(zmin, bit);
+            }
+            (false, true, false) | (true, true, false) => return None,
+            _ => {}
+        }
+    }
+
+    result
+}
+
+///Length of the largest aligned Morton block starting at code that stays inside span.
+/// code must decode to an index inside span. None for the block of all 2^64 codes, which a u64 can not count.
+fn aligned_block_len(code: u64, span: &MemSpan2D) -> Option<u64>
+{
+    let index2d: MemIndex2D = decode(code);
+    let mut level: u32 = 0;
+
+    while level < 32 && code.trailing_zeros() >= 2 * (level + 1)
+    {
+        let side: usize = 1 << (level + 1);
+        if index2d.row + side > span.row_span.upper_bound() || index2d.col + side > span.col_span.upper_bound()
+        {
+            break;
+        }
+        level += 1;
+    }
+
+    1u64.checked_shl(2 * level)
+}
+
+///Decompose a span into the contiguous runs of Morton codes covering exactly its cells, in code order.
+/// Jumps over the gaps with bigmin, so the work depends on the span outline rather than its area.
+/// None if the span is empty, a coordinate needs more than 32 bits or a run would end past the last u64 code.
+pub fn span_runs(span: &MemSpan2D) -> Option<Vec<MemSpan<u64>>>
+{
+    if !span.valid()
+    {
+        return None;
+    }
+
+    let zmin: u64 = encode(span.min_absolute_index2d())?;
+    let zmax: u64 = encode(span.max_absolute_index2d()?)?;
+
+    let mut runs: Vec<MemSpan<u64>> = Vec::new();
+    let mut cursor: Option<u64> = Some(zmin);
+
+    while let Some(run_start) = cursor
+    {
+        let mut run_end: u64 = run_start;
+        cursor = None;
+
+        loop
+        {
+            run_end = run_end.checked_add(aligned_block_len(run_end, span)?)?;
+            if run_end > zmax
+            {
+                break;
+            }
+
+            if !span.contains_index2d(&decode(run_end))
+            {
+                cursor = bigmin(run_end, zmin, zmax);
+                break;
+            }
+        }
+
+        runs.push(MemSpan::new_range(run_start..run_end));
+    }
+
+    Some(runs)
+}
+
+//Quadrants of an aligned block in code order, as (row, column) halves
+const QUADRANTS: [(u64, u64); 4] = [(0, 0), (0, 1), (1, 0), (1, 1)];
+
+///Cells of span inside the aligned block of side cells at row, col. Counted in u128, the root block holds 2^64 cells
+fn block_overlap(span: &MemSpan2D, row: u64, col: u64, side: u64) -> u128
+{
+    let overlap = |min: u64, bound_min: usize, bound_upper: usize| {
+        let lower: u64 = min.max(bound_min as u64);
+        let upper: u64 = (min + side).min(bound_upper as u64);
+        upper.saturating_sub(lower) as u128
+    };
+
+    overlap(row, span.min_row(), span.row_span.upper_bound()) * overlap(col, span.min_column(), span.col_span.upper_bound())
+}
+
+///Number of cells of span which come before index2d in Morton order.
+/// Walks the quadtree path of index2d and counts the span cells of the quadrants it passes, so the work is constant per level.
+/// None if index2d is outside of span, a coordinate needs more than 32 bits or the count does not fit in usize.
+pub fn span_rank(span: &MemSpan2D, index2d: MemIndex2D) -> Option<usize>
+{
+    if !span.contains_index2d(&index2d)
+    {
+        return None;
+    }
+
+    let code: u64 = encode(index2d)?;
+    let (mut row, mut col): (u64, u64) = (0, 0);
+    let mut before: u128 = 0;
+
+    for level in (0..32).rev()
+    {
+        let side: u64 = 1 << level;
+        let quadrant: usize = ((code >> (2 * level)) & 3) as usize;
+
+        for (row_half, col_half) in &QUADRANTS[..quadrant]
+        {
+            before += block_overlap(span, row + row_half * side, col + col_half * side, side);
+        }
+
+        let (row_half, col_half) = QUADRANTS[quadrant];
+        row += row_half * side;
+        col += col_half * side;
+    }
+
+    usize::try_from(before).ok()
+}
+
+///Cell of span at position in Morton order, the inverse of span_rank.
+/// Descends into the quadrant holding position at every level. None if position is past the last cell of span.
+pub fn span_unrank(span: &MemSpan2D, position: usize) -> Option<MemIndex2D>
+{
+    let (mut row, mut col): (u64, u64) = (0, 0);
+    let mut remaining: u128 = position as u128;
+
+    for level in (0..32).rev()
+    {
+        let side: u64 = 1 << level;
+        let (row_half, col_half) = QUADRANTS.iter().find(|(row_half, col_half)| {
+            let count: u128 = block_overlap(span, row + row_half * side, col + col_half * side, side);
+            if remaining < count
+            {
+                return true;
+            }
+
+            remaining -= count;
+            false
+        })?;
+
+        row += row_half * side;
+        col += col_half * side;
+    }
+
+    Some(MemIndex2D::new(usize::try_from(row).ok()?, usize::try_from(col).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode()
+    {
+        assert_eq!(Some(0), encode(MemIndex2D::origin()));
+        assert_eq!(Some(1), encode(MemIndex2D::new(0, 1)));
+        assert_eq!(Some(2), encode(MemIndex2D::new(1, 0)));
+        assert_eq!(Some(3), encode(MemIndex2D::new(1, 1)));
+        assert_eq!(Some(0b0101), encode(MemIndex2D::new(0, 3)));
+        assert_eq!(Some(0b1010), encode(MemIndex2D::new(3, 0)));
+
+        for index2d in [MemIndex2D::new(4095, 17), MemIndex2D::new(123_456, 7_654_321), MemIndex2D::new(u32::MAX as usize, 0)]
+        {
+            assert_eq!(index2d, decode(encode(index2d).unwrap()));
+        }
+
+        assert!(encode(MemIndex2D::new(u32::MAX as usize + 1, 0)).is_none());
+    }
+
+    #[test]
+    fn test_bigmin_litmax()
+    {
+        //First box is rows 1..=2, cols 1..=2 of a 4x4 grid, so codes 3, 6, 9 and 12 are inside
+        for span in [MemSpan2D::new_from_usize(1, 1, 3, 3), MemSpan2D::new_from_usize(3, 2, 11, 13)]
+        {
+            let zmin: u64 = encode(span.min_absolute_index2d()).unwrap();
+            let zmax: u64 = encode(span.max_absolute_index2d().unwrap()).unwrap();
+            let inside = |code: u64| span.contains_index2d(&decode(code));
+
+            for code in (zmin..=zmax).filter(|c| !inside(*c))
+            {
+                let expected_big = (code + 1..=zmax).find(|c| inside(*c));
+                let expected_lit = (zmin..code).rev().find(|c| inside(*c));
+                assert_eq!(expected_big, bigmin(code, zmin, zmax), "bigmin {}", code);
+                assert_eq!(expected_lit, litmax(code, zmin, zmax), "litmax {}", code);
+            }
+        }
+    }
+
+    #[test]
+    fn test_span_runs_cover_span_exactly()
+    {
+        let spans = [
+            MemSpan2D::new_from_usize(0, 0, 8, 8),
+            MemSpan2D::new_from_usize(1, 1, 3, 3),
+            MemSpan2D::new_from_usize(3, 2, 11, 13),
+            MemSpan2D::new_from_usize(5, 0, 6, 16),
+        ];
+
+        for span in spans
+        {
+            let runs = span_runs(&span).unwrap();
+            let mut codes: Vec<u64> = runs.iter().flat_map(|r| *r).collect();
+            assert_eq!(span.area(), Some(codes.len()));
+            assert!(codes.iter().all(|c| span.contains_index2d(&decode(*c))));
+            assert!(runs.windows(2).all(|w| w[0].upper_bound() < w[1].min));
+
+            let len = codes.len();
+            codes.dedup();
+            assert_eq!(len, codes.len());
+        }
+
+        assert_eq!(vec![MemSpan::new_range(0u64..64)], span_runs(&MemSpan2D::new_from_usize(0, 0, 8, 8)).unwrap());
+        assert!(span_runs(&MemSpan2D::new_from_usize(0, 0, 0, 8)).is_none());
+    }
+
+    #[test]
+    fn test_span_rank_unrank()
+    {
+        for span in [MemSpan2D::new_from_usize(0, 0, 5, 7), MemSpan2D::new_from_usize(3, 2, 11, 13)]
+        {
+            let codes: Vec<u64> = span_runs(&span).unwrap().into_iter().flatten().collect();
+            for (position, code) in codes.iter().enumerate()
+            {
+                assert_eq!(Some(decode(*code)), span_unrank(&span, position));
+                assert_eq!(Some(position), span_rank(&span, decode(*code)));
+            }
+
+            assert!(span_unrank(&span, codes.len()).is_none());
+            assert!(span_rank(&span, MemIndex2D::new(span.min_row(), span.col_span.upper_bound())).is_none());
+        }
+
+        //the whole code space, where the runs can not be listed
+        let limit: usize = u32::MAX as usize;
+        let all = MemSpan2D::new_from_usize(0, 0, limit + 1, limit + 1);
+        assert_eq!(Some(MemIndex2D::new(limit, limit)), span_unrank(&all, usize::MAX));
+        assert_eq!(Some(usize::MAX), span_rank(&all, MemIndex2D::new(limit, limit)));
+    }
+
+    #[test]
+    fn test_span_runs_at_the_coordinate_limit()
+    {
+        let limit: usize = u32::MAX as usize;
+
+        //the last row ends just before the last code, which is the corner cell
+        let last_row = MemSpan2D::new_from_usize(limit, 0, limit + 1, 4);
+        let runs = span_runs(&last_row).unwrap();
+        assert_eq!(Some(runs.iter().map(|r| r.len() as usize).sum::<usize>()), last_row.area());
+        assert!(runs.iter().flat_map(|r| *r).all(|c| last_row.contains_index2d(&decode(c))));
+
+        //a run through the corner cell would end at 2^64
+        assert!(span_runs(&MemSpan2D::new_from_usize(limit - 1, limit - 1, limit + 1, limit + 1)).is_none());
+        assert!(span_runs(&MemSpan2D::new_from_usize(0, 0, limit + 1, limit + 1)).is_none());
+    }
+}
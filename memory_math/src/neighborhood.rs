@@ -0,0 +1,98 @@
+use crate::memory_index2d::MemIndex2D;
+use crate::memory_offset2d::MemOffset2D;
+use crate::size_2d::Size2D;
+
+///The offsets from a center cell that make up its neighbors, in the order they are visited
+#[derive(Clone)]
+pub struct Neighborhood
+{
+    offsets: Vec<MemOffset2D>,
+    reach: usize,
+}
+
+impl Neighborhood
+{
+    ///Any list of offsets, the center itself is only included if the list has a zero offset
+    pub fn from_offsets(offsets: Vec<MemOffset2D>) -> Self
+    {
+        let reach: usize = offsets.iter().map(|o| o.row.unsigned_abs().max(o.col.unsigned_abs())).max().unwrap_or(0);
+        Neighborhood { offsets, reach }
+    }
+
+    ///Cells within radius steps along rows and columns, without the center, row major
+    pub fn von_neumann(radius: usize) -> Self
+    {
+        Neighborhood::within(radius, |row, col| row.unsigned_abs() + col.unsigned_abs() <= radius)
+    }
+
+    ///Cells of the square of side 2 * radius + 1 around the center, without the center, row major
+    pub fn moore(radius: usize) -> Self
+    {
+        Neighborhood::within(radius, |_, _| true)
+    }
+
+    fn within(radius: usize, keep: impl Fn(isize, isize) -> bool) -> Self
+    {
+        let radius: isize = radius as isize;
+        let offsets: Vec<MemOffset2D> = (-radius..=radius)
+            .flat_map(|row| (-radius..=radius).map(move |col| MemOffset2D::new(row, col)))
+            .filter(|o| (o.row, o.col) != (0, 0) && keep(o.row, o.col))
+            .collect();
+
+        Neighborhood::from_offsets(offsets)
+    }
+
+    pub fn offsets(&self) -> &[MemOffset2D]
+    {
+        &self.offsets
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.offsets.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.offsets.is_empty()
+    }
+
+    ///Largest distance along a single axis any offset reaches
+    pub fn reach(&self) -> usize
+    {
+        self.reach
+    }
+
+    ///True if every neighbor of center lies inside size, so none of them needs a bounds check
+    pub fn is_interior(&self, center: MemIndex2D, size: Size2D) -> bool
+    {
+        center.row >= self.reach
+            && center.col >= self.reach
+            && center.row.checked_add(self.reach).is_some_and(|max| max < size.row_count)
+            && center.col.checked_add(self.reach).is_some_and(|max| max < size.column_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shapes()
+    {
+        assert_eq!(4, Neighborhood::von_neumann(1).len());
+        assert_eq!(12, Neighborhood::von_neumann(2).len());
+        assert_eq!(8, Neighborhood::moore(1).len());
+        assert_eq!(24, Neighborhood::moore(2).len());
+        assert!(Neighborhood::moore(0).is_empty());
+
+        let first: Vec<(isize, isize)> = Neighborhood::von_neumann(1).offsets().iter().map(|o| (o.row, o.col)).collect();
+        assert_eq!(vec![(-1, 0), (0, -1), (0, 1), (1, 0)], first);
+
+        let knight = Neighborhood::from_offsets(vec![MemOffset2D::new(-2, 1), MemOffset2D::new(1, 2)]);
+        assert_eq!(2, knight.reach());
+        assert!(knight.is_interior(MemIndex2D::new(2, 2), Size2D::new(5, 5)));
+        assert!(!knight.is_interior(MemIndex2D::new(2, 3), Size2D::new(5, 5)));
+        assert!(!knight.is_interior(MemIndex2D::new(1, 2), Size2D::new(5, 5)));
+    }
+}
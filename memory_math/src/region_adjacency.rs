@@ -0,0 +1,397 @@
+use crate::memory_index2d::MemIndex2D;
+use crate::memory_span::MemSpan;
+use crate::memory_span2d::MemSpan2D;
+
+///Side of a region
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Side
+{
+    Top,
+    Bottom,
+    Left,
+    Right
+}
+
+impl Side
+{
+    pub fn opposite(&self) -> Side
+    {
+        match self
+        {
+            Side::Top => Side::Bottom,
+            Side::Bottom => Side::Top,
+            Side::Left => Side::Right,
+            Side::Right => Side::Left
+        }
+    }
+}
+
+///How two regions touch
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Contact
+{
+    ///The regions share a stretch of boundary. The segment runs along the side:
+    /// columns for Top/Bottom, rows for Left/Right.
+    Edge { side: Side, segment: MemSpan },
+    ///The regions only meet in a single corner. The index is the lattice point
+    /// between cells, i.e. the upper bound row and column of the upper left region.
+    Corner(MemIndex2D)
+}
+
+///Contact between regions a and b, sides are given from the point of view of a
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RegionEdge
+{
+    pub a: usize,
+    pub b: usize,
+    pub contact: Contact
+}
+
+impl RegionEdge
+{
+    #[inline]
+    pub fn is_corner(&self) -> bool
+    {
+        matches!(self.contact, Contact::Corner(_))
+    }
+
+    pub fn other(&self, region: usize) -> Option<usize>
+    {
+        if region == self.a
+        {
+            return Some(self.b);
+        }
+        else if region == self.b
+        {
+            return Some(self.a);
+        }
+
+        None
+    }
+
+    ///Side of region the contact lies on, None for corners or unrelated regions
+    pub fn side_for(&self, region: usize) -> Option<Side>
+    {
+        let Contact::Edge { side, .. } = self.contact else {
+            return None;
+        };
+
+        if region == self.a
+        {
+            return Some(side);
+        }
+        else if region == self.b
+        {
+            return Some(side.opposite());
+        }
+
+        None
+    }
+}
+
+///Which regions of a set of non overlapping MemSpan2Ds touch, and where
+pub struct RegionAdjacencyGraph
+{
+    spans: Vec<MemSpan2D>,
+    edges: Vec<RegionEdge>,
+    incident: Vec<Vec<usize>>,
+    ///Component of every region, by edges only and by edges and corners
+    components: [Vec<usize>; 2]
+}
+
+impl RegionAdjacencyGraph
+{
+    ///None if any span is empty or two spans overlap
+    pub fn build(spans: Vec<MemSpan2D>) -> Option<Self>
+    {
+        if MemSpan2D::spans_overlap_or_invalid(&spans)
+        {
+            return None;
+        }
+
+        let mut edges: Vec<RegionEdge> = Vec::new();
+        let mut incident: Vec<Vec<usize>> = vec![Vec::new(); spans.len()];
+
+        for a in 0..spans.len()
+        {
+            for b in (a + 1)..spans.len()
+            {
+                if let Some(contact) = Self::contact(&spans[a], &spans[b])
+                {
+                    incident[a].push(edges.len());
+                    incident[b].push(edges.len());
+                    edges.push(RegionEdge { a, b, contact });
+                }
+            }
+        }
+
+        let components = [
+            Self::label_components(spans.len(), &edges, false),
+            Self::label_components(spans.len(), &edges, true)
+        ];
+
+        Some(RegionAdjacencyGraph { spans, edges, incident, components })
+    }
+
+    ///How a touches b, seen from a
+    pub fn contact(a: &MemSpan2D, b: &MemSpan2D) -> Option<Contact>
+    {
+        let side: Side = if a.row_span.upper_bound() == b.min_row()
+        {
+            Side::Bottom
+        }
+        else if b.row_span.upper_bound() == a.min_row()
+        {
+            Side::Top
+        }
+        else if a.col_span.upper_bound() == b.min_column()
+        {
+            Side::Right
+        }
+        else if b.col_span.upper_bound() == a.min_column()
+        {
+            Side::Left
+        }
+        else
+        {
+            return None;
+        };
+
+        let (along_a, along_b) = match side
+        {
+            Side::Top | Side::Bottom => (&a.col_span, &b.col_span),
+            Side::Left | Side::Right => (&a.row_span, &b.row_span)
+        };
+
+        if let Some(segment) = along_a.intersect(along_b).filter(|s| !s.is_empty())
+        {
+            return Some(Contact::Edge { side, segment });
+        }
+
+        //only the corners can still meet
+        let row: usize = match side
+        {
+            Side::Bottom => a.row_span.upper_bound(),
+            Side::Top => a.min_row(),
+            Side::Left | Side::Right => Self::touching_bound(&a.row_span, &b.row_span)?
+        };
+
+        let col: usize = match side
+        {
+            Side::Right => a.col_span.upper_bound(),
+            Side::Left => a.min_column(),
+            Side::Top | Side::Bottom => Self::touching_bound(&a.col_span, &b.col_span)?
+        };
+
+        Some(Contact::Corner(MemIndex2D::new(row, col)))
+    }
+
+    fn touching_bound(a: &MemSpan, b: &MemSpan) -> Option<usize>
+    {
+        if a.upper_bound() == b.min
+        {
+            return Some(b.min);
+        }
+        else if b.upper_bound() == a.min
+        {
+            return Some(a.min);
+        }
+
+        None
+    }
+
+    pub fn spans(&self) -> &[MemSpan2D]
+    {
+        &self.spans
+    }
+
+    pub fn edges(&self) -> &[RegionEdge]
+    {
+        &self.edges
+    }
+
+    ///Every contact of a region, edges and corners
+    pub fn edges_of(&self, region: usize) -> impl Iterator<Item = &RegionEdge>
+    {
+        self.incident.get(region).into_iter().flatten().map(|&e| &self.edges[e])
+    }
+
+    ///Regions sharing a stretch of edge with region
+    pub fn neighbours(&self, region: usize) -> Vec<usize>
+    {
+        self.edges_of(region).filter(|e| !e.is_corner()).filter_map(|e| e.other(region)).collect()
+    }
+
+    ///Regions touching region only in a corner
+    pub fn corner_neighbours(&self, region: usize) -> Vec<usize>
+    {
+        self.edges_of(region).filter(|e| e.is_corner()).filter_map(|e| e.other(region)).collect()
+    }
+
+    ///Groups of connected regions, each sorted, ordered by their lowest region.
+    /// With include_corners regions meeting in a single corner are connected as well.
+    pub fn connected_components(&self, include_corners: bool) -> Vec<Vec<usize>>
+    {
+        let labels: &[usize] = &self.components[include_corners as usize];
+        let mut components: Vec<Vec<usize>> = vec![Vec::new(); labels.iter().max().map_or(0, |&max| max + 1)];
+
+        for (region, &component) in labels.iter().enumerate()
+        {
+            components[component].push(region);
+        }
+
+        components
+    }
+
+    ///Looks up the components labelled when the graph was built, false if either region does not exist
+    pub fn same_component(&self, a: usize, b: usize, include_corners: bool) -> bool
+    {
+        let labels: &[usize] = &self.components[include_corners as usize];
+        matches!((labels.get(a), labels.get(b)), (Some(x), Some(y)) if x == y)
+    }
+
+    ///Union find over the edges, components are numbered in order of their lowest region
+    fn label_components(region_count: usize, edges: &[RegionEdge], include_corners: bool) -> Vec<usize>
+    {
+        let mut parents: Vec<usize> = (0..region_count).collect();
+
+        for edge in edges.iter().filter(|e| include_corners || !e.is_corner())
+        {
+            let root_a: usize = Self::find_root(&mut parents, edge.a);
+            let root_b: usize = Self::find_root(&mut parents, edge.b);
+            parents[root_a.max(root_b)] = root_a.min(root_b);
+        }
+
+        let mut component_of_root: Vec<Option<usize>> = vec![None; region_count];
+        let mut component_count: usize = 0;
+
+        (0..region_count)
+            .map(|region| {
+                let root: usize = Self::find_root(&mut parents, region);
+                *component_of_root[root].get_or_insert_with(|| {
+                    component_count += 1;
+                    component_count - 1
+                })
+            })
+            .collect()
+    }
+
+    fn find_root(parents: &mut [usize], mut region: usize) -> usize
+    {
+        while parents[region] != region
+        {
+            parents[region] = parents[parents[region]];
+            region = parents[region];
+        }
+
+        region
+    }
+
+    ///Repeatedly join regions which line up exactly (same extent along a shared edge)
+    /// into larger rectangles. The result covers the same cells as the input.
+    pub fn merge_aligned(&self) -> Vec<MemSpan2D>
+    {
+        let mut spans: Vec<MemSpan2D> = self.spans.clone();
+        let mut merged: bool = true;
+
+        while merged
+        {
+            merged = false;
+
+            'search: for i in 0..spans.len()
+            {
+                for j in (i + 1)..spans.len()
+                {
+                    if let Some(joined) = spans[i].join_aligned(&spans[j])
+                    {
+                        spans[i] = joined;
+                        spans.swap_remove(j);
+                        merged = true;
+                        break 'search;
+                    }
+                }
+            }
+        }
+
+        spans.sort_by_key(|s| (s.min_row(), s.min_column()));
+        spans
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 0 1 1
+    // 0 0 1 1
+    // 2 3 3 3
+    //       4 4 (row 3, cols 4..6)
+    fn rooms() -> Vec<MemSpan2D>
+    {
+        vec![
+            MemSpan2D::new_from_usize(0, 0, 2, 2),
+            MemSpan2D::new_from_usize(0, 2, 2, 4),
+            MemSpan2D::new_from_usize(2, 0, 3, 1),
+            MemSpan2D::new_from_usize(2, 1, 3, 4),
+            MemSpan2D::new_from_usize(3, 4, 4, 6),
+        ]
+    }
+
+    #[test]
+    fn test_edges_and_segments()
+    {
+        let graph = RegionAdjacencyGraph::build(rooms()).unwrap();
+
+        let edge_01 = graph.edges().iter().find(|e| e.a == 0 && e.b == 1).unwrap();
+        assert_eq!(Contact::Edge { side: Side::Right, segment: MemSpan::new_range(0..2) }, edge_01.contact);
+        assert_eq!(Some(Side::Left), edge_01.side_for(1));
+
+        let edge_03 = graph.edges().iter().find(|e| e.a == 0 && e.b == 3).unwrap();
+        assert_eq!(Contact::Edge { side: Side::Bottom, segment: MemSpan::new_range(1..2) }, edge_03.contact);
+
+        let mut neighbours = graph.neighbours(3);
+        neighbours.sort();
+        assert_eq!(vec![0, 1, 2], neighbours);
+    }
+
+    #[test]
+    fn test_corner_contact()
+    {
+        let graph = RegionAdjacencyGraph::build(rooms()).unwrap();
+
+        assert_eq!(vec![4], graph.corner_neighbours(3));
+        let corner = graph.edges().iter().find(|e| e.a == 3 && e.b == 4).unwrap();
+        assert_eq!(Contact::Corner(MemIndex2D::new(3, 4)), corner.contact);
+        assert_eq!(None, corner.side_for(3));
+
+        let components = graph.connected_components(false);
+        assert_eq!(vec![vec![0, 1, 2, 3], vec![4]], components);
+        assert_eq!(1, graph.connected_components(true).len());
+        assert!(graph.same_component(0, 4, true));
+        assert!(!graph.same_component(0, 4, false));
+        assert!(!graph.same_component(0, 5, true));
+    }
+
+    #[test]
+    fn test_merge_aligned()
+    {
+        let spans = vec![
+            MemSpan2D::new_from_usize(0, 0, 2, 2),
+            MemSpan2D::new_from_usize(0, 2, 2, 4),
+            MemSpan2D::new_from_usize(2, 0, 4, 4),
+            MemSpan2D::new_from_usize(4, 0, 5, 1),
+        ];
+
+        let graph = RegionAdjacencyGraph::build(spans).unwrap();
+        let merged = graph.merge_aligned();
+        assert_eq!(vec![MemSpan2D::new_from_usize(0, 0, 4, 4), MemSpan2D::new_from_usize(4, 0, 5, 1)], merged);
+    }
+
+    #[test]
+    fn test_overlap_rejected()
+    {
+        let spans = vec![MemSpan2D::new_from_usize(0, 0, 2, 2), MemSpan2D::new_from_usize(1, 1, 3, 3)];
+        assert!(RegionAdjacencyGraph::build(spans).is_none());
+    }
+}
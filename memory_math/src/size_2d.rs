@@ -1,132 +1,153 @@
 use std::fmt::Display;
+use crate::mem_coord::MemCoord;
 use crate::memory_index2d::MemIndex2D;
 use crate::memory_span2d::MemSpan2D;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-pub struct Size2D
+pub struct Size2D<I = usize>
 {
-    pub row_count: usize,
-    pub column_count: usize
+    pub row_count: I,
+    pub column_count: I
 }
 
-impl Display for Size2D
+impl<I: MemCoord> Display for Size2D<I>
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Size2D({}, {})", self.row_count, self.column_count)
     }
 }
 
-impl Into<MemSpan2D> for Size2D
+impl<I: MemCoord> From<Size2D<I>> for MemSpan2D<I>
 {
-    fn into(self) -> MemSpan2D
+    fn from(size: Size2D<I>) -> Self
     {
-        MemSpan2D::new_row_columns(self.row_count, self.column_count)
+        MemSpan2D::from_row_columns(size.row_count, size.column_count)
     }
 }
 
 impl Size2D
 {
     pub fn new(row_count: usize, column_count: usize) -> Self {
+        Size2D::from_counts(row_count, column_count)
+    }
+}
+
+impl<I: MemCoord> Size2D<I>
+{
+    ///Constructor for any coordinate width, Size2D::new stays usize so plain literals keep inferring
+    pub fn from_counts(row_count: I, column_count: I) -> Self {
         Size2D {
             row_count,
             column_count
         }
     }
 
+    ///Number of cells, counted in usize so narrow coordinate types cannot overflow.
+    /// None if the count does not fit in usize
     #[inline]
-    pub fn area(&self) -> usize {
-        self.row_count() * self.column_count()
+    pub fn area(&self) -> Option<usize> {
+        self.row_count.to_usize()?.checked_mul(self.column_count.to_usize()?)
     }
 
     #[inline]
-    pub fn len(&self) -> usize {
-        self.area()
+    pub fn is_empty(&self) -> bool {
+        self.row_count == I::ZERO || self.column_count == I::ZERO
     }
 
+    ///Linear index of the last cell, None for an empty size
     #[inline]
-    pub fn max_index(&self) -> usize {
-        self.area() - 1
+    pub fn max_index(&self) -> Option<usize> {
+        self.area()?.checked_sub(1)
     }
 
     #[inline]
-    pub fn max_index2d(&self) -> Option<MemIndex2D>
+    pub fn max_index2d(&self) -> Option<MemIndex2D<I>>
     {
-        let max_row: usize = self.max_row()?;
-        let max_col: usize = self.max_col()?;
+        let max_row: I = self.max_row()?;
+        let max_col: I = self.max_col()?;
         Some(MemIndex2D::new(max_row, max_col))
     }
 
-    pub fn index2d_to_index(&self, index2d: MemIndex2D) -> Option<usize> {
+    ///Row major linear index of index2d, computed in usize
+    pub fn index2d_to_index(&self, index2d: MemIndex2D<I>) -> Option<usize> {
         if index2d.row >= self.row_count() || index2d.col >= self.column_count() {
             return None;
         }
 
-        Some(Size2D::index2d_to_index_unchecked(self.column_count, index2d))
+        index2d.row.to_usize()?.checked_mul(self.column_count.to_usize()?)?.checked_add(index2d.col.to_usize()?)
     }
 
+    ///Row major linear index without bounds or overflow checks, for indexes already known to be inside a buffer
     #[inline]
-    pub fn index2d_to_index_unchecked(column_count: usize, index2d: MemIndex2D) -> usize {
-        index2d.row * column_count + index2d.col
+    pub fn index2d_to_index_unchecked(column_count: I, index2d: MemIndex2D<I>) -> usize {
+        index2d.row.as_usize() * column_count.as_usize() + index2d.col.as_usize()
     }
 
 
-    pub fn row_column_to_index(&self, row: usize, column: usize) -> Option<usize>
+    pub fn row_column_to_index(&self, row: I, column: I) -> Option<usize>
     {
         self.index2d_to_index(MemIndex2D::new(row, column))
     }
 
-    pub fn index_to_index2d(&self, index: usize) -> Option<MemIndex2D> {
-        if index > self.max_index()
+    pub fn index_to_index2d(&self, index: usize) -> Option<MemIndex2D<I>> {
+        if index >= self.area()?
         {
             return None;
         }
 
-        Some(MemIndex2D::new(index / self.column_count(), index % self.column_count()))
+        let column_count: usize = self.column_count.to_usize()?;
+        Some(MemIndex2D::new(I::from_usize(index / column_count)?, I::from_usize(index % column_count)?))
     }
 
-    pub fn index2d_in_bounds(&self, index2d: &MemIndex2D) -> bool {
+    ///Convert to another coordinate width, None if either count does not fit
+    pub fn try_cast<J: MemCoord>(&self) -> Option<Size2D<J>>
+    {
+        Some(Size2D::from_counts(self.row_count.try_cast()?, self.column_count.try_cast()?))
+    }
+
+    pub fn index2d_in_bounds(&self, index2d: &MemIndex2D<I>) -> bool {
         index2d.row < self.row_count() && index2d.col < self.column_count()
     }
 
-    pub fn index2d_in_bounds_exclusive(&self, index2d: &MemIndex2D) -> bool {
+    pub fn index2d_in_bounds_exclusive(&self, index2d: &MemIndex2D<I>) -> bool {
         index2d.row <= self.row_count() && index2d.col <= self.column_count()
     }
 
-    pub fn contains_span2d(&self, span2d: &MemSpan2D) -> bool {
-        self.index2d_in_bounds_exclusive(&span2d.min_absolute_index2d())
+    pub fn contains_span2d(&self, span2d: &MemSpan2D<I>) -> bool {
+        span2d.row_span.upper_bound() <= self.row_count && span2d.col_span.upper_bound() <= self.column_count
     }
 }
 
-pub trait HasSize2D
+pub trait HasSize2D<I: MemCoord = usize>
 {
-    fn row_count(&self) -> usize;
-    fn column_count(&self) -> usize;
+    fn row_count(&self) -> I;
+    fn column_count(&self) -> I;
 
-    fn max_row(&self) -> Option<usize>
+    fn max_row(&self) -> Option<I>
     {
-        self.row_count().checked_sub(1)
+        self.row_count().checked_sub(I::ONE)
     }
     
-    fn max_col(&self) -> Option<usize>
+    fn max_col(&self) -> Option<I>
     {
-        self.column_count().checked_sub(1)
+        self.column_count().checked_sub(I::ONE)
     }
     
     #[inline]
-    fn size(&self) -> Size2D
+    fn size(&self) -> Size2D<I>
     {
-        Size2D::new(self.row_count(), self.column_count())
+        Size2D::from_counts(self.row_count(), self.column_count())
     }
 
 }
 
-impl HasSize2D for Size2D
+impl<I: MemCoord> HasSize2D<I> for Size2D<I>
 {
-    fn row_count(&self) -> usize {
+    fn row_count(&self) -> I {
         self.row_count
     }
 
-    fn column_count(&self) -> usize {
+    fn column_count(&self) -> I {
         self.column_count
     }
 }
@@ -158,4 +179,17 @@ mod tests {
         assert!(size.index_to_index2d(100).is_none());
         assert_eq!(MemIndex2D::origin(), size.index_to_index2d(0).unwrap());
     }
+
+    #[test]
+    fn test_area_and_max_index_are_checked()
+    {
+        assert_eq!(Some(99), Size2D::new(10, 10).max_index());
+        assert_eq!(None, Size2D::new(0, 10).max_index());
+        assert_eq!(Some(0), Size2D::new(10, 0).area());
+
+        let huge = Size2D::new(usize::MAX, 2);
+        assert_eq!(None, huge.area());
+        assert_eq!(None, huge.max_index());
+        assert!(huge.index_to_index2d(0).is_none());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,167 @@
+use std::fmt::Display;
+use crate::memory_index3d::MemIndex3D;
+use crate::memory_span3d::MemSpan3D;
+use crate::size_2d::Size2D;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Size3D
+{
+    pub layer_count: usize,
+    pub row_count: usize,
+    pub column_count: usize
+}
+
+impl Display for Size3D
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Size3D({}, {}, {})", self.layer_count, self.row_count, self.column_count)
+    }
+}
+
+impl From<Size3D> for MemSpan3D
+{
+    fn from(size: Size3D) -> Self
+    {
+        MemSpan3D::new_layers_rows_columns(size.layer_count, size.row_count, size.column_count)
+    }
+}
+
+impl Size3D
+{
+    pub fn new(layer_count: usize, row_count: usize, column_count: usize) -> Self {
+        Size3D {
+            layer_count,
+            row_count,
+            column_count
+        }
+    }
+
+    pub fn from_size2d(layer_count: usize, plane: Size2D) -> Self {
+        Size3D::new(layer_count, plane.row_count, plane.column_count)
+    }
+
+    ///Size of a single layer
+    #[inline]
+    pub fn plane_size(&self) -> Size2D {
+        Size2D::new(self.row_count, self.column_count)
+    }
+
+    #[inline]
+    pub fn plane_area(&self) -> usize {
+        self.row_count * self.column_count
+    }
+
+    #[inline]
+    pub fn volume(&self) -> usize {
+        self.layer_count * self.plane_area()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.volume()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.volume() == 0
+    }
+
+    pub fn max_index3d(&self) -> Option<MemIndex3D>
+    {
+        Some(MemIndex3D::new(
+            self.layer_count.checked_sub(1)?,
+            self.row_count.checked_sub(1)?,
+            self.column_count.checked_sub(1)?
+        ))
+    }
+
+    pub fn index3d_in_bounds(&self, index3d: &MemIndex3D) -> bool {
+        index3d.layer < self.layer_count && index3d.row < self.row_count && index3d.col < self.column_count
+    }
+
+    pub fn index3d_to_index(&self, index3d: MemIndex3D) -> Option<usize> {
+        if !self.index3d_in_bounds(&index3d) {
+            return None;
+        }
+
+        Some(Size3D::index3d_to_index_unchecked(self.row_count, self.column_count, index3d))
+    }
+
+    #[inline]
+    pub fn index3d_to_index_unchecked(row_count: usize, column_count: usize, index3d: MemIndex3D) -> usize {
+        (index3d.layer * row_count + index3d.row) * column_count + index3d.col
+    }
+
+    pub fn index_to_index3d(&self, index: usize) -> Option<MemIndex3D> {
+        if index >= self.volume() {
+            return None;
+        }
+
+        let plane_area: usize = self.plane_area();
+        let in_plane: usize = index % plane_area;
+        Some(MemIndex3D::new(index / plane_area, in_plane / self.column_count, in_plane % self.column_count))
+    }
+
+    ///True if the span lies completely inside the size
+    pub fn contains_span3d(&self, span3d: &MemSpan3D) -> bool {
+        span3d.layer_span.upper_bound() <= self.layer_count
+            && span3d.row_span.upper_bound() <= self.row_count
+            && span3d.col_span.upper_bound() <= self.column_count
+    }
+}
+
+pub trait HasSize3D
+{
+    fn layer_count(&self) -> usize;
+    fn row_count(&self) -> usize;
+    fn column_count(&self) -> usize;
+
+    #[inline]
+    fn size3d(&self) -> Size3D
+    {
+        Size3D::new(self.layer_count(), self.row_count(), self.column_count())
+    }
+
+    #[inline]
+    fn plane_size(&self) -> Size2D
+    {
+        Size2D::new(self.row_count(), self.column_count())
+    }
+}
+
+impl HasSize3D for Size3D
+{
+    fn layer_count(&self) -> usize {
+        self.layer_count
+    }
+
+    fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    fn column_count(&self) -> usize {
+        self.column_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index3d_round_trip()
+    {
+        let size = Size3D::new(3, 4, 5);
+        assert_eq!(60, size.volume());
+
+        for index in 0..size.volume()
+        {
+            let index3d = size.index_to_index3d(index).unwrap();
+            assert_eq!(index, size.index3d_to_index(index3d).unwrap());
+        }
+
+        assert_eq!(Some(MemIndex3D::new(1, 0, 0)), size.index_to_index3d(20));
+        assert!(size.index_to_index3d(60).is_none());
+        assert!(size.index3d_to_index(MemIndex3D::new(0, 4, 0)).is_none());
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use crate::memory_span::MemSpan;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FitStrategy
+{
+    ///Take the lowest free block that can hold the request
+    FirstFit,
+    ///Take the smallest free block that can hold the request
+    BestFit
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SpanFreeError
+{
+    ///The span lies in memory which is already free
+    DoubleFree(MemSpan),
+    ///The span was never handed out by this allocator
+    UnknownSpan(MemSpan)
+}
+
+impl Display for SpanFreeError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self
+        {
+            SpanFreeError::DoubleFree(span) => write!(f, "span {} is already free", span),
+            SpanFreeError::UnknownSpan(span) => write!(f, "span {} was not allocated by this allocator", span)
+        }
+    }
+}
+
+impl std::error::Error for SpanFreeError {}
+
+///Runtime allocator handing out MemSpans from a pool of indexes.
+/// Free blocks are kept sorted and neighbouring blocks are coalesced on free.
+pub struct SpanAllocator
+{
+    pool: MemSpan,
+    strategy: FitStrategy,
+    free_blocks: Vec<MemSpan>,
+    allocated: BTreeMap<usize, usize>
+}
+
+impl SpanAllocator
+{
+    pub fn new(pool: MemSpan, strategy: FitStrategy) -> Self
+    {
+        let mut free_blocks: Vec<MemSpan> = Vec::new();
+        if !pool.is_empty()
+        {
+            free_blocks.push(pool);
+        }
+
+        SpanAllocator
+        {
+            pool,
+            strategy,
+            free_blocks,
+            allocated: BTreeMap::new()
+        }
+    }
+
+    #[inline]
+    pub fn pool(&self) -> MemSpan
+    {
+        self.pool
+    }
+
+    ///Allocate count indexes starting on a multiple of align (a power of two).
+    /// None if the request is empty, the alignment is invalid or no free block is big enough.
+    pub fn alloc(&mut self, count: usize, align: usize) -> Option<MemSpan>
+    {
+        if count == 0 || !align.is_power_of_two()
+        {
+            return None;
+        }
+
+        let candidates = self.free_blocks.iter().enumerate()
+            .filter_map(|(i, block)| Some((i, block, Self::aligned_fit(block, count, align)?)));
+
+        let (block_index, start) = match self.strategy
+        {
+            FitStrategy::FirstFit => candidates.map(|(i, _, start)| (i, start)).next()?,
+            FitStrategy::BestFit => candidates.min_by_key(|(_, block, _)| block.len()).map(|(i, _, start)| (i, start))?
+        };
+
+        let block: MemSpan = self.free_blocks.remove(block_index);
+        let span = MemSpan { min: start, count };
+
+        let trailing = MemSpan::lower_bound_upper_bound(span.upper_bound(), block.upper_bound())?;
+        if !trailing.is_empty()
+        {
+            self.free_blocks.insert(block_index, trailing);
+        }
+
+        let leading = MemSpan::lower_bound_upper_bound(block.min, span.min)?;
+        if !leading.is_empty()
+        {
+            self.free_blocks.insert(block_index, leading);
+        }
+
+        self.allocated.insert(span.min, span.count);
+        Some(span)
+    }
+
+    ///Return a span to the pool, it must be exactly a span handed out by alloc
+    pub fn free(&mut self, span: MemSpan) -> Result<(), SpanFreeError>
+    {
+        if self.allocated.get(&span.min) != Some(&span.count) || span.is_empty()
+        {
+            if !span.is_empty() && self.free_blocks.iter().any(|b| b.min <= span.min && span.upper_bound() <= b.upper_bound())
+            {
+                return Err(SpanFreeError::DoubleFree(span));
+            }
+
+            return Err(SpanFreeError::UnknownSpan(span));
+        }
+
+        self.allocated.remove(&span.min);
+        self.insert_free_block(span);
+        Ok(())
+    }
+
+    ///Extend the pool upwards by additional indexes, None on overflow
+    pub fn grow(&mut self, additional: usize) -> Option<MemSpan>
+    {
+        let pool: MemSpan = self.pool.shift_max_up_checked(additional)?;
+        let added = MemSpan { min: self.pool.upper_bound(), count: additional };
+        self.pool = pool;
+
+        if !added.is_empty()
+        {
+            self.insert_free_block(added);
+        }
+
+        Some(self.pool)
+    }
+
+    pub fn free_total(&self) -> usize
+    {
+        self.free_blocks.iter().map(|b| b.len()).sum()
+    }
+
+    pub fn allocated_total(&self) -> usize
+    {
+        self.allocated.values().sum()
+    }
+
+    pub fn allocation_count(&self) -> usize
+    {
+        self.allocated.len()
+    }
+
+    pub fn largest_free_block(&self) -> Option<MemSpan>
+    {
+        self.free_blocks.iter().copied().max_by_key(|b| b.len())
+    }
+
+    pub fn free_blocks(&self) -> &[MemSpan]
+    {
+        &self.free_blocks
+    }
+
+    fn aligned_fit(block: &MemSpan, count: usize, align: usize) -> Option<usize>
+    {
+        let mask: usize = align - 1;
+        let start: usize = block.min.checked_add(mask)? & !mask;
+        if start.checked_add(count)? > block.upper_bound()
+        {
+            return None;
+        }
+
+        Some(start)
+    }
+
+    fn insert_free_block(&mut self, span: MemSpan)
+    {
+        let mut index: usize = self.free_blocks.partition_point(|b| b.min < span.min);
+        self.free_blocks.insert(index, span);
+
+        if index + 1 < self.free_blocks.len() && self.free_blocks[index].upper_bound() == self.free_blocks[index + 1].min
+        {
+            let next: MemSpan = self.free_blocks.remove(index + 1);
+            self.free_blocks[index].count += next.count;
+        }
+
+        if index > 0 && self.free_blocks[index - 1].upper_bound() == self.free_blocks[index].min
+        {
+            let current: MemSpan = self.free_blocks.remove(index);
+            index -= 1;
+            self.free_blocks[index].count += current.count;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alloc_free_coalesce()
+    {
+        let mut allocator = SpanAllocator::new(MemSpan::new_range(0..100), FitStrategy::FirstFit);
+        let a = allocator.alloc(10, 1).unwrap();
+        let b = allocator.alloc(20, 1).unwrap();
+        let c = allocator.alloc(30, 1).unwrap();
+
+        assert_eq!(MemSpan::new_range(0..10), a);
+        assert_eq!(MemSpan::new_range(10..30), b);
+        assert_eq!(40, allocator.free_total());
+
+        allocator.free(a).unwrap();
+        allocator.free(c).unwrap();
+        assert_eq!(2, allocator.free_blocks().len());
+
+        allocator.free(b).unwrap();
+        assert_eq!(&[MemSpan::new_range(0..100)], allocator.free_blocks());
+        assert_eq!(0, allocator.allocation_count());
+    }
+
+    #[test]
+    fn test_alignment_keeps_padding_free()
+    {
+        let mut allocator = SpanAllocator::new(MemSpan::new_range(3..67), FitStrategy::FirstFit);
+        let span = allocator.alloc(16, 16).unwrap();
+
+        assert_eq!(16, span.min);
+        assert_eq!(48, allocator.free_total());
+        assert!(allocator.alloc(4, 3).is_none());
+    }
+
+    #[test]
+    fn test_best_fit_picks_smallest_block()
+    {
+        let mut allocator = SpanAllocator::new(MemSpan::new_range(0..100), FitStrategy::BestFit);
+        let spans: Vec<MemSpan> = [40, 5, 10, 5, 40].iter().map(|&c| allocator.alloc(c, 1).unwrap()).collect();
+        allocator.free(spans[0]).unwrap();
+        allocator.free(spans[2]).unwrap();
+
+        let small = allocator.alloc(8, 1).unwrap();
+        assert_eq!(spans[2].min, small.min);
+    }
+
+    #[test]
+    fn test_free_errors()
+    {
+        let mut allocator = SpanAllocator::new(MemSpan::new_range(0..32), FitStrategy::FirstFit);
+        let span = allocator.alloc(8, 1).unwrap();
+        let _other = allocator.alloc(8, 1).unwrap();
+
+        assert_eq!(Ok(()), allocator.free(span));
+        assert_eq!(Err(SpanFreeError::DoubleFree(span)), allocator.free(span));
+
+        let partial = MemSpan::new_range(8..12);
+        assert_eq!(Err(SpanFreeError::UnknownSpan(partial)), allocator.free(partial));
+    }
+
+    #[test]
+    fn test_grow()
+    {
+        let mut allocator = SpanAllocator::new(MemSpan::new_range(0..16), FitStrategy::FirstFit);
+        let _first = allocator.alloc(8, 1).unwrap();
+        assert!(allocator.alloc(16, 1).is_none());
+
+        assert_eq!(MemSpan::new_range(0..32), allocator.grow(16).unwrap());
+        assert_eq!(Some(MemSpan::new_range(8..32)), allocator.largest_free_block());
+        assert_eq!(MemSpan::new_range(8..24), allocator.alloc(16, 1).unwrap());
+    }
+}
@@ -0,0 +1,272 @@
+use crate::memory_span::MemSpan;
+use crate::memory_span2d::MemSpan2D;
+use crate::size_2d::HasSize2D;
+
+///How a span is cut into parts for a pool of workers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartitionStrategy
+{
+    ///Bands of whole rows, one per part
+    RowBands,
+    ///Bands of whole columns, one per part
+    ColumnBands,
+    ///A grid of blocks as close to square as the part count allows
+    Blocks,
+    ///Near square blocks of at most max_cells cells, at least as many blocks as parts
+    CacheBlocks { max_cells: usize },
+}
+
+///Cut span into parts pieces whose lengths differ by at most one, longer pieces first.
+/// None if there are more parts than cells.
+fn even_cuts(span: MemSpan, parts: usize) -> Option<Vec<MemSpan>>
+{
+    if parts == 0 || parts > span.len()
+    {
+        return None;
+    }
+
+    let (base, longer) = (span.len() / parts, span.len() % parts);
+    let mut start: usize = span.min;
+
+    Some((0..parts)
+        .map(|part| {
+            let len: usize = base + usize::from(part < longer);
+            let cut = MemSpan::new_range(start..start + len);
+            start += len;
+            cut
+        })
+        .collect())
+}
+
+///Cut span into parts pieces of similar total cost, every piece keeps at least one index.
+/// Falls back to even cuts when nothing costs anything.
+fn weighted_cuts(span: MemSpan, parts: usize, cost: &dyn Fn(usize) -> f64) -> Option<Vec<MemSpan>>
+{
+    if parts == 0 || parts > span.len()
+    {
+        return None;
+    }
+
+    let mut prefix: Vec<f64> = Vec::with_capacity(span.len() + 1);
+    prefix.push(0.0);
+    for index in span
+    {
+        prefix.push(prefix.last().unwrap() + cost(index).max(0.0));
+    }
+
+    let total: f64 = *prefix.last().unwrap();
+    if total <= 0.0
+    {
+        return even_cuts(span, parts);
+    }
+
+    let mut bounds: Vec<usize> = vec![0];
+    for part in 1..parts
+    {
+        let target: f64 = total * part as f64 / parts as f64;
+        let above: usize = prefix.partition_point(|sum| *sum < target);
+        //prefer whichever boundary lands closer to the target
+        let nearest: usize = if above > 0 && target - prefix[above - 1] <= prefix[above] - target { above - 1 } else { above };
+
+        //leave room for one index in this part and every following part
+        let low: usize = bounds.last().unwrap() + 1;
+        let high: usize = span.len() - (parts - part);
+        bounds.push(nearest.clamp(low, high));
+    }
+    bounds.push(span.len());
+
+    Some(bounds.windows(2).map(|w| MemSpan::new_range(span.min + w[0]..span.min + w[1])).collect())
+}
+
+///Rows x columns of blocks with rows * columns = parts, whose blocks are closest to square
+fn block_grid(span: &MemSpan2D, parts: usize) -> Option<(usize, usize)>
+{
+    let squareness = |(block_rows, block_columns): (usize, usize)| {
+        let height: f64 = span.row_count() as f64 / block_rows as f64;
+        let width: f64 = span.column_count() as f64 / block_columns as f64;
+        (height / width).max(width / height)
+    };
+
+    (1..=parts)
+        .filter(|block_rows| parts.is_multiple_of(*block_rows))
+        .map(|block_rows| (block_rows, parts / block_rows))
+        .filter(|(block_rows, block_columns)| *block_rows <= span.row_count() && *block_columns <= span.column_count())
+        .min_by(|a, b| squareness(*a).total_cmp(&squareness(*b)))
+}
+
+///Rows x columns of blocks no larger than max_cells, refined until there are at least parts blocks
+fn cache_block_grid(span: &MemSpan2D, parts: usize, max_cells: usize) -> Option<(usize, usize)>
+{
+    if max_cells == 0 || span.area().is_some_and(|area| parts > area)
+    {
+        return None;
+    }
+
+    let side: usize = max_cells.isqrt().max(1);
+    let block_height: usize = side.min(span.row_count());
+    let block_width: usize = (max_cells / block_height).min(span.column_count());
+
+    let mut block_rows: usize = span.row_count().div_ceil(block_height);
+    let mut block_columns: usize = span.column_count().div_ceil(block_width);
+
+    while block_rows * block_columns < parts
+    {
+        let taller: bool = span.row_count() / block_rows >= span.column_count() / block_columns;
+        if (taller || block_columns == span.column_count()) && block_rows < span.row_count()
+        {
+            block_rows += 1;
+        }
+        else
+        {
+            block_columns += 1;
+        }
+    }
+
+    Some((block_rows, block_columns))
+}
+
+fn grid_spans(row_cuts: Vec<MemSpan>, column_cuts: Vec<MemSpan>) -> Vec<MemSpan2D>
+{
+    row_cuts.iter()
+        .flat_map(|rows| column_cuts.iter().map(move |columns| MemSpan2D { row_span: *rows, col_span: *columns }))
+        .collect()
+}
+
+///Split span into disjoint sub spans covering it exactly, in row major order.
+/// Rows and columns are shared out evenly, so pieces differ by at most one row or column.
+/// None if the span is empty or cannot be cut into that many non empty parts.
+pub fn partition(span: &MemSpan2D, parts: usize, strategy: PartitionStrategy) -> Option<Vec<MemSpan2D>>
+{
+    partition_rows_by(span, parts, strategy, &|row_span, parts| even_cuts(row_span, parts))
+}
+
+///Like partition but rows are shared out by their cost instead of their count,
+/// so expensive rows end up in thinner bands. Column cuts stay even.
+/// None for CacheBlocks, cheap rows would make bands taller than a block may be.
+pub fn partition_weighted(span: &MemSpan2D, parts: usize, strategy: PartitionStrategy, row_cost: &dyn Fn(usize) -> f64) -> Option<Vec<MemSpan2D>>
+{
+    if matches!(strategy, PartitionStrategy::CacheBlocks { .. })
+    {
+        return None;
+    }
+
+    partition_rows_by(span, parts, strategy, &|row_span, parts| weighted_cuts(row_span, parts, row_cost))
+}
+
+fn partition_rows_by(span: &MemSpan2D, parts: usize, strategy: PartitionStrategy, cut_rows: &dyn Fn(MemSpan, usize) -> Option<Vec<MemSpan>>) -> Option<Vec<MemSpan2D>>
+{
+    if !span.valid()
+    {
+        return None;
+    }
+
+    let (block_rows, block_columns) = match strategy
+    {
+        PartitionStrategy::RowBands => (parts, 1),
+        PartitionStrategy::ColumnBands => (1, parts),
+        PartitionStrategy::Blocks => block_grid(span, parts)?,
+        PartitionStrategy::CacheBlocks { max_cells } => cache_block_grid(span, parts, max_cells)?,
+    };
+
+    let row_cuts: Vec<MemSpan> = cut_rows(span.row_span, block_rows)?;
+    let column_cuts: Vec<MemSpan> = even_cuts(span.col_span, block_columns)?;
+    Some(grid_spans(row_cuts, column_cuts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_exact_cover(span: &MemSpan2D, parts: &[MemSpan2D])
+    {
+        assert!(!MemSpan2D::spans_overlap_or_invalid(parts), "span {}", span);
+        assert!(parts.iter().all(|p| p.intersect(span).as_ref() == Some(p)), "span {}", span);
+        assert_eq!(span.area(), parts.iter().map(|p| p.area()).sum::<Option<usize>>(), "span {}", span);
+    }
+
+    fn spread(values: impl Iterator<Item = usize>) -> usize
+    {
+        let values: Vec<usize> = values.collect();
+        values.iter().max().unwrap() - values.iter().min().unwrap()
+    }
+
+    #[test]
+    fn test_strategies_cover_and_balance()
+    {
+        let spans = [MemSpan2D::new_from_usize(3, 5, 40, 22), MemSpan2D::new_from_usize(0, 0, 7, 7), MemSpan2D::new_from_usize(10, 100, 11, 164)];
+
+        for span in spans
+        {
+            for parts in [1, 2, 3, 4, 6, 7]
+            {
+                if let Some(bands) = partition(&span, parts, PartitionStrategy::RowBands)
+                {
+                    assert_eq!(parts, bands.len());
+                    assert_exact_cover(&span, &bands);
+                    assert!(spread(bands.iter().map(|b| b.row_count())) <= 1);
+                }
+
+                let bands = partition(&span, parts, PartitionStrategy::ColumnBands).unwrap();
+                assert_eq!(parts, bands.len());
+                assert_exact_cover(&span, &bands);
+                assert!(spread(bands.iter().map(|b| b.column_count())) <= 1);
+
+                if let Some(blocks) = partition(&span, parts, PartitionStrategy::Blocks)
+                {
+                    assert_eq!(parts, blocks.len());
+                    assert_exact_cover(&span, &blocks);
+                    assert!(spread(blocks.iter().map(|b| b.row_count())) <= 1);
+                    assert!(spread(blocks.iter().map(|b| b.column_count())) <= 1);
+                }
+
+                let cache_blocks = partition(&span, parts, PartitionStrategy::CacheBlocks { max_cells: 32 }).unwrap();
+                assert!(cache_blocks.len() >= parts);
+                assert!(cache_blocks.iter().all(|b| b.area().is_some_and(|area| area <= 32)));
+                assert_exact_cover(&span, &cache_blocks);
+            }
+        }
+
+        //a single row cannot be cut into row bands
+        assert!(partition(&MemSpan2D::new_from_usize(10, 100, 11, 164), 2, PartitionStrategy::RowBands).is_none());
+        assert!(partition(&MemSpan2D::new_from_usize(0, 0, 4, 4), 0, PartitionStrategy::ColumnBands).is_none());
+    }
+
+    #[test]
+    fn test_blocks_prefer_square()
+    {
+        let span = MemSpan2D::new_from_usize(0, 0, 30, 40);
+        let blocks = partition(&span, 12, PartitionStrategy::Blocks).unwrap();
+        assert_eq!((10, 10), (blocks[0].row_count(), blocks[0].column_count()));
+
+        let wide = MemSpan2D::new_from_usize(0, 0, 10, 160);
+        let blocks = partition(&wide, 4, PartitionStrategy::Blocks).unwrap();
+        assert!(blocks.iter().all(|b| b.row_count() == 10 && b.column_count() == 40));
+
+        let cache_blocks = partition(&wide, 1, PartitionStrategy::CacheBlocks { max_cells: 64 }).unwrap();
+        assert!(cache_blocks.iter().all(|b| b.row_count() == 5 || b.row_count() == 10));
+        assert!(cache_blocks.iter().all(|b| b.area().is_some_and(|area| area <= 64)));
+    }
+
+    #[test]
+    fn test_weighted_rows()
+    {
+        //the bottom half costs three times as much per row
+        let span = MemSpan2D::new_from_usize(4, 0, 20, 8);
+        let cost = |row: usize| if row >= 12 { 3.0 } else { 1.0 };
+
+        let bands = partition_weighted(&span, 2, PartitionStrategy::RowBands, &cost).unwrap();
+        assert_exact_cover(&span, &bands);
+        assert_eq!(MemSpan2D::new_from_usize(4, 0, 15, 8), bands[0]);
+
+        let blocks = partition_weighted(&span, 4, PartitionStrategy::Blocks, &cost).unwrap();
+        assert_exact_cover(&span, &blocks);
+        assert!(blocks[0].row_count() > blocks[2].row_count());
+
+        //free rows fall back to even bands
+        let even = partition_weighted(&span, 4, PartitionStrategy::RowBands, &|_| 0.0).unwrap();
+        assert_eq!(partition(&span, 4, PartitionStrategy::RowBands).unwrap(), even);
+
+        //weighted bands ignore the block height, so they could exceed max_cells
+        assert!(partition_weighted(&span, 4, PartitionStrategy::CacheBlocks { max_cells: 16 }, &cost).is_none());
+    }
+}
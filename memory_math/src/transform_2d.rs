@@ -0,0 +1,182 @@
+use crate::memory_index2d::MemIndex2D;
+use crate::memory_span2d::MemSpan2D;
+use crate::size_2d::Size2D;
+
+///One of the eight symmetries of a rectangle (the dihedral group D4).
+/// Rotations are clockwise, FlipHorizontal mirrors columns and FlipVertical mirrors rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Transform2D
+{
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+    AntiTranspose,
+}
+
+impl Transform2D
+{
+    pub const ALL: [Transform2D; 8] = [
+        Transform2D::Identity,
+        Transform2D::Rotate90,
+        Transform2D::Rotate180,
+        Transform2D::Rotate270,
+        Transform2D::FlipHorizontal,
+        Transform2D::FlipVertical,
+        Transform2D::Transpose,
+        Transform2D::AntiTranspose,
+    ];
+
+    ///The transform as a transpose followed by mirroring the rows and then the columns of the result
+    pub fn parts(&self) -> (bool, bool, bool)
+    {
+        match self
+        {
+            Transform2D::Identity => (false, false, false),
+            Transform2D::Rotate90 => (true, false, true),
+            Transform2D::Rotate180 => (false, true, true),
+            Transform2D::Rotate270 => (true, true, false),
+            Transform2D::FlipHorizontal => (false, false, true),
+            Transform2D::FlipVertical => (false, true, false),
+            Transform2D::Transpose => (true, false, false),
+            Transform2D::AntiTranspose => (true, true, true),
+        }
+    }
+
+    pub fn from_parts(transpose: bool, flip_rows: bool, flip_columns: bool) -> Self
+    {
+        *Transform2D::ALL.iter().find(|t| t.parts() == (transpose, flip_rows, flip_columns)).unwrap()
+    }
+
+    ///True if rows become columns, so a non square rectangle changes shape
+    #[inline]
+    pub fn swaps_axes(&self) -> bool
+    {
+        self.parts().0
+    }
+
+    ///Size of a rectangle of size once transformed
+    pub fn map_size(&self, size: Size2D) -> Size2D
+    {
+        if self.swaps_axes() { Size2D::new(size.column_count, size.row_count) } else { size }
+    }
+
+    ///Where the cell index2d of a rectangle of size lands, None if it is outside of size
+    pub fn map_index2d(&self, index2d: MemIndex2D, size: Size2D) -> Option<MemIndex2D>
+    {
+        if !size.index2d_in_bounds(&index2d)
+        {
+            return None;
+        }
+
+        let (transpose, flip_rows, flip_columns) = self.parts();
+        let target: Size2D = self.map_size(size);
+        let mut mapped: MemIndex2D = if transpose { MemIndex2D::new(index2d.col, index2d.row) } else { index2d };
+
+        if flip_rows
+        {
+            mapped.row = target.row_count - 1 - mapped.row;
+        }
+        if flip_columns
+        {
+            mapped.col = target.column_count - 1 - mapped.col;
+        }
+
+        Some(mapped)
+    }
+
+    ///Where span2d of a rectangle of size lands, None if it is empty or reaches outside of size
+    pub fn map_span2d(&self, span2d: &MemSpan2D, size: Size2D) -> Option<MemSpan2D>
+    {
+        if !span2d.valid() || !size.contains_span2d(span2d)
+        {
+            return None;
+        }
+
+        //opposite corners stay opposite corners
+        let a: MemIndex2D = self.map_index2d(span2d.min_absolute_index2d(), size)?;
+        let b: MemIndex2D = self.map_index2d(span2d.max_absolute_index2d()?, size)?;
+
+        Some(MemSpan2D::new_from_usize(a.row.min(b.row), a.col.min(b.col), a.row.max(b.row) + 1, a.col.max(b.col) + 1))
+    }
+
+    ///Applying self and then next
+    pub fn then(&self, next: Transform2D) -> Transform2D
+    {
+        let (transpose, flip_rows, flip_columns) = self.parts();
+        let (next_transpose, next_flip_rows, next_flip_columns) = next.parts();
+
+        //a transpose turns earlier row flips into column flips and back
+        let (flip_rows, flip_columns) = if next_transpose { (flip_columns, flip_rows) } else { (flip_rows, flip_columns) };
+        Transform2D::from_parts(transpose ^ next_transpose, flip_rows ^ next_flip_rows, flip_columns ^ next_flip_columns)
+    }
+
+    ///The transform undoing self
+    pub fn inverse(&self) -> Transform2D
+    {
+        let (transpose, flip_rows, flip_columns) = self.parts();
+        if transpose { Transform2D::from_parts(true, flip_columns, flip_rows) } else { *self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_index2d()
+    {
+        let size = Size2D::new(2, 3);
+        let corner = MemIndex2D::new(0, 2);
+        let expected = [
+            (Transform2D::Identity, MemIndex2D::new(0, 2)),
+            (Transform2D::Rotate90, MemIndex2D::new(2, 1)),
+            (Transform2D::Rotate180, MemIndex2D::new(1, 0)),
+            (Transform2D::Rotate270, MemIndex2D::new(0, 0)),
+            (Transform2D::FlipHorizontal, MemIndex2D::new(0, 0)),
+            (Transform2D::FlipVertical, MemIndex2D::new(1, 2)),
+            (Transform2D::Transpose, MemIndex2D::new(2, 0)),
+            (Transform2D::AntiTranspose, MemIndex2D::new(0, 1)),
+        ];
+
+        for (transform, index2d) in expected
+        {
+            assert_eq!(Some(index2d), transform.map_index2d(corner, size), "{:?}", transform);
+        }
+        assert_eq!(Size2D::new(3, 2), Transform2D::Rotate90.map_size(size));
+        assert!(Transform2D::Identity.map_index2d(MemIndex2D::new(2, 0), size).is_none());
+
+        let span = MemSpan2D::new_from_usize(0, 1, 2, 3);
+        assert_eq!(Some(MemSpan2D::new_from_usize(1, 0, 3, 2)), Transform2D::Rotate90.map_span2d(&span, size));
+        assert_eq!(Some(MemSpan2D::new_from_usize(0, 0, 2, 2)), Transform2D::FlipHorizontal.map_span2d(&span, size));
+    }
+
+    #[test]
+    fn test_group_laws()
+    {
+        let size = Size2D::new(3, 5);
+        let cells: Vec<MemIndex2D> = (0..3).flat_map(|row| (0..5).map(move |col| MemIndex2D::new(row, col))).collect();
+
+        for a in Transform2D::ALL
+        {
+            assert_eq!(Transform2D::Identity, a.then(a.inverse()));
+            assert_eq!(Transform2D::Identity, a.inverse().then(a));
+
+            for b in Transform2D::ALL
+            {
+                let composed = a.then(b);
+                for cell in &cells
+                {
+                    let stepwise = b.map_index2d(a.map_index2d(*cell, size).unwrap(), a.map_size(size));
+                    assert_eq!(stepwise, composed.map_index2d(*cell, size), "{:?} then {:?}", a, b);
+                }
+            }
+        }
+
+        assert_eq!(Transform2D::Rotate180, Transform2D::Rotate90.then(Transform2D::Rotate90));
+        assert_eq!(Transform2D::Rotate270, Transform2D::Rotate90.inverse());
+    }
+}
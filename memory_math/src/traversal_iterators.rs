@@ -0,0 +1,747 @@
+use crate::memory_index2d::MemIndex2D;
+use crate::memory_iterators::{sized_traversal_iterator, TraversalIterator, TraversalOrder};
+use crate::memory_span2d::MemSpan2D;
+use crate::morton;
+use crate::size_2d::{HasSize2D, Size2D};
+
+sized_traversal_iterator!(ColumnMajorIterator, ColumnMajorOrder);
+sized_traversal_iterator!(DiagonalIterator, DiagonalOrder);
+sized_traversal_iterator!(AntiDiagonalIterator, AntiDiagonalOrder);
+sized_traversal_iterator!(InwardSpiralIterator, InwardSpiralOrder);
+sized_traversal_iterator!(OutwardSpiralIterator, OutwardSpiralOrder);
+sized_traversal_iterator!(HilbertIterator, HilbertOrder);
+sized_traversal_iterator!(MortonIterator, MortonOrder, fallible);
+
+pub type TileBlockedIterator = TraversalIterator<TileBlockedOrder>;
+
+impl TraversalIterator<TileBlockedOrder>
+{
+    ///None if the tile size is empty
+    pub fn new(extents: MemSpan2D, tile_size: Size2D) -> Option<Self>
+    {
+        let order = TileBlockedOrder::new(extents.size(), tile_size)?;
+        TraversalIterator::with_order(extents, order)
+    }
+}
+
+///Top to bottom down each column, columns left to right
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnMajorOrder
+{
+    size: Size2D
+}
+
+impl ColumnMajorOrder
+{
+    pub fn new(size: Size2D) -> Self
+    {
+        ColumnMajorOrder { size }
+    }
+}
+
+impl TraversalOrder for ColumnMajorOrder
+{
+    fn size(&self) -> Size2D
+    {
+        self.size
+    }
+
+    fn unrank(&self, position: usize) -> Option<MemIndex2D>
+    {
+        if position >= self.len()
+        {
+            return None;
+        }
+
+        Some(MemIndex2D::new(position % self.size.row_count, position / self.size.row_count))
+    }
+
+    fn rank(&self, index2d: MemIndex2D) -> Option<usize>
+    {
+        if !self.size.index2d_in_bounds(&index2d)
+        {
+            return None;
+        }
+
+        Some(index2d.col * self.size.row_count + index2d.row)
+    }
+}
+
+///Cells with row + col < diagonal in a rows x columns rectangle
+fn cells_before_anti_diagonal(rows: usize, columns: usize, diagonal: usize) -> usize
+{
+    let triangle = |n: usize| n * (n + 1) / 2;
+    triangle(diagonal) + triangle(diagonal.saturating_sub(rows + columns))
+        - triangle(diagonal.saturating_sub(rows)) - triangle(diagonal.saturating_sub(columns))
+}
+
+///Lines of constant row + col starting at the top left corner,
+/// each line walked from its top right end down to its bottom left end
+#[derive(Clone, Debug, PartialEq)]
+pub struct AntiDiagonalOrder
+{
+    size: Size2D
+}
+
+impl AntiDiagonalOrder
+{
+    pub fn new(size: Size2D) -> Self
+    {
+        AntiDiagonalOrder { size }
+    }
+}
+
+impl TraversalOrder for AntiDiagonalOrder
+{
+    fn size(&self) -> Size2D
+    {
+        self.size
+    }
+
+    fn unrank(&self, position: usize) -> Option<MemIndex2D>
+    {
+        if position >= self.len()
+        {
+            return None;
+        }
+
+        let (rows, columns) = (self.size.row_count, self.size.column_count);
+
+        //largest diagonal whose first cell is at or before position
+        let (mut low, mut high) = (0usize, rows + columns - 1);
+        while high - low > 1
+        {
+            let mid = low + (high - low) / 2;
+            if cells_before_anti_diagonal(rows, columns, mid) <= position { low = mid; } else { high = mid; }
+        }
+
+        let offset: usize = position - cells_before_anti_diagonal(rows, columns, low);
+        let row: usize = low.saturating_sub(columns - 1) + offset;
+        Some(MemIndex2D::new(row, low - row))
+    }
+
+    fn rank(&self, index2d: MemIndex2D) -> Option<usize>
+    {
+        if !self.size.index2d_in_bounds(&index2d)
+        {
+            return None;
+        }
+
+        let (rows, columns) = (self.size.row_count, self.size.column_count);
+        let diagonal: usize = index2d.row + index2d.col;
+        Some(cells_before_anti_diagonal(rows, columns, diagonal) + index2d.row - diagonal.saturating_sub(columns - 1))
+    }
+}
+
+///Lines of constant col - row starting at the top right corner,
+/// each line walked from its top left end down to its bottom right end
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiagonalOrder
+{
+    mirrored: AntiDiagonalOrder
+}
+
+impl DiagonalOrder
+{
+    pub fn new(size: Size2D) -> Self
+    {
+        DiagonalOrder { mirrored: AntiDiagonalOrder::new(size) }
+    }
+}
+
+impl TraversalOrder for DiagonalOrder
+{
+    fn size(&self) -> Size2D
+    {
+        self.mirrored.size
+    }
+
+    fn unrank(&self, position: usize) -> Option<MemIndex2D>
+    {
+        let mirrored: MemIndex2D = self.mirrored.unrank(position)?;
+        Some(MemIndex2D::new(mirrored.row, self.mirrored.size.column_count - 1 - mirrored.col))
+    }
+
+    fn rank(&self, index2d: MemIndex2D) -> Option<usize>
+    {
+        let mirrored_col: usize = self.mirrored.size.column_count.checked_sub(index2d.col + 1)?;
+        self.mirrored.rank(MemIndex2D::new(index2d.row, mirrored_col))
+    }
+}
+
+///Clockwise from the top left corner, ring by ring towards the center
+#[derive(Clone, Debug, PartialEq)]
+pub struct InwardSpiralOrder
+{
+    size: Size2D
+}
+
+impl InwardSpiralOrder
+{
+    pub fn new(size: Size2D) -> Self
+    {
+        InwardSpiralOrder { size }
+    }
+
+    ///Cells on the rings outside of ring
+    fn cells_before_ring(&self, ring: usize) -> usize
+    {
+        let inner_rows: usize = self.size.row_count.saturating_sub(2 * ring);
+        let inner_columns: usize = self.size.column_count.saturating_sub(2 * ring);
+        self.len() - inner_rows * inner_columns
+    }
+}
+
+impl TraversalOrder for InwardSpiralOrder
+{
+    fn size(&self) -> Size2D
+    {
+        self.size
+    }
+
+    fn unrank(&self, position: usize) -> Option<MemIndex2D>
+    {
+        if position >= self.len()
+        {
+            return None;
+        }
+
+        let ring_count: usize = self.size.row_count.min(self.size.column_count).div_ceil(2);
+        let (mut low, mut high) = (0usize, ring_count);
+        while high - low > 1
+        {
+            let mid = low + (high - low) / 2;
+            if self.cells_before_ring(mid) <= position { low = mid; } else { high = mid; }
+        }
+
+        let ring: usize = low;
+        let offset: usize = position - self.cells_before_ring(ring);
+        let height: usize = self.size.row_count - 2 * ring;
+        let width: usize = self.size.column_count - 2 * ring;
+
+        let (row, col) = if offset < width {
+            (0, offset)
+        } else if offset < width + height - 1 {
+            (offset - width + 1, width - 1)
+        } else if offset < 2 * width + height - 2 {
+            (height - 1, width - 2 - (offset + 1 - width - height))
+        } else {
+            (height - 2 - (offset + 2 - 2 * width - height), 0)
+        };
+
+        Some(MemIndex2D::new(ring + row, ring + col))
+    }
+
+    fn rank(&self, index2d: MemIndex2D) -> Option<usize>
+    {
+        if !self.size.index2d_in_bounds(&index2d)
+        {
+            return None;
+        }
+
+        let (rows, columns) = (self.size.row_count, self.size.column_count);
+        let ring: usize = index2d.row.min(index2d.col).min(rows - 1 - index2d.row).min(columns - 1 - index2d.col);
+        let height: usize = rows - 2 * ring;
+        let width: usize = columns - 2 * ring;
+        let (row, col) = (index2d.row - ring, index2d.col - ring);
+
+        let offset: usize = if row == 0 {
+            col
+        } else if col == width - 1 {
+            width - 1 + row
+        } else if row == height - 1 {
+            width + height - 2 + (width - 1 - col)
+        } else {
+            2 * width + height - 3 + (height - 1 - row)
+        };
+
+        Some(self.cells_before_ring(ring) + offset)
+    }
+}
+
+///The inward spiral walked backwards, from the center out to the top left corner
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutwardSpiralOrder
+{
+    inward: InwardSpiralOrder
+}
+
+impl OutwardSpiralOrder
+{
+    pub fn new(size: Size2D) -> Self
+    {
+        OutwardSpiralOrder { inward: InwardSpiralOrder::new(size) }
+    }
+}
+
+impl TraversalOrder for OutwardSpiralOrder
+{
+    fn size(&self) -> Size2D
+    {
+        self.inward.size
+    }
+
+    fn unrank(&self, position: usize) -> Option<MemIndex2D>
+    {
+        self.inward.unrank(self.len().checked_sub(position + 1)?)
+    }
+
+    fn rank(&self, index2d: MemIndex2D) -> Option<usize>
+    {
+        Some(self.len() - 1 - self.inward.rank(index2d)?)
+    }
+}
+
+///Generalized Hilbert curve, continuous on any rectangle and the classic curve on power of two squares.
+/// Follows the gilbert construction by J. Cerveny, starting at the origin along the longer side.
+/// An odd longer side with an even shorter side forces a single diagonal step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HilbertOrder
+{
+    size: Size2D
+}
+
+///A gilbert sub rectangle at (x, y) spanning the major axis (a) and the minor axis (b).
+/// x runs along columns and y along rows.
+#[derive(Clone, Copy)]
+struct GilbertBlock
+{
+    x: isize,
+    y: isize,
+    ax: isize,
+    ay: isize,
+    bx: isize,
+    by: isize
+}
+
+impl GilbertBlock
+{
+    fn major_len(&self) -> isize
+    {
+        (self.ax + self.ay).abs()
+    }
+
+    fn minor_len(&self) -> isize
+    {
+        (self.bx + self.by).abs()
+    }
+
+    fn len(&self) -> isize
+    {
+        self.major_len() * self.minor_len()
+    }
+
+    ///Steps along the major and minor axes from the block start to (x, y), None if outside the block
+    fn local(&self, x: isize, y: isize) -> Option<(isize, isize)>
+    {
+        let (dx, dy) = (x - self.x, y - self.y);
+        let major: isize = dx * self.ax.signum() + dy * self.ay.signum();
+        let minor: isize = dx * self.bx.signum() + dy * self.by.signum();
+        let inside: bool = (0..self.major_len()).contains(&major) && (0..self.minor_len()).contains(&minor);
+        inside.then_some((major, minor))
+    }
+
+    ///The two or three blocks the curve visits in order, all None once the block is a single line.
+    /// A fixed array so rank and unrank do not allocate at every level
+    fn split(&self) -> [Option<GilbertBlock>; 3]
+    {
+        let GilbertBlock { x, y, ax, ay, bx, by } = *self;
+        let (w, h) = (self.major_len(), self.minor_len());
+        if w == 1 || h == 1
+        {
+            return [None; 3];
+        }
+
+        let (dax, day) = (ax.signum(), ay.signum());
+        let (dbx, dby) = (bx.signum(), by.signum());
+        let (mut ax2, mut ay2) = (ax.div_euclid(2), ay.div_euclid(2));
+        let (mut bx2, mut by2) = (bx.div_euclid(2), by.div_euclid(2));
+
+        if 2 * w > 3 * h
+        {
+            if (ax2 + ay2).abs() % 2 != 0 && w > 2
+            {
+                ax2 += dax;
+                ay2 += day;
+            }
+
+            return [
+                Some(GilbertBlock { x, y, ax: ax2, ay: ay2, bx, by }),
+                Some(GilbertBlock { x: x + ax2, y: y + ay2, ax: ax - ax2, ay: ay - ay2, bx, by }),
+                None,
+            ];
+        }
+
+        if (bx2 + by2).abs() % 2 != 0 && h > 2
+        {
+            bx2 += dbx;
+            by2 += dby;
+        }
+
+        [
+            Some(GilbertBlock { x, y, ax: bx2, ay: by2, bx: ax2, by: ay2 }),
+            Some(GilbertBlock { x: x + bx2, y: y + by2, ax, ay, bx: bx - bx2, by: by - by2 }),
+            Some(GilbertBlock {
+                x: x + (ax - dax) + (bx2 - dbx),
+                y: y + (ay - day) + (by2 - dby),
+                ax: -bx2,
+                ay: -by2,
+                bx: -(ax - ax2),
+                by: -(ay - ay2)
+            }),
+        ]
+    }
+}
+
+impl HilbertOrder
+{
+    pub fn new(size: Size2D) -> Self
+    {
+        HilbertOrder { size }
+    }
+
+    fn root(&self) -> Option<GilbertBlock>
+    {
+        let width: isize = isize::try_from(self.size.column_count).ok()?;
+        let height: isize = isize::try_from(self.size.row_count).ok()?;
+        Some(if width >= height {
+            GilbertBlock { x: 0, y: 0, ax: width, ay: 0, bx: 0, by: height }
+        } else {
+            GilbertBlock { x: 0, y: 0, ax: 0, ay: height, bx: width, by: 0 }
+        })
+    }
+}
+
+impl TraversalOrder for HilbertOrder
+{
+    fn size(&self) -> Size2D
+    {
+        self.size
+    }
+
+    fn unrank(&self, position: usize) -> Option<MemIndex2D>
+    {
+        if position >= self.len()
+        {
+            return None;
+        }
+
+        let mut block: GilbertBlock = self.root()?;
+        let mut step: isize = isize::try_from(position).ok()?;
+
+        loop
+        {
+            let children: [Option<GilbertBlock>; 3] = block.split();
+            if children[0].is_none()
+            {
+                //a single line, walk along whichever axis is longer than one
+                let (dx, dy) = if block.minor_len() == 1 { (block.ax.signum(), block.ay.signum()) } else { (block.bx.signum(), block.by.signum()) };
+                let row: usize = usize::try_from(block.y + dy * step).ok()?;
+                let col: usize = usize::try_from(block.x + dx * step).ok()?;
+                return Some(MemIndex2D::new(row, col));
+            }
+
+            for child in children.into_iter().flatten()
+            {
+                if step < child.len()
+                {
+                    block = child;
+                    break;
+                }
+                step -= child.len();
+            }
+        }
+    }
+
+    fn rank(&self, index2d: MemIndex2D) -> Option<usize>
+    {
+        if !self.size.index2d_in_bounds(&index2d)
+        {
+            return None;
+        }
+
+        let (x, y) = (isize::try_from(index2d.col).ok()?, isize::try_from(index2d.row).ok()?);
+        let mut block: GilbertBlock = self.root()?;
+        let mut start: isize = 0;
+
+        loop
+        {
+            let children: [Option<GilbertBlock>; 3] = block.split();
+            if children[0].is_none()
+            {
+                let (major, minor) = block.local(x, y)?;
+                return usize::try_from(start + major + minor).ok();
+            }
+
+            let mut found: bool = false;
+            for child in children.into_iter().flatten()
+            {
+                if child.local(x, y).is_some()
+                {
+                    block = child;
+                    found = true;
+                    break;
+                }
+                start += child.len();
+            }
+
+            if !found
+            {
+                return None;
+            }
+        }
+    }
+}
+
+///Z-order relative to the rectangle origin, cells outside the rectangle are skipped.
+/// Positions are counted along the quadtree path of a cell, so nothing is stored per cell or per run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MortonOrder
+{
+    size: Size2D
+}
+
+impl MortonOrder
+{
+    ///Sizes needing more than 32 bits per axis produce an empty order
+    pub fn new(size: Size2D) -> Self
+    {
+        let encodable: bool = size.max_index2d().is_some_and(|max| morton::encode(max).is_some());
+        let size: Size2D = if encodable { size } else { Size2D::new(0, 0) };
+        MortonOrder { size }
+    }
+
+    fn span(&self) -> MemSpan2D
+    {
+        self.size.into()
+    }
+}
+
+impl TraversalOrder for MortonOrder
+{
+    fn size(&self) -> Size2D
+    {
+        self.size
+    }
+
+    fn unrank(&self, position: usize) -> Option<MemIndex2D>
+    {
+        if position >= self.len()
+        {
+            return None;
+        }
+
+        morton::span_unrank(&self.span(), position)
+    }
+
+    fn rank(&self, index2d: MemIndex2D) -> Option<usize>
+    {
+        if !self.size.index2d_in_bounds(&index2d)
+        {
+            return None;
+        }
+
+        morton::span_rank(&self.span(), index2d)
+    }
+}
+
+///Row major inside fixed size tiles, tiles visited row major, edge tiles are clipped
+#[derive(Clone, Debug, PartialEq)]
+pub struct TileBlockedOrder
+{
+    size: Size2D,
+    tile_size: Size2D
+}
+
+impl TileBlockedOrder
+{
+    ///None if the tile size is empty
+    pub fn new(size: Size2D, tile_size: Size2D) -> Option<Self>
+    {
+        if tile_size.is_empty()
+        {
+            return None;
+        }
+
+        Some(TileBlockedOrder { size, tile_size })
+    }
+
+    pub fn tile_size(&self) -> Size2D
+    {
+        self.tile_size
+    }
+}
+
+impl TraversalOrder for TileBlockedOrder
+{
+    fn size(&self) -> Size2D
+    {
+        self.size
+    }
+
+    fn unrank(&self, position: usize) -> Option<MemIndex2D>
+    {
+        if position >= self.len()
+        {
+            return None;
+        }
+
+        let columns: usize = self.size.column_count;
+        let band_cells: usize = self.tile_size.row_count * columns;
+        let band: usize = position / band_cells;
+        let band_offset: usize = position % band_cells;
+        let band_height: usize = self.tile_size.row_count.min(self.size.row_count - band * self.tile_size.row_count);
+
+        let tile_cells: usize = band_height * self.tile_size.column_count;
+        let tile: usize = band_offset / tile_cells;
+        let tile_offset: usize = band_offset % tile_cells;
+        let tile_width: usize = self.tile_size.column_count.min(columns - tile * self.tile_size.column_count);
+
+        Some(MemIndex2D::new(
+            band * self.tile_size.row_count + tile_offset / tile_width,
+            tile * self.tile_size.column_count + tile_offset % tile_width
+        ))
+    }
+
+    fn rank(&self, index2d: MemIndex2D) -> Option<usize>
+    {
+        if !self.size.index2d_in_bounds(&index2d)
+        {
+            return None;
+        }
+
+        let (tile_rows, tile_columns) = (self.tile_size.row_count, self.tile_size.column_count);
+        let (band, tile) = (index2d.row / tile_rows, index2d.col / tile_columns);
+        let band_height: usize = tile_rows.min(self.size.row_count - band * tile_rows);
+        let tile_width: usize = tile_columns.min(self.size.column_count - tile * tile_columns);
+
+        Some(band * tile_rows * self.size.column_count
+            + tile * band_height * tile_columns
+            + (index2d.row - band * tile_rows) * tile_width
+            + index2d.col - tile * tile_columns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_iterators::MemoryIterator;
+    use crate::memory_iterators::tests::assert_memory_iterator_conformance;
+    use crate::memory_range_iter::IterateWithMemIndex;
+
+    fn relative(iter: impl Iterator<Item = MemIndex2D>) -> Vec<(usize, usize)>
+    {
+        iter.map(|i| (i.row, i.col)).collect()
+    }
+
+    ///Every cell of span exactly once
+    fn assert_visits_span_once(span: &MemSpan2D, visited: Vec<MemIndex2D>)
+    {
+        assert_eq!(span.area(), Some(visited.len()), "span {}", span);
+        let mut sorted = visited.clone();
+        sorted.sort_by_key(|i| (i.row, i.col));
+        sorted.dedup();
+        assert_eq!(span.area(), Some(sorted.len()), "span {}", span);
+        assert!(visited.iter().all(|i| span.contains_index2d(i)), "span {}", span);
+    }
+
+    #[test]
+    fn test_small_orders()
+    {
+        let span = MemSpan2D::new_row_columns(2, 3);
+        assert_eq!(vec![(0, 0), (1, 0), (0, 1), (1, 1), (0, 2), (1, 2)], relative(ColumnMajorIterator::new(span.clone())));
+        assert_eq!(vec![(0, 0), (0, 1), (1, 0), (0, 2), (1, 1), (1, 2)], relative(AntiDiagonalIterator::new(span.clone())));
+        assert_eq!(vec![(0, 2), (0, 1), (1, 2), (0, 0), (1, 1), (1, 0)], relative(DiagonalIterator::new(span.clone())));
+
+        let square = MemSpan2D::new_row_columns(3, 3);
+        assert_eq!(vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (2, 1), (2, 0), (1, 0), (1, 1)], relative(InwardSpiralIterator::new(square.clone())));
+        assert_eq!(vec![(1, 1), (1, 0), (2, 0), (2, 1), (2, 2), (1, 2), (0, 2), (0, 1), (0, 0)], relative(OutwardSpiralIterator::new(square)));
+
+        let quad = MemSpan2D::new_row_columns(2, 2);
+        assert_eq!(vec![(0, 0), (0, 1), (1, 0), (1, 1)], relative(MortonIterator::new(quad.clone()).unwrap()));
+        assert_eq!(vec![(0, 0), (1, 0), (1, 1), (0, 1)], relative(HilbertIterator::new(quad)));
+
+        let tiled = TileBlockedIterator::new(MemSpan2D::new_row_columns(3, 3), Size2D::new(2, 2)).unwrap();
+        assert_eq!(vec![(0, 0), (0, 1), (1, 0), (1, 1), (0, 2), (1, 2), (2, 0), (2, 1), (2, 2)], relative(tiled));
+        assert!(TileBlockedIterator::new(MemSpan2D::new_row_columns(3, 3), Size2D::new(0, 2)).is_none());
+    }
+
+    #[test]
+    fn test_orders_cover_offset_spans()
+    {
+        let spans = [
+            MemSpan2D::new_from_usize(3, 5, 4, 6),
+            MemSpan2D::new_from_usize(2, 7, 9, 8),
+            MemSpan2D::new_from_usize(10, 1, 17, 12),
+            MemSpan2D::new_from_usize(4, 4, 20, 20),
+            MemSpan2D::new_from_usize(1, 2, 6, 40),
+        ];
+
+        for span in spans
+        {
+            assert_visits_span_once(&span, ColumnMajorIterator::new(span.clone()).collect());
+            assert_visits_span_once(&span, DiagonalIterator::new(span.clone()).collect());
+            assert_visits_span_once(&span, AntiDiagonalIterator::new(span.clone()).collect());
+            assert_visits_span_once(&span, InwardSpiralIterator::new(span.clone()).collect());
+            assert_visits_span_once(&span, OutwardSpiralIterator::new(span.clone()).collect());
+            assert_visits_span_once(&span, HilbertIterator::new(span.clone()).collect());
+            assert_visits_span_once(&span, MortonIterator::new(span.clone()).unwrap().collect());
+            assert_visits_span_once(&span, TileBlockedIterator::new(span.clone(), Size2D::new(4, 3)).unwrap().collect());
+        }
+    }
+
+    #[test]
+    fn test_curve_iterators_conform()
+    {
+        assert_memory_iterator_conformance(ColumnMajorIterator::new);
+        assert_memory_iterator_conformance(DiagonalIterator::new);
+        assert_memory_iterator_conformance(AntiDiagonalIterator::new);
+        assert_memory_iterator_conformance(InwardSpiralIterator::new);
+        assert_memory_iterator_conformance(OutwardSpiralIterator::new);
+        assert_memory_iterator_conformance(HilbertIterator::new);
+        assert_memory_iterator_conformance(|span| MortonIterator::new(span).unwrap());
+        assert_memory_iterator_conformance(|span| TileBlockedIterator::new(span, Size2D::new(4, 3)).unwrap());
+    }
+
+    #[test]
+    fn test_continuous_curves_step_to_neighbours()
+    {
+        let adjacent = |a: &MemIndex2D, b: &MemIndex2D| a.row.abs_diff(b.row) + a.col.abs_diff(b.col) == 1;
+
+        for span in [MemSpan2D::new_from_usize(2, 3, 18, 19), MemSpan2D::new_from_usize(0, 1, 7, 12), MemSpan2D::new_from_usize(5, 5, 29, 9)]
+        {
+            let hilbert: Vec<MemIndex2D> = HilbertIterator::new(span.clone()).collect();
+            assert!(hilbert.windows(2).all(|w| adjacent(&w[0], &w[1])), "span {}", span);
+
+            let spiral: Vec<MemIndex2D> = InwardSpiralIterator::new(span.clone()).collect();
+            assert!(spiral.windows(2).all(|w| adjacent(&w[0], &w[1])), "span {}", span);
+        }
+    }
+
+    #[test]
+    fn test_iterate_with_mem_index()
+    {
+        let span = MemSpan2D::new_from_usize(4, 4, 6, 6);
+        let mut iter = HilbertIterator::new(span.clone());
+        assert_eq!(None, iter.current_index());
+        assert_eq!(Some(MemIndex2D::new(4, 4)), iter.next());
+        assert_eq!(Some(MemIndex2D::new(4, 4)), iter.current_index());
+
+        for (index2d, visited) in MortonIterator::new(span).unwrap().iterate_with_mem_index()
+        {
+            assert_eq!(index2d, visited);
+        }
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_morton_rejects_axes_past_32_bits()
+    {
+        assert!(MortonIterator::new(MemSpan2D::new_row_columns(1, (1 << 32) + 1)).is_none());
+        assert!(MortonIterator::new(MemSpan2D::new_row_columns(1 << 33, 2)).is_none());
+
+        //only the size counts, the span itself may sit anywhere
+        let far = MemSpan2D::new_from_usize(1 << 40, 1 << 40, (1 << 40) + 2, (1 << 40) + 2);
+        assert_eq!(4, MortonIterator::new(far).unwrap().count());
+    }
+}
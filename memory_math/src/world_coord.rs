@@ -0,0 +1,323 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+use crate::memory_index2d::MemIndex2D;
+use crate::memory_offset2d::MemOffset2D;
+use crate::memory_span2d::MemSpan2D;
+use crate::size_2d::{HasSize2D, Size2D};
+
+///Signed, unbounded cell coordinate for maps that grow in every direction.
+/// Also used as the key of a chunk, where it counts chunks instead of cells.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct WorldCoord2D
+{
+    pub row: isize,
+    pub col: isize
+}
+
+impl fmt::Display for WorldCoord2D
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+impl TryFrom<MemIndex2D> for WorldCoord2D
+{
+    type Error = &'static str;
+
+    fn try_from(value: MemIndex2D) -> Result<Self, Self::Error> {
+        match (isize::try_from(value.row), isize::try_from(value.col))
+        {
+            (Ok(row), Ok(col)) => Ok(WorldCoord2D::new(row, col)),
+            _ => Err("MemIndex2D does not fit in a WorldCoord2D")
+        }
+    }
+}
+
+impl TryFrom<WorldCoord2D> for MemIndex2D
+{
+    type Error = &'static str;
+
+    fn try_from(value: WorldCoord2D) -> Result<Self, Self::Error> {
+        if value.row < 0 || value.col < 0
+        {
+            return Err("WorldCoord2D must be positive");
+        }
+
+        Ok(MemIndex2D::new(value.row as usize, value.col as usize))
+    }
+}
+
+impl Add<MemOffset2D> for WorldCoord2D
+{
+    type Output = Option<WorldCoord2D>;
+
+    fn add(self, rhs: MemOffset2D) -> Self::Output {
+        Some(WorldCoord2D::new(self.row.checked_add(rhs.row)?, self.col.checked_add(rhs.col)?))
+    }
+}
+
+impl Sub<MemOffset2D> for WorldCoord2D
+{
+    type Output = Option<WorldCoord2D>;
+
+    fn sub(self, rhs: MemOffset2D) -> Self::Output {
+        Some(WorldCoord2D::new(self.row.checked_sub(rhs.row)?, self.col.checked_sub(rhs.col)?))
+    }
+}
+
+impl Sub for WorldCoord2D
+{
+    type Output = Option<MemOffset2D>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Some(MemOffset2D::new(self.row.checked_sub(rhs.row)?, self.col.checked_sub(rhs.col)?))
+    }
+}
+
+impl WorldCoord2D
+{
+    pub fn new(row: isize, col: isize) -> Self
+    {
+        WorldCoord2D { row, col }
+    }
+
+    pub fn origin() -> Self
+    {
+        WorldCoord2D { row: 0, col: 0 }
+    }
+
+    ///Split a cell coordinate into the chunk holding it and the index inside that chunk.
+    /// Uses floor division, so (-1, -1) lands in chunk (-1, -1) at the chunk's last cell.
+    pub fn to_chunk(&self, chunk_size: &Size2D) -> Option<(WorldCoord2D, MemIndex2D)>
+    {
+        let rows: isize = isize::try_from(chunk_size.row_count).ok().filter(|r| *r > 0)?;
+        let cols: isize = isize::try_from(chunk_size.column_count).ok().filter(|c| *c > 0)?;
+
+        let chunk = WorldCoord2D::new(self.row.div_euclid(rows), self.col.div_euclid(cols));
+        let local = MemIndex2D::new(self.row.rem_euclid(rows) as usize, self.col.rem_euclid(cols) as usize);
+        Some((chunk, local))
+    }
+
+    ///Inverse of to_chunk, None if the local index is outside the chunk or the result overflows
+    pub fn from_chunk(chunk: WorldCoord2D, local: MemIndex2D, chunk_size: &Size2D) -> Option<Self>
+    {
+        if !chunk_size.index2d_in_bounds(&local)
+        {
+            return None;
+        }
+
+        let rows: isize = isize::try_from(chunk_size.row_count).ok()?;
+        let cols: isize = isize::try_from(chunk_size.column_count).ok()?;
+
+        Some(WorldCoord2D::new(
+            chunk.row.checked_mul(rows)?.checked_add(local.row as isize)?,
+            chunk.col.checked_mul(cols)?.checked_add(local.col as isize)?
+        ))
+    }
+}
+
+///Half-open (inclusive min, exclusive upper bound) rectangle of world cells.
+/// The counts stay unsigned, only the position may be negative.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WorldSpan2D
+{
+    pub min: WorldCoord2D,
+    pub size: Size2D
+}
+
+impl HasSize2D for WorldSpan2D
+{
+    fn row_count(&self) -> usize {
+        self.size.row_count
+    }
+
+    fn column_count(&self) -> usize {
+        self.size.column_count
+    }
+}
+
+impl fmt::Display for WorldSpan2D
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({} + {})", self.min, self.size)
+    }
+}
+
+impl Add<MemOffset2D> for WorldSpan2D
+{
+    type Output = Option<WorldSpan2D>;
+
+    fn add(self, rhs: MemOffset2D) -> Self::Output {
+        self.shift(rhs)
+    }
+}
+
+impl Sub<MemOffset2D> for WorldSpan2D
+{
+    type Output = Option<WorldSpan2D>;
+
+    fn sub(self, rhs: MemOffset2D) -> Self::Output {
+        self.shift(-rhs)
+    }
+}
+
+impl WorldSpan2D
+{
+    pub fn new(min: WorldCoord2D, size: Size2D) -> Self
+    {
+        WorldSpan2D { min, size }
+    }
+
+    ///Span from an inclusive min and exclusive upper bound corner, None if the corners are swapped
+    pub fn from_bounds(min: WorldCoord2D, upper_bound: WorldCoord2D) -> Option<Self>
+    {
+        let rows: usize = usize::try_from(upper_bound.row.checked_sub(min.row)?).ok()?;
+        let cols: usize = usize::try_from(upper_bound.col.checked_sub(min.col)?).ok()?;
+        Some(WorldSpan2D::new(min, Size2D::new(rows, cols)))
+    }
+
+    ///Place a memory span in the world with its origin at the given coordinate
+    pub fn from_mem_span2d(origin: WorldCoord2D, span2d: &MemSpan2D) -> Option<Self>
+    {
+        let min: WorldCoord2D = (origin + MemOffset2D::from(span2d.min_absolute_index2d()))?;
+        Some(WorldSpan2D::new(min, span2d.size()))
+    }
+
+    pub fn upper_bound(&self) -> Option<WorldCoord2D>
+    {
+        Some(WorldCoord2D::new(
+            self.min.row.checked_add_unsigned(self.size.row_count)?,
+            self.min.col.checked_add_unsigned(self.size.column_count)?
+        ))
+    }
+
+    pub fn max_coord(&self) -> Option<WorldCoord2D>
+    {
+        if !self.valid()
+        {
+            return None;
+        }
+
+        self.upper_bound()? - MemOffset2D::new(1, 1)
+    }
+
+    ///None if the area does not fit in usize
+    pub fn area(&self) -> Option<usize>
+    {
+        self.size.area()
+    }
+
+    pub fn valid(&self) -> bool
+    {
+        !self.size.is_empty()
+    }
+
+    pub fn contains(&self, coord: &WorldCoord2D) -> bool
+    {
+        self.relative_index2d(coord).is_some()
+    }
+
+    ///Position of a world cell relative to the span min, None if it lies outside the span
+    pub fn relative_index2d(&self, coord: &WorldCoord2D) -> Option<MemIndex2D>
+    {
+        let index2d = MemIndex2D::try_from((*coord - self.min)?).ok()?;
+        self.size.index2d_in_bounds(&index2d).then_some(index2d)
+    }
+
+    ///The span as a MemSpan2D relative to origin, None if it reaches above or left of origin
+    pub fn to_mem_span2d(&self, origin: WorldCoord2D) -> Option<MemSpan2D>
+    {
+        let min: MemIndex2D = MemIndex2D::try_from((self.min - origin)?).ok()?;
+        let upper_bound: MemIndex2D = MemIndex2D::try_from((self.upper_bound()? - origin)?).ok()?;
+        Some(MemSpan2D::new_from_index2d(min, upper_bound))
+    }
+
+    pub fn intersect(&self, other: &WorldSpan2D) -> Option<WorldSpan2D>
+    {
+        let self_upper: WorldCoord2D = self.upper_bound()?;
+        let other_upper: WorldCoord2D = other.upper_bound()?;
+
+        let min = WorldCoord2D::new(self.min.row.max(other.min.row), self.min.col.max(other.min.col));
+        let upper_bound = WorldCoord2D::new(self_upper.row.min(other_upper.row), self_upper.col.min(other_upper.col));
+        WorldSpan2D::from_bounds(min, upper_bound)
+    }
+
+    pub fn overlaps(&self, other: &WorldSpan2D) -> bool
+    {
+        self.intersect(other).is_some_and(|s| s.valid())
+    }
+
+    pub fn shift(&self, shift: MemOffset2D) -> Option<Self>
+    {
+        let shifted = WorldSpan2D::new((self.min + shift)?, self.size);
+        shifted.upper_bound()?;
+        Some(shifted)
+    }
+
+    ///Every chunk touched by the span, expressed as a span of chunk coordinates
+    pub fn chunk_span(&self, chunk_size: &Size2D) -> Option<WorldSpan2D>
+    {
+        let (min_chunk, _) = self.min.to_chunk(chunk_size)?;
+        let (max_chunk, _) = self.max_coord()?.to_chunk(chunk_size)?;
+        WorldSpan2D::from_bounds(min_chunk, (max_chunk + MemOffset2D::new(1, 1))?)
+    }
+
+    ///Cells of the span in row major order
+    pub fn coords(&self) -> impl Iterator<Item = WorldCoord2D> + use<>
+    {
+        let span: WorldSpan2D = *self;
+        let rows = 0..if span.valid() { span.size.row_count } else { 0 };
+        rows.flat_map(move |row| {
+            (0..span.size.column_count).map(move |col| {
+                WorldCoord2D::new(span.min.row + row as isize, span.min.col + col as isize)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_chunk_floor_division()
+    {
+        let chunk_size: Size2D = Size2D::new(16, 8);
+
+        assert_eq!(Some((WorldCoord2D::origin(), MemIndex2D::new(3, 7))), WorldCoord2D::new(3, 7).to_chunk(&chunk_size));
+        assert_eq!(Some((WorldCoord2D::new(-1, -1), MemIndex2D::new(15, 7))), WorldCoord2D::new(-1, -1).to_chunk(&chunk_size));
+        assert_eq!(Some((WorldCoord2D::new(-2, 1), MemIndex2D::new(0, 0))), WorldCoord2D::new(-32, 8).to_chunk(&chunk_size));
+        assert!(WorldCoord2D::origin().to_chunk(&Size2D::new(0, 8)).is_none());
+
+        for coord in [WorldCoord2D::new(-17, 5), WorldCoord2D::new(40, -9), WorldCoord2D::new(-1, 0)]
+        {
+            let (chunk, local) = coord.to_chunk(&chunk_size).unwrap();
+            assert_eq!(Some(coord), WorldCoord2D::from_chunk(chunk, local, &chunk_size));
+        }
+    }
+
+    #[test]
+    fn test_span_across_origin()
+    {
+        let span = WorldSpan2D::from_bounds(WorldCoord2D::new(-3, -2), WorldCoord2D::new(2, 4)).unwrap();
+        assert_eq!(Some(30), span.area());
+        assert!(span.contains(&WorldCoord2D::new(-3, 3)));
+        assert!(!span.contains(&WorldCoord2D::new(2, 0)));
+        assert_eq!(Some(WorldCoord2D::new(1, 3)), span.max_coord());
+        assert_eq!(Some(MemIndex2D::new(3, 2)), span.relative_index2d(&WorldCoord2D::origin()));
+
+        let other = WorldSpan2D::new(WorldCoord2D::origin(), Size2D::new(10, 10));
+        let expected = WorldSpan2D::new(WorldCoord2D::origin(), Size2D::new(2, 4));
+        assert_eq!(Some(expected), span.intersect(&other));
+        assert!(span.intersect(&(other + MemOffset2D::new(0, 4)).unwrap()).is_some_and(|s| !s.valid()));
+
+        let chunks = span.chunk_span(&Size2D::new(4, 4)).unwrap();
+        assert_eq!(WorldSpan2D::new(WorldCoord2D::new(-1, -1), Size2D::new(2, 2)), chunks);
+
+        assert_eq!(Some(MemSpan2D::new_from_usize(0, 1, 5, 7)), span.to_mem_span2d(WorldCoord2D::new(-3, -3)));
+        assert!(span.to_mem_span2d(WorldCoord2D::origin()).is_none());
+        assert_eq!(span.coords().next(), Some(span.min));
+        assert_eq!(30, span.coords().count());
+    }
+}
@@ -0,0 +1,147 @@
+use crate::memory_index2d::MemIndex2D;
+use crate::memory_offset2d::MemOffset2D;
+use crate::memory_span::MemSpan;
+use crate::memory_span2d::MemSpan2D;
+use crate::size_2d::Size2D;
+
+///Position of offset along an axis of extent once wrapped around, None if the axis is empty
+fn wrap_axis(offset: i128, extent: usize) -> Option<usize>
+{
+    if extent == 0
+    {
+        return None;
+    }
+
+    Some(offset.rem_euclid(extent as i128) as usize)
+}
+
+impl Size2D
+{
+    ///Treat the size as a torus, offset lands back inside it from whichever edge it left through.
+    /// None if the size has no cells
+    pub fn wrap_offset2d(&self, offset: MemOffset2D) -> Option<MemIndex2D>
+    {
+        Some(MemIndex2D::new(wrap_axis(offset.row as i128, self.row_count)?, wrap_axis(offset.col as i128, self.column_count)?))
+    }
+
+    ///index2d moved by offset on the torus of self, index2d itself may lie outside of self
+    pub fn wrapping_add(&self, index2d: MemIndex2D, offset: MemOffset2D) -> Option<MemIndex2D>
+    {
+        let row: i128 = index2d.row as i128 + offset.row as i128;
+        let col: i128 = index2d.col as i128 + offset.col as i128;
+        Some(MemIndex2D::new(wrap_axis(row, self.row_count)?, wrap_axis(col, self.column_count)?))
+    }
+}
+
+///An in bounds rectangle of a torus together with where its top left cell sits inside the wrapped region
+#[derive(Clone, Debug, PartialEq)]
+pub struct WrappedPiece
+{
+    pub span2d: MemSpan2D,
+    pub region_index2d: MemIndex2D,
+}
+
+///A rectangle on a torus, it may start anywhere and run over the edges to continue on the opposite side
+#[derive(Clone, Copy)]
+pub struct WrappedSpan2D
+{
+    pub origin: MemOffset2D,
+    pub size: Size2D,
+}
+
+impl WrappedSpan2D
+{
+    pub fn new(origin: MemOffset2D, size: Size2D) -> Self
+    {
+        WrappedSpan2D { origin, size }
+    }
+
+    ///True if the region fits on torus without covering any cell twice
+    pub fn fits(&self, torus: Size2D) -> bool
+    {
+        self.size.row_count <= torus.row_count && self.size.column_count <= torus.column_count
+    }
+
+    ///Where the cell index2d of the region lies on torus
+    pub fn region_index2d_to_torus(&self, index2d: MemIndex2D, torus: Size2D) -> Option<MemIndex2D>
+    {
+        if !self.fits(torus) || !self.size.index2d_in_bounds(&index2d)
+        {
+            return None;
+        }
+
+        torus.wrapping_add(index2d, self.origin)
+    }
+
+    ///Split the region at the edges of torus into up to four in bounds spans, top left piece first.
+    /// None if the region is empty or does not fit on torus
+    pub fn pieces(&self, torus: Size2D) -> Option<Vec<WrappedPiece>>
+    {
+        if self.size.is_empty() || !self.fits(torus)
+        {
+            return None;
+        }
+
+        let rows: Vec<(MemSpan, usize)> = split_axis(self.origin.row, self.size.row_count, torus.row_count)?;
+        let cols: Vec<(MemSpan, usize)> = split_axis(self.origin.col, self.size.column_count, torus.column_count)?;
+
+        Some(rows.iter().flat_map(|(row_span, row)| cols.iter().map(move |(col_span, col)| WrappedPiece {
+            span2d: MemSpan2D { row_span: *row_span, col_span: *col_span },
+            region_index2d: MemIndex2D::new(*row, *col),
+        })).collect())
+    }
+}
+
+///The in bounds runs of len cells starting at start on an axis of extent, each with its position along the run
+fn split_axis(start: isize, len: usize, extent: usize) -> Option<Vec<(MemSpan, usize)>>
+{
+    let first: usize = wrap_axis(start as i128, extent)?;
+    let before_edge: usize = extent - first;
+
+    if len <= before_edge
+    {
+        return Some(vec![(MemSpan::new_range(first..first + len), 0)]);
+    }
+
+    Some(vec![(MemSpan::new_range(first..extent), 0), (MemSpan::new_range(0..len - before_edge), before_edge)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrapping_add()
+    {
+        let torus = Size2D::new(4, 5);
+        assert_eq!(Some(MemIndex2D::new(3, 4)), torus.wrapping_add(MemIndex2D::origin(), MemOffset2D::new(-1, -1)));
+        assert_eq!(Some(MemIndex2D::new(0, 1)), torus.wrapping_add(MemIndex2D::new(3, 4), MemOffset2D::new(1, 2)));
+        assert_eq!(Some(MemIndex2D::new(2, 3)), torus.wrap_offset2d(MemOffset2D::new(-42, 23)));
+        assert_eq!(Some(MemIndex2D::new(3, 2)), torus.wrap_offset2d(MemOffset2D::new(isize::MAX, isize::MIN)));
+        assert!(Size2D::new(0, 5).wrap_offset2d(MemOffset2D::new(1, 1)).is_none());
+    }
+
+    #[test]
+    fn test_pieces()
+    {
+        let torus = Size2D::new(4, 5);
+
+        let inside = WrappedSpan2D::new(MemOffset2D::new(1, 1), Size2D::new(2, 2));
+        assert_eq!(vec![MemSpan2D::new_from_usize(1, 1, 3, 3)], inside.pieces(torus).unwrap().into_iter().map(|p| p.span2d).collect::<Vec<_>>());
+
+        let corner = WrappedSpan2D::new(MemOffset2D::new(-1, 3), Size2D::new(3, 4));
+        let pieces = corner.pieces(torus).unwrap();
+        assert_eq!(4, pieces.len());
+        assert_eq!(WrappedPiece { span2d: MemSpan2D::new_from_usize(3, 3, 4, 5), region_index2d: MemIndex2D::new(0, 0) }, pieces[0]);
+        assert_eq!(WrappedPiece { span2d: MemSpan2D::new_from_usize(3, 0, 4, 2), region_index2d: MemIndex2D::new(0, 2) }, pieces[1]);
+        assert_eq!(WrappedPiece { span2d: MemSpan2D::new_from_usize(0, 3, 2, 5), region_index2d: MemIndex2D::new(1, 0) }, pieces[2]);
+        assert_eq!(WrappedPiece { span2d: MemSpan2D::new_from_usize(0, 0, 2, 2), region_index2d: MemIndex2D::new(1, 2) }, pieces[3]);
+        assert_eq!(12, pieces.iter().map(|p| p.span2d.row_span.len() * p.span2d.col_span.len()).sum::<usize>());
+        assert_eq!(Some(MemIndex2D::new(1, 0)), corner.region_index2d_to_torus(MemIndex2D::new(2, 2), torus));
+
+        //the whole torus seen from an offset splits along both edges but still covers every cell once
+        assert_eq!(4, WrappedSpan2D::new(MemOffset2D::new(2, 2), torus).pieces(torus).unwrap().len());
+        assert!(WrappedSpan2D::new(MemOffset2D::new(0, 0), Size2D::new(5, 1)).pieces(torus).is_none());
+        assert!(WrappedSpan2D::new(MemOffset2D::new(0, 0), Size2D::new(0, 1)).pieces(torus).is_none());
+    }
+}